@@ -1,11 +1,11 @@
 use sp_core::{Pair, Public, sr25519};
 use node_superorganism_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	ProposalConfig, SudoConfig, SystemConfig, WASM_BINARY, Signature
+	AccountId, AuraConfig, BalancesConfig, CommunityIdentityConfig, CouncilConfig, GenesisConfig, GrandpaConfig,
+	ProposalConfig, SudoConfig, SystemConfig, TreasuryId, WASM_BINARY, Signature
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{Verify, IdentifyAccount};
+use sp_runtime::traits::{AccountIdConversion, Verify, IdentifyAccount};
 use sc_service::ChainType;
 
 // The URL for the telemetry server.
@@ -140,8 +140,11 @@ fn testnet_genesis(
 			changes_trie_config: Default::default(),
 		}),
 		pallet_balances: Some(BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60)).collect(),
+			// Configure endowed accounts and the governance pot with initial balance of 1 << 60.
+			balances: endowed_accounts.iter().cloned()
+				.chain(std::iter::once(TreasuryId::get().into_account()))
+				.map(|k|(k, 1 << 60))
+				.collect(),
 		}),
 		pallet_aura: Some(AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
@@ -151,10 +154,20 @@ fn testnet_genesis(
 		}),
 		pallet_sudo: Some(SudoConfig {
 			// Assign network admin rights.
-			key: root_key,
+			key: root_key.clone(),
+		}),
+		pallet_community_identity: Some(CommunityIdentityConfig {
+			// Grant the genesis council members an identity level without peer review
+			bootstrap: vec![(root_key.clone(), 3)],
+			identities: vec![],
+		}),
+		pallet_council: Some(CouncilConfig {
+			members: vec![root_key],
 		}),
 		pallet_proposal: Some(ProposalConfig {
-			state: Default::default()
+			state: Default::default(),
+			round: 0,
+			proposals: vec![],
 		}),
 	}
 }
@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use node_superorganism_runtime::{opaque::Block, AccountId, Balance, Index};
+use node_superorganism_runtime::{opaque::Block, pallet_community_identity::TicketId, AccountId, Balance, BlockNumber, Index};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
@@ -34,11 +34,15 @@ pub fn create_full<C, P>(
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_proposal_rpc::ProposalRuntimeApi<Block, AccountId, BlockNumber, AccountId>,
+	C::Api: pallet_community_identity_rpc::CommunityIdentityRuntimeApi<Block, AccountId, TicketId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_proposal_rpc::{ProposalApi, Proposals};
+	use pallet_community_identity_rpc::{CommunityIdentity, CommunityIdentityApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
@@ -55,6 +59,14 @@ pub fn create_full<C, P>(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
 
+	io.extend_with(
+		ProposalApi::to_delegate(Proposals::<_, (Block, BlockNumber, AccountId)>::new(client.clone()))
+	);
+
+	io.extend_with(
+		CommunityIdentityApi::to_delegate(CommunityIdentity::<_, Block>::new(client.clone()))
+	);
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
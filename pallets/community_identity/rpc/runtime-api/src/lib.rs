@@ -0,0 +1,32 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # pallet-community_identity-rpc-runtime-api
+//! Runtime API definition to query peer review data from the community identity pallet
+
+use codec::{Codec, EncodeLike};
+use sp_std::{fmt::Debug, vec::Vec};
+
+sp_api::decl_runtime_apis! {
+	/// Query peer review data from the community identity pallet
+	pub trait CommunityIdentityApi<IdentityId, TicketId> where
+		IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+		TicketId: Codec,
+	{
+		/// Get the open reviews that await the vote of a reviewer
+		fn pending_reviews_of(reviewer: IdentityId) -> Vec<TicketId>;
+	}
+}
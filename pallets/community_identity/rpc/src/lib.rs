@@ -0,0 +1,72 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # pallet-community_identity-rpc
+//! JSON-RPC interface to query peer review data without decoding SCALE encoded storage
+
+use std::{fmt::Debug, sync::Arc};
+
+use codec::{Codec, EncodeLike};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+pub use pallet_community_identity_rpc_runtime_api::CommunityIdentityApi as CommunityIdentityRuntimeApi;
+
+/// Error code of failed runtime API calls
+const RUNTIME_ERROR: i64 = 1;
+
+/// Query peer review data of the community identity pallet
+#[rpc]
+pub trait CommunityIdentityApi<BlockHash, IdentityId, TicketId> {
+	/// Get the open reviews that await the vote of a reviewer
+	#[rpc(name = "superorganism_pendingReviews")]
+	fn pending_reviews(&self, reviewer: IdentityId, at: Option<BlockHash>) -> Result<Vec<TicketId>>;
+}
+
+/// Implementation of CommunityIdentityApi that calls into the runtime.
+/// `B` is the Block the runtime API is implemented for.
+pub struct CommunityIdentity<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> CommunityIdentity<C, B> {
+	/// Create new `CommunityIdentity` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		CommunityIdentity { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, IdentityId, TicketId> CommunityIdentityApi<<Block as BlockT>::Hash, IdentityId, TicketId>
+	for CommunityIdentity<C, Block>
+where
+	Block: BlockT,
+	C: 'static + Send + Sync + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: CommunityIdentityRuntimeApi<Block, IdentityId, TicketId>,
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug + Send + Sync + 'static,
+	TicketId: Codec + Send + Sync + 'static,
+{
+	fn pending_reviews(&self, reviewer: IdentityId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<TicketId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pending_reviews_of(&at, reviewer).map_err(|error| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to query the community identity pallet.".into(),
+			data: Some(format!("{:?}", error).into()),
+		})
+	}
+}
@@ -0,0 +1,152 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Identity provider that combines the peer reviewed identities with an external registry.
+//! Runtimes configure it as `Identity` of the governance pallets instead of the community
+//! identity pallet to accept identities from both sources, e.g.
+//! `type Identity = WithExternalAttestations<CommunityIdentity, KycAttestations>;`
+
+use frame_support::{
+	dispatch::{Codec, Decode, DispatchError, Encode, fmt::Debug, Vec},
+	sp_std::marker::PhantomData,
+};
+use crate::{IdentityMetadata, traits::{ExternalAttestations, PeerReviewedPhysicalIdentity}};
+
+/// Peer reviewed identities of `Community`, extended by the levels that `External` attests.
+/// The effective level is the higher of both levels. Peer reviews, signers and metadata are
+/// managed by `Community` only, identities that are disputed there have level 0.
+pub struct WithExternalAttestations<Community, External>(PhantomData<(Community, External)>);
+
+impl<ProofData, Community, External> PeerReviewedPhysicalIdentity<ProofData>
+	for WithExternalAttestations<Community, External>
+where
+	ProofData: Codec + Clone + Debug + Decode + Encode + Eq + PartialEq,
+	Community: PeerReviewedPhysicalIdentity<ProofData>,
+	Community::IdentityLevel: Copy + Ord,
+	External: ExternalAttestations<Community::IdentityId, Community::IdentityLevel>,
+{
+	type Address = Community::Address;
+	type Ticket = Community::Ticket;
+	type Timestamp = Community::Timestamp;
+	type IdentityLevel = Community::IdentityLevel;
+	type IdentityId = Community::IdentityId;
+
+	fn request_peer_review(user: Self::Address, identity_level: Self::IdentityLevel, at: Self::Timestamp)
+		-> Result<Self::Ticket, DispatchError>
+	{
+		Community::request_peer_review(user, identity_level, at)
+	}
+
+	fn approve_identity(reviewer: Self::Address, review_process: Self::Ticket, proof_data: ProofData)
+		-> Result<(), DispatchError>
+	{
+		Community::approve_identity(reviewer, review_process, proof_data)
+	}
+
+	fn reject_identity(reviewer: Self::Address, review_process: Self::Ticket) -> Result<(), DispatchError> {
+		Community::reject_identity(reviewer, review_process)
+	}
+
+	fn report_missing(review_process: Self::Ticket, missing: Vec<Self::IdentityId>) -> Result<(), DispatchError> {
+		Community::report_missing(review_process, missing)
+	}
+
+	fn get_appointments(identity: &Self::IdentityId) -> Vec<(Self::Timestamp, Vec<Self::IdentityId>)> {
+		Community::get_appointments(identity)
+	}
+
+	/// The higher of the peer reviewed and the externally attested level
+	fn get_identity_level(identity: &Self::IdentityId) -> Self::IdentityLevel {
+		let reviewed: Self::IdentityLevel = Community::get_identity_level(identity);
+
+		// A dispute or ban in the community overrides any external attestation
+		if Community::is_disputed(identity) || Community::is_banned(identity) {
+			return reviewed;
+		}
+
+		External::attested_level(identity).map_or(reviewed, |attested| attested.max(reviewed))
+	}
+
+	fn get_identity_id(address: &Self::Address) -> Self::IdentityId {
+		Community::get_identity_id(address)
+	}
+
+	fn get_address(identity: &Self::IdentityId) -> Self::Address {
+		Community::get_address(identity)
+	}
+
+	fn get_review_count(identity: &Self::IdentityId) -> u32 {
+		Community::get_review_count(identity)
+	}
+
+	fn ensure_attested_signer(address: &Self::Address) -> Result<(), DispatchError> {
+		Community::ensure_attested_signer(address)
+	}
+
+	fn is_disputed(identity: &Self::IdentityId) -> bool {
+		Community::is_disputed(identity)
+	}
+
+	fn is_banned(identity: &Self::IdentityId) -> bool {
+		Community::is_banned(identity)
+	}
+
+	fn get_metadata(identity: &Self::IdentityId) -> Option<IdentityMetadata> {
+		Community::get_metadata(identity)
+	}
+
+	fn has_identity(address: &Self::Address) -> bool {
+		Community::has_identity(address)
+			|| External::attested_level(&Community::get_identity_id(address)).is_some()
+	}
+
+	/// Identities of both sources, identities that exist in both are counted once
+	fn identity_count() -> u32 {
+		let external_only: usize = External::attested_identities().iter()
+			.filter(|(identity, _)| !Community::has_identity(&Community::get_address(identity)))
+			.count();
+		Community::identity_count().saturating_add(external_only as u32)
+	}
+
+	fn identities_from_level(min_level: Self::IdentityLevel) -> Vec<Self::IdentityId> {
+		let mut identities: Vec<Self::IdentityId> = Community::identities_from_level(min_level);
+
+		for (identity, level) in External::attested_identities().into_iter() {
+			if level >= min_level && !Community::is_disputed(&identity) && !Community::is_banned(&identity)
+				&& !identities.contains(&identity) {
+				identities.push(identity);
+			}
+		}
+
+		identities
+	}
+
+	/// Identities of both sources, identities that reach the level in the community are
+	/// counted once
+	fn identity_count_from_level(min_level: Self::IdentityLevel) -> u32 {
+		let external_only: usize = External::attested_identities().iter()
+			.filter(|(identity, level)| *level >= min_level
+				&& Community::get_identity_level(identity) < min_level
+				&& !Community::is_disputed(identity)
+				&& !Community::is_banned(identity)
+			)
+			.count();
+		Community::identity_count_from_level(min_level).saturating_add(external_only as u32)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_identity_level(identity: &Self::IdentityId, level: Self::IdentityLevel) {
+		Community::set_identity_level(identity, level)
+	}
+}
@@ -0,0 +1,478 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the community identity pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::sp_runtime::traits::{Bounded, One};
+use frame_system::RawOrigin;
+use crate::*;
+
+const SEED: u32 = 0;
+/// Upper bound of the benchmarked multisig signatories
+const MAX_SIGNATORIES: u32 = 100;
+
+/// Create an account that can afford the reviewer stake
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Let the caller attest itself as signer, so every signer check has to read the attestation
+fn attest_self<T: Trait>(caller: &T::AccountId) {
+	<SignerToIdentity<T>>::insert(caller, caller);
+	<Attestations<T>>::insert(caller, SigningScheme::Key(caller.clone()));
+}
+
+/// Open a review request of a subject. The caller and `r - 1` other reviewers are assigned,
+/// the other reviewers already cast `vote`.
+fn pending_review<T: Trait>(subject: &T::AccountId, caller: &T::AccountId, r: u32, vote: ReviewVote) -> TicketId {
+	let ticket: TicketId = 0;
+	let mut reviewers: Vec<T::AccountId> = (1..r).map(|i| account("reviewer", i, SEED)).collect();
+
+	for reviewer in reviewers.iter() {
+		<ReviewVotes<T>>::insert(ticket, reviewer, vote);
+	}
+
+	reviewers.push(caller.clone());
+	Module::<T>::add_to_inboxes(ticket, &reviewers);
+	<Tickets<T>>::insert(ticket, ReviewTicket {
+		subject: subject.clone(),
+		level: 1,
+		requested: T::BlockNumber::zero(),
+		appointment: T::Time::now(),
+		reviewers,
+		status: ReviewStatus::Pending,
+		expires: T::ReviewTimeout::get(),
+		appeal: ReviewAppeal::NotAppealed,
+	});
+	<PendingReviews<T>>::insert(subject, ticket);
+	ticket
+}
+
+/// Let a funded account with the identity level `level` bond the reviewer stake
+fn bonded_reviewer<T: Trait>(index: u32, level: IdentityLevel) -> T::AccountId {
+	let reviewer: T::AccountId = funded_account::<T>("reviewer", index);
+	Module::<T>::insert_identity(reviewer.clone(), level, ProofType::default(), ProofKind::Genesis);
+	Module::<T>::do_bond_reviewer(reviewer.clone()).expect("Funded reviewers with a level can bond; qed");
+	reviewer
+}
+
+/// Complete a review of the account "subject" with `reviewers` reviewers that bonded their stake
+fn complete_review<T: Trait>(reviewers: u32) -> TicketId {
+	let subject: T::AccountId = account("subject", 0, SEED);
+	let reviewers: Vec<T::AccountId> = (0..reviewers)
+		.map(|i| bonded_reviewer::<T>(i, T::MaxBootstrapLevel::get().saturating_add(1)))
+		.collect();
+
+	Module::<T>::do_bootstrap_identity(subject.clone(), 0).expect("Level 0 can always be bootstrapped; qed");
+	let ticket: TicketId = 0;
+	<CompletedReviews<T>>::insert(ticket, ReviewRecord {
+		subject: subject.clone(),
+		reviewers,
+		level: T::MaxBootstrapLevel::get(),
+		completed: T::Time::now(),
+	});
+	ticket
+}
+
+benchmarks! {
+	_ { }
+
+	bootstrap_identity {
+		let who: T::AccountId = account("who", 0, SEED);
+	}: _(RawOrigin::Root, who.clone(), T::MaxBootstrapLevel::get())
+	verify {
+		assert_eq!(Module::<T>::do_get_identity_level(&who), T::MaxBootstrapLevel::get());
+	}
+
+	// Worst case: an expired cooldown of a previous rejection is removed, an expired review that
+	// none of the `r` reviewers voted on is closed and more candidates than MaxReviewers are available
+	request_peer_review {
+		let r in 1 .. T::MaxReviewers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		Module::<T>::do_bootstrap_identity(caller.clone(), 0).expect("Level 0 can always be bootstrapped; qed");
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+
+		for i in 0 .. T::MaxReviewers::get() * 2 {
+			bonded_reviewer::<T>(i, 2);
+		}
+
+		<ReviewCooldowns<T>>::insert(&caller, T::BlockNumber::zero());
+		let expired: TicketId = pending_review::<T>(&caller, &account("reviewer", 0, SEED), r, ReviewVote::Reject);
+		<ReviewVotes<T>>::remove_prefix(expired);
+		<NextTicketId>::put(expired + 1);
+		frame_system::Module::<T>::set_block_number(T::ReviewTimeout::get() + One::one());
+	}: _(RawOrigin::Signed(caller.clone()), 1, T::Time::now())
+	verify {
+		assert!(<PendingReviews<T>>::contains_key(&caller));
+		assert!(!<ReviewCooldowns<T>>::contains_key(&caller));
+		assert_eq!(<Tickets<T>>::get(expired).map(|ticket| ticket.status), Some(ReviewStatus::Expired));
+	}
+
+	// Worst case: the reviewers of the rejected review are bonded and have to be skipped
+	appeal_review {
+		let r in 1 .. T::MaxReviewers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		Module::<T>::do_bootstrap_identity(caller.clone(), 0).expect("Level 0 can always be bootstrapped; qed");
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+
+		for i in 0 .. T::MaxReviewers::get() * (APPEAL_REVIEWER_FACTOR + 1) {
+			bonded_reviewer::<T>(i, 2);
+		}
+
+		let ticket: TicketId = pending_review::<T>(&caller, &account("reviewer", 0, SEED), r, ReviewVote::Reject);
+		Module::<T>::reject_review(ticket, caller.clone(), 1);
+	}: _(RawOrigin::Signed(caller.clone()), ticket)
+	verify {
+		assert_eq!(<Tickets<T>>::get(ticket).map(|ticket| ticket.appeal), Some(ReviewAppeal::Appealed));
+		assert_eq!(<PendingReviews<T>>::get(&caller), Some(ticket));
+	}
+
+	// Worst case: the vote of the caller grants the level
+	approve_identity {
+		let r in 1 .. T::MaxReviewers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let subject: T::AccountId = account("subject", 0, SEED);
+		let ticket: TicketId = pending_review::<T>(&subject, &caller, r, ReviewVote::Approve(ProofType::default()));
+		<VerificationStatements>::insert(ticket, ProofType::default());
+		let proof: ProofType = Module::<T>::review_proof(ticket, &caller, &ProofType::default());
+	}: _(RawOrigin::Signed(caller), ticket, proof)
+	verify {
+		assert!(!<PendingReviews<T>>::contains_key(&subject));
+	}
+
+	confirm_verification {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let ticket: TicketId = pending_review::<T>(&caller, &account("reviewer", 0, SEED), 1, ReviewVote::Reject);
+	}: _(RawOrigin::Signed(caller), ticket, [1u8; 32])
+	verify {
+		assert!(<VerificationStatements>::contains_key(ticket));
+	}
+
+	// Worst case: the vote of the caller rejects the review
+	reject_identity {
+		let r in 1 .. T::MaxReviewers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let subject: T::AccountId = account("subject", 0, SEED);
+		let ticket: TicketId = pending_review::<T>(&subject, &caller, r, ReviewVote::Reject);
+	}: _(RawOrigin::Signed(caller), ticket)
+	verify {
+		assert!(<ReviewCooldowns<T>>::contains_key(&subject));
+	}
+
+	report_missing {
+		let m in 0 .. T::MaxReviewers::get();
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let missing: Vec<IdentityId<T>> = (0..m).map(|i| account("missing", i, SEED)).collect();
+	}: _(RawOrigin::Signed(caller), 0, missing)
+
+	audit_review {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		T::Auditors::add(&caller);
+		let review: TicketId = complete_review::<T>(T::MaxReviewers::get());
+	}: _(RawOrigin::Signed(caller.clone()), review)
+	verify {
+		assert_eq!(<Audits<T>>::get(review), Some(caller));
+	}
+
+	// Worst case: fraud is confirmed and every reviewer is slashed
+	conclude_audit {
+		let r in 1 .. T::MaxReviewers::get().max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let review: TicketId = complete_review::<T>(r);
+		<Audits<T>>::insert(review, &caller);
+	}: _(RawOrigin::Signed(caller), review, true)
+	verify {
+		assert_eq!(Module::<T>::do_get_identity_level(&account("subject", 0, SEED)), 0);
+	}
+
+	// Worst case: a previous attestation is replaced by a multisig scheme
+	attest_signing_scheme {
+		let s in 0 .. MAX_SIGNATORIES;
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let scheme = SigningScheme::Multisig {
+			account: account("multisig", 0, SEED),
+			signatories: (0..s).map(|i| account("signatory", i, SEED)).collect(),
+			threshold: s as u16,
+		};
+	}: _(RawOrigin::Signed(caller.clone()), scheme.clone())
+	verify {
+		assert_eq!(<Attestations<T>>::get(&caller), Some(scheme));
+	}
+
+	remove_attestation {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(<Attestations<T>>::get(&caller).is_none());
+	}
+
+	// Worst case: the support of the caller completes the quorum and opens the dispute
+	revoke_identity {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let subject: T::AccountId = account("subject", 0, SEED);
+		Module::<T>::insert_identity(subject.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		Module::<T>::insert_identity(caller.clone(), 2, ProofType::default(), ProofKind::Genesis);
+		let support: Vec<(T::AccountId, DocumentCID)> = (1..T::RevocationQuorum::get())
+			.map(|i| (account("reviewer", i, SEED), DocumentCID::default()))
+			.collect();
+		<RevocationSupport<T>>::insert(&subject, support);
+	}: _(RawOrigin::Signed(caller), subject.clone(), DocumentCID::default())
+	verify {
+		assert!(<Disputes<T>>::contains_key(&subject));
+	}
+
+	appeal_revocation {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		Module::<T>::insert_identity(caller.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		Module::<T>::do_revoke_identity(None, caller.clone(), DocumentCID::default())?;
+	}: _(RawOrigin::Signed(caller.clone()), DocumentCID::default())
+	verify {
+		assert!(<Disputes<T>>::get(&caller).and_then(|dispute| dispute.appeal).is_some());
+	}
+
+	// Worst case: the identity is revoked
+	resolve_dispute {
+		let subject: T::AccountId = account("subject", 0, SEED);
+		Module::<T>::insert_identity(subject.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		Module::<T>::do_revoke_identity(None, subject.clone(), DocumentCID::default())?;
+	}: _(T::CouncilOrigin::successful_origin(), subject.clone(), true)
+	verify {
+		assert_eq!(Module::<T>::stored_identity_level(&subject), 0);
+	}
+
+	finalize_revocation {
+		let caller: T::AccountId = whitelisted_caller();
+		let subject: T::AccountId = account("subject", 0, SEED);
+		Module::<T>::insert_identity(subject.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		Module::<T>::do_revoke_identity(None, subject.clone(), DocumentCID::default())?;
+		frame_system::Module::<T>::set_block_number(T::AppealPeriod::get() + One::one());
+	}: _(RawOrigin::Signed(caller), subject.clone())
+	verify {
+		assert!(!<Disputes<T>>::contains_key(&subject));
+	}
+
+	ban_identity {
+		let subject: T::AccountId = account("subject", 0, SEED);
+	}: _(T::CouncilOrigin::successful_origin(), subject.clone())
+	verify {
+		assert!(<Banned<T>>::get(&subject));
+	}
+
+	unban_identity {
+		let subject: T::AccountId = account("subject", 0, SEED);
+		<Banned<T>>::insert(&subject, true);
+	}: _(T::CouncilOrigin::successful_origin(), subject.clone())
+	verify {
+		assert!(!<Banned<T>>::get(&subject));
+	}
+
+	// Worst case: every assigned reviewer has an unclaimed reward and the caller is the last one
+	claim_review_reward {
+		let caller: T::AccountId = whitelisted_caller();
+		let mut reviewers: Vec<T::AccountId> = (1..T::MaxReviewers::get()).map(|i| account("reviewer", i, SEED)).collect();
+		reviewers.push(caller.clone());
+		<ReviewRewards<T>>::insert(0, reviewers);
+		T::Currency::make_free_balance_be(&Module::<T>::treasury_account(), BalanceOf::<T>::max_value() / 2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(!<ReviewRewards<T>>::get(0).contains(&caller));
+	}
+
+	bond_reviewer {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		Module::<T>::insert_identity(caller.clone(), 1, ProofType::default(), ProofKind::Genesis);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(<ReviewerBonds<T>>::contains_key(&caller));
+	}
+
+	unbond_reviewer {
+		let caller: T::AccountId = bonded_reviewer::<T>(0, 1);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Module::<T>::is_bonded_reviewer(&caller));
+	}
+
+	withdraw_bond {
+		let caller: T::AccountId = bonded_reviewer::<T>(0, 1);
+		<ReviewerBonds<T>>::mutate(&caller, |bond| {
+			if let Some(bond) = bond {
+				bond.unbonding_until = Some(T::BlockNumber::zero());
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!<ReviewerBonds<T>>::contains_key(&caller));
+	}
+
+	challenge_review {
+		let r in 1 .. T::MaxReviewers::get().max(1);
+		let review: TicketId = complete_review::<T>(r);
+	}: _(T::CouncilOrigin::successful_origin(), review, DocumentCID::default())
+	verify {
+		assert_eq!(Module::<T>::do_get_identity_level(&account("subject", 0, SEED)), 0);
+	}
+
+	link_address {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let address: T::AccountId = account("address", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), address.clone())
+	verify {
+		assert_eq!(<LinkOffers<T>>::get(&address), Some(caller));
+	}
+
+	// Worst case: the identity already linked MaxLinkedAddresses - 1 addresses
+	accept_link {
+		let identity: T::AccountId = account("identity", 0, SEED);
+		let linked: Vec<T::AccountId> = (1..T::MaxLinkedAddresses::get()).map(|i| account("address", i, SEED)).collect();
+		<LinkedAddresses<T>>::insert(&identity, linked);
+		let caller: T::AccountId = whitelisted_caller();
+		<LinkOffers<T>>::insert(&caller, &identity);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Module::<T>::do_get_identity_id(&caller), identity);
+	}
+
+	unlink_address {
+		let caller: T::AccountId = whitelisted_caller();
+		let address: T::AccountId = account("address", 0, SEED);
+		<LinkedAddresses<T>>::append(&caller, &address);
+		<AddressToIdentity<T>>::insert(&address, &caller);
+	}: _(RawOrigin::Signed(caller), address.clone())
+	verify {
+		assert!(!<AddressToIdentity<T>>::contains_key(&address));
+	}
+
+	set_guardians {
+		let g in 1 .. T::MaxGuardians::get().max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		let guardians: Vec<T::AccountId> = (0..g).map(|i| account("guardian", i, SEED)).collect();
+	}: _(RawOrigin::Signed(caller.clone()), guardians, g)
+	verify {
+		assert!(<Guardians<T>>::contains_key(&caller));
+	}
+
+	// Worst case: the vote of the caller approves the recovery
+	vote_recovery {
+		let g in 1 .. T::MaxGuardians::get().max(1);
+		let identity: T::AccountId = account("identity", 0, SEED);
+		let new_account: T::AccountId = account("new", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let mut guardians: Vec<T::AccountId> = (1..g).map(|i| account("guardian", i, SEED)).collect();
+		<Recoveries<T>>::insert(&identity, Recovery {
+			new_account: new_account.clone(),
+			approvals: guardians.clone(),
+			executable_from: None,
+		});
+		guardians.push(caller.clone());
+		<Guardians<T>>::insert(&identity, (guardians, g));
+	}: _(RawOrigin::Signed(caller), identity.clone(), new_account)
+	verify {
+		assert!(<Recoveries<T>>::get(&identity).and_then(|recovery| recovery.executable_from).is_some());
+	}
+
+	cancel_recovery {
+		let caller: T::AccountId = whitelisted_caller();
+		attest_self::<T>(&caller);
+		<Recoveries<T>>::insert(&caller, Recovery {
+			new_account: account("new", 0, SEED),
+			approvals: Vec::new(),
+			executable_from: None,
+		});
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!<Recoveries<T>>::contains_key(&caller));
+	}
+
+	// Worst case: the identity attested a signer and is part of the reviewer pool
+	finalize_recovery {
+		let caller: T::AccountId = whitelisted_caller();
+		let identity: T::AccountId = account("identity", 0, SEED);
+		let new_account: T::AccountId = account("new", 0, SEED);
+		Module::<T>::insert_identity(identity.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		attest_self::<T>(&identity);
+		<Recoveries<T>>::insert(&identity, Recovery {
+			new_account: new_account.clone(),
+			approvals: Vec::new(),
+			executable_from: Some(T::BlockNumber::zero()),
+		});
+	}: _(RawOrigin::Signed(caller), identity.clone())
+	verify {
+		assert_eq!(Module::<T>::do_get_identity_id(&new_account), identity);
+	}
+
+	set_metadata {
+		let caller: T::AccountId = whitelisted_caller();
+		Module::<T>::insert_identity(caller.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		attest_self::<T>(&caller);
+		let metadata = IdentityMetadata { display_hash: Some([1u8; 32]), contact: Some([2u8; 32]) };
+	}: _(RawOrigin::Signed(caller.clone()), metadata)
+	verify {
+		assert!(<Metadata<T>>::contains_key(&caller));
+	}
+
+	clear_metadata {
+		let caller: T::AccountId = whitelisted_caller();
+		Module::<T>::insert_identity(caller.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		attest_self::<T>(&caller);
+		<Metadata<T>>::insert(&caller, IdentityMetadata::default());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!<Metadata<T>>::contains_key(&caller));
+	}
+
+	set_region {
+		let caller: T::AccountId = whitelisted_caller();
+		Module::<T>::insert_identity(caller.clone(), 1, ProofType::default(), ProofKind::Genesis);
+		attest_self::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), Some(*b"DE"))
+	verify {
+		assert_eq!(<Regions<T>>::get(&caller), Some(*b"DE"));
+	}
+
+	// Worst case: every candidate is an eligible reviewer from the region of the subject
+	assign_regional_reviewers {
+		let c in 1 .. T::MaxReviewers::get() * REGIONAL_CANDIDATE_FACTOR;
+		let subject: T::AccountId = account("subject", 0, SEED);
+		for i in 0..c {
+			let candidate: T::AccountId = bonded_reviewer::<T>(i, IdentityLevel::max_value());
+			<Regions<T>>::insert(&candidate, *b"DE");
+		}
+		Module::<T>::insert_identity(subject.clone(), 0, ProofType::default(), ProofKind::Bootstrap);
+		<Regions<T>>::insert(&subject, *b"DE");
+		let review_process: TicketId = pending_review::<T>(&subject, &account("assigned", 0, SEED), 1, ReviewVote::Reject);
+	}: _(RawOrigin::None, review_process)
+	verify {
+		assert!(<RegionalAssignments>::get(review_process));
+	}
+}
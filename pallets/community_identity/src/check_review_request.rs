@@ -0,0 +1,101 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::fmt,
+	sp_runtime::{
+		traits::{DispatchInfoOf, SignedExtension},
+		transaction_validity::{
+			InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+		},
+	},
+	sp_std::marker::PhantomData,
+	traits::IsSubType,
+};
+use crate::{Call, Module, Trait};
+
+/// Custom error code: The account already has an open peer review request
+pub const REVIEW_ALREADY_REQUESTED: u8 = 1;
+/// Custom error code: The cooldown after a rejected review is still active
+pub const REVIEW_COOLDOWN_ACTIVE: u8 = 2;
+/// Custom error code: The previous upgrade of the identity level is too recent
+pub const UPGRADE_COOLDOWN_ACTIVE: u8 = 3;
+
+/// Reject `request_peer_review` transactions of accounts that already have an open request or
+/// an active cooldown before they enter the transaction pool
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckReviewRequest<T: Trait + Send + Sync>(PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckReviewRequest<T> {
+	pub fn new() -> Self {
+		CheckReviewRequest(PhantomData)
+	}
+}
+
+impl<T: Trait + Send + Sync> Default for CheckReviewRequest<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Trait + Send + Sync> fmt::Debug for CheckReviewRequest<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckReviewRequest")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckReviewRequest<T> where
+	<T as frame_system::Trait>::Call: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckReviewRequest";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Trait>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if let Some(Call::request_peer_review(level, _)) = call.is_sub_type() {
+			if <Module<T>>::has_open_review(who) {
+				return InvalidTransaction::Custom(REVIEW_ALREADY_REQUESTED).into();
+			}
+
+			if <Module<T>>::review_cooldown_active(who) {
+				return InvalidTransaction::Custom(REVIEW_COOLDOWN_ACTIVE).into();
+			}
+
+			if <Module<T>>::upgrade_cooldown_active(who, *level) {
+				return InvalidTransaction::Custom(UPGRADE_COOLDOWN_ACTIVE).into();
+			}
+		}
+
+		Ok(ValidTransaction::default())
+	}
+}
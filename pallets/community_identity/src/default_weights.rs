@@ -0,0 +1,213 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Default weights of the community identity pallet. Regenerate them with the benchmarks in
+//! benchmarking.rs on the reference hardware when the extrinsics change.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+impl crate::WeightInfo for () {
+	fn bootstrap_identity() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn request_peer_review(r: u32) -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(12 as Weight))
+			.saturating_add(DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(11 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn approve_identity(r: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(11 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn confirm_verification() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn reject_identity(r: u32) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn appeal_review(r: u32) -> Weight {
+		(69_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn report_missing(m: u32) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+	}
+	fn audit_review() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn conclude_audit(r: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((31_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+	}
+	fn attest_signing_scheme(s: u32) -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn remove_attestation() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn revoke_identity() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn appeal_revocation() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn resolve_dispute() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn finalize_revocation() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn ban_identity() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn unban_identity() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_review_reward() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn bond_reviewer() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn unbond_reviewer() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn withdraw_bond() -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn challenge_review(r: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+	}
+	fn link_address() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn accept_link() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn unlink_address() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_guardians(g: u32) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((650_000 as Weight).saturating_mul(g as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn vote_recovery(g: u32) -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add((420_000 as Weight).saturating_mul(g as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_recovery() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn finalize_recovery() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn set_metadata() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn clear_metadata() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_region() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn assign_regional_reviewers(c: u32) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((8 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+}
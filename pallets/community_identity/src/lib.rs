@@ -14,27 +14,104 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 use frame_support::{
-	decl_module,
+	decl_error, decl_event, decl_module, decl_storage, ensure,
 	dispatch::{DispatchError, fmt::Debug, Vec},
 	Parameter,
-	sp_runtime::traits::{AtLeast32Bit, Scale},
+	weights::Weight,
+	sp_runtime::{ModuleId, Permill,
+		traits::{AccountIdConversion, AtLeast32Bit, CheckedDiv, Hash, One, Saturating, Scale, ValidateUnsigned, Zero},
+		transaction_validity::{InvalidTransaction, TransactionPriority, TransactionSource,
+			TransactionValidity, ValidTransaction},
+	},
+	traits::{Contains, Currency, EnsureOrigin, ExistenceRequirement, Get, Randomness, ReservableCurrency, Time},
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_none, ensure_root, ensure_signed, offchain::SendTransactionTypes};
 use codec::{Codec, Decode, Encode, EncodeLike};
 #[cfg(feature = "std")]
 use frame_support::serde::{Deserialize, Serialize};
+use pallet_reputation::{ReputationAction, traits::Reputation as ReputationRegistry};
+/// Identity provider that accepts peer reviewed and externally attested identities
+pub mod adapter;
+mod benchmarking;
+mod default_weights;
+mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+/// Transaction pool filter for peer review requests
+pub mod check_review_request;
+/// Off-chain worker that proposes reviewers from the region of the subject
+pub mod offchain;
 /// Public interface to PhysicalIdentity
 pub mod traits;
 
 
 pub type IdentityLevel = u8;
+/// Identity level that expired identities retain until they are re-verified
+pub const DECAYED_IDENTITY_LEVEL: IdentityLevel = 1;
 pub type ProofType = [u8; 32];
+/// Sha2-256 digest of the document's content identifier
+pub type DocumentCID = [u8; 32];
 pub type IdentityId<T> = <T as frame_system::Trait>::AccountId;
-type Ticket<T> = <T as frame_system::Trait>::AccountId;
+/// Index of a peer review ticket
+pub type TicketId = u64;
+/// ISO 3166-1 alpha-2 country code, e.g. b"DE"
+pub type RegionCode = [u8; 2];
+
+/// How many regional candidates per assigned reviewer does a regional assignment consider at most?
+pub const REGIONAL_CANDIDATE_FACTOR: u32 = 4;
+/// How many times the reviewers of the rejected review are assigned to its appeal
+/// (limited by MaxReviewers)
+pub const APPEAL_REVIEWER_FACTOR: u32 = 2;
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// Storage layout versions of this pallet
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+enum Releases {
+	/// Review requests only store the block in which they were requested
+	V1ReviewRequests,
+	/// Review requests are tickets with the requested level and the assigned reviewers
+	V2PendingReviews,
+	/// Identities expire after IdentityValidityPeriod
+	V3IdentityExpiry,
+	/// Reviews are identified by ticket indices instead of the subject's account
+	V4Tickets,
+	/// The number of stored identities is tracked
+	V5IdentityCount,
+	/// Open reviews are indexed by their assigned reviewers
+	V6ReviewerInbox,
+	/// Tickets track whether their rejection was appealed
+	V7ReviewAppeals,
+	/// Identities are counted per level
+	V8LevelCounts,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1ReviewRequests
+	}
+}
+
+/// Describes how an identity level was obtained
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProofKind {
+	/// The identity level was approved during a peer review
+	PeerReview,
+	/// The identity level was granted without peer review (genesis or root).
+	/// Such identities should be re-verified by a peer review later.
+	Bootstrap,
+	/// The identity was verified before the chain was launched, the proof was supplied
+	/// in the genesis config
+	Genesis,
+}
+
+impl Default for ProofKind {
+	fn default() -> Self {
+		ProofKind::PeerReview
+	}
+}
 
 /// Structure that contains the proof
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
@@ -43,109 +120,2222 @@ pub struct PhysicalProof<Timestamp, ProofData> where
 	ProofData: Codec + Clone + Debug + Eq + PartialEq,
 	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
 {
-	proof: ProofData,
-	date: Timestamp,
+	/// Proof of the identity, for peer reviews the digest of the proof shares of the approvers
+	pub proof: ProofData,
+	/// Point in time at which the proof was supplied
+	pub date: Timestamp,
+	/// How the identity level was obtained
+	pub kind: ProofKind,
+}
+
+/// Structure that contains the identity ID, level, proof and the end of the validity period
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PhysicalIdentityData<Timestamp, AccountId, ProofData, BlockNumber> where
+	ProofData: Codec + Clone + Debug + Eq + PartialEq,
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Identity the data belongs to
+	pub identity: AccountId,
+	/// Identity level as it is stored, see `identity_level_of` for the effective level
+	pub level: IdentityLevel,
+	/// Proof of the current level
+	pub proof: PhysicalProof<Timestamp, ProofData>,
+	/// Block after which the level decays to DECAYED_IDENTITY_LEVEL until it is re-verified
+	pub expires: BlockNumber,
+}
+
+/// Structure that contains the participants and the outcome of a completed peer review
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReviewRecord<Timestamp, AccountId> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+{
+	pub subject: AccountId,
+	pub reviewers: Vec<AccountId>,
+	pub level: IdentityLevel,
+	pub completed: Timestamp,
+}
+
+/// Progress of a peer review ticket
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReviewStatus {
+	/// The assigned reviewers are voting
+	Pending,
+	/// Enough reviewers approved, the level was granted
+	Approved,
+	/// The approval threshold was not reached
+	Rejected,
+	/// The reviewers did not conclude the review before it expired
+	Expired,
+}
+
+impl Default for ReviewStatus {
+	fn default() -> Self {
+		ReviewStatus::Pending
+	}
+}
+
+/// Appeal state of a peer review ticket
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReviewAppeal<BlockNumber> {
+	/// The review was not rejected or its rejection was not appealed
+	NotAppealed,
+	/// The review was rejected, the subject can appeal until the block
+	Appealable(BlockNumber),
+	/// The subject appealed the rejection, fresh reviewers review again. A second rejection
+	/// is final.
+	Appealed,
+}
+
+impl<BlockNumber> Default for ReviewAppeal<BlockNumber> {
+	fn default() -> Self {
+		ReviewAppeal::NotAppealed
+	}
+}
+
+/// Structure that contains a peer review request, the reviewers assigned to it and its progress
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReviewTicket<Timestamp, AccountId, BlockNumber> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+{
+	/// Account whose identity is reviewed
+	pub subject: AccountId,
+	/// Identity level the subject requested
+	pub level: IdentityLevel,
+	/// Block in which the review was requested
+	pub requested: BlockNumber,
+	/// Proposed date of the review
+	pub appointment: Timestamp,
+	/// Randomly selected reviewers with an identity level above the requested level
+	pub reviewers: Vec<AccountId>,
+	/// Progress of the review
+	pub status: ReviewStatus,
+	/// Last block in which the reviewers can vote
+	pub expires: BlockNumber,
+	/// Whether the rejection of the review can be or was appealed
+	pub appeal: ReviewAppeal<BlockNumber>,
+}
+
+/// Revocation of an identity that is disputed. The identity can't propose or vote until the
+/// dispute is resolved.
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Dispute<BlockNumber> where
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Evidence documents that justify the revocation
+	pub evidence: Vec<DocumentCID>,
+	/// Last block in which the subject can appeal the revocation
+	pub appeal_until: BlockNumber,
+	/// Document of the subject that contests the evidence
+	pub appeal: Option<DocumentCID>,
+}
+
+/// Optional public information about an identity, which allows frontends to show who
+/// submitted a proposal without external indexers
+#[derive(Clone, Decode, Debug, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct IdentityMetadata {
+	/// blake2-256 hash of the display handle
+	pub display_hash: Option<[u8; 32]>,
+	/// Contact or profile document
+	pub contact: Option<DocumentCID>,
+}
+
+/// Requirements to gain a specific identity level by peer review
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LevelRequirement<BlockNumber> where
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// How many reviewers are assigned to a review? (limited by MaxReviewers)
+	pub reviewers: u32,
+	/// Which identity level must the reviewers have at least? Reviewers always have a level
+	/// above the requested level.
+	pub min_reviewer_level: IdentityLevel,
+	/// How many blocks must pass after the previous upgrade until this level can be requested?
+	pub upgrade_cooldown: BlockNumber,
+}
+
+/// Deposit that a reviewer bonded to be assigned to peer reviews
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReviewerBond<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Reserved amount that is slashed if the reviewer approved a fraudulent review
+	pub amount: Balance,
+	/// Block from which the bond can be withdrawn, None if the reviewer is not unbonding
+	pub unbonding_until: Option<BlockNumber>,
+}
+
+/// Vote of an assigned reviewer on a review ticket
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReviewVote {
+	/// Approve the requested level, including the proof share of the reviewer
+	Approve(ProofType),
+	/// Reject the requested level
+	Reject,
+}
+
+/// Recovery of an identity whose main address was lost
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Recovery<AccountId, BlockNumber> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Account that becomes the main address and attested signer of the identity
+	pub new_account: AccountId,
+	/// Guardians that approved the recovery
+	pub approvals: Vec<AccountId>,
+	/// Block from which the recovery can be finalized, None until enough guardians approved
+	pub executable_from: Option<BlockNumber>,
+}
+
+/// Signing scheme that an identity attested for governance critical calls
+#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SigningScheme<AccountId> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+{
+	/// A single key, e.g. held by a hardware wallet
+	Key(AccountId),
+	/// A multisig account and the configuration it was derived from
+	Multisig {
+		account: AccountId,
+		signatories: Vec<AccountId>,
+		threshold: u16,
+	},
+}
+
+impl<AccountId> SigningScheme<AccountId> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+{
+	/// The account that has to sign governance critical calls
+	pub fn signer(&self) -> &AccountId {
+		match self {
+			SigningScheme::Key(account) => account,
+			SigningScheme::Multisig { account, .. } => account,
+		}
+	}
+
+	/// The number of signatories of a multisig account (0 for a single key)
+	pub fn signatory_count(&self) -> u32 {
+		match self {
+			SigningScheme::Key(_) => 0,
+			SigningScheme::Multisig { signatories, .. } => signatories.len() as u32,
+		}
+	}
+}
+
+/// Outcomes of the council audits of the reviews a reviewer has performed
+#[derive(Clone, Copy, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReviewerReputation {
+	/// Audited reviews that were confirmed
+	pub confirmed: u32,
+	/// Audited reviews that turned out to be fraudulent
+	pub fraudulent: u32,
+}
+
+/// Weight functions of the extrinsics of this pallet
+pub trait WeightInfo {
+	fn bootstrap_identity() -> Weight;
+	fn request_peer_review(r: u32) -> Weight;
+	fn approve_identity(r: u32) -> Weight;
+	fn confirm_verification() -> Weight;
+	fn reject_identity(r: u32) -> Weight;
+	fn appeal_review(r: u32) -> Weight;
+	fn report_missing(m: u32) -> Weight;
+	fn audit_review() -> Weight;
+	fn conclude_audit(r: u32) -> Weight;
+	fn attest_signing_scheme(s: u32) -> Weight;
+	fn remove_attestation() -> Weight;
+	fn revoke_identity() -> Weight;
+	fn appeal_revocation() -> Weight;
+	fn resolve_dispute() -> Weight;
+	fn finalize_revocation() -> Weight;
+	fn ban_identity() -> Weight;
+	fn unban_identity() -> Weight;
+	fn claim_review_reward() -> Weight;
+	fn bond_reviewer() -> Weight;
+	fn unbond_reviewer() -> Weight;
+	fn withdraw_bond() -> Weight;
+	fn challenge_review(r: u32) -> Weight;
+	fn link_address() -> Weight;
+	fn accept_link() -> Weight;
+	fn unlink_address() -> Weight;
+	fn set_guardians(g: u32) -> Weight;
+	fn vote_recovery(g: u32) -> Weight;
+	fn cancel_recovery() -> Weight;
+	fn finalize_recovery() -> Weight;
+	fn set_metadata() -> Weight;
+	fn clear_metadata() -> Weight;
+	fn set_region() -> Weight;
+	fn assign_regional_reviewers(c: u32) -> Weight;
+}
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	type Timestamp: Parameter + Default + AtLeast32Bit
+		+ Scale<Self::BlockNumber, Output = Self::Timestamp> + Copy;
+
+	/// Source of the current time
+	type Time: Time<Moment = Self::Timestamp>;
+
+	/// Source of randomness for the selection of reviewers
+	type Randomness: Randomness<Self::Hash>;
+
+	/// Which identity level can be granted at most without peer review?
+	type MaxBootstrapLevel: Get<IdentityLevel>;
+
+	/// Type that manages balances
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// Accounts that are allowed to audit completed reviews (council members)
+	type Auditors: Contains<Self::AccountId>;
+
+	/// Origin that revokes identities and resolves disputes (e.g. the council)
+	type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+	/// How many reviewers with a level above the level of an identity must support its
+	/// revocation to dispute it?
+	type RevocationQuorum: Get<u32>;
+
+	/// How many blocks can the subject of a revocation appeal it?
+	type AppealPeriod: Get<Self::BlockNumber>;
+
+	/// How many blocks does an audit era last?
+	type AuditEraLength: Get<Self::BlockNumber>;
+
+	/// How many reviews can be pulled for an audit per era?
+	type MaxAuditsPerEra: Get<u32>;
+
+	/// How much must reviewers bond to be assigned to peer reviews? The bond is slashed if they
+	/// approved a fraudulent review.
+	type ReviewerStake: Get<BalanceOf<Self>>;
+
+	/// How many blocks must pass after unbonding until reviewers can withdraw their bond?
+	/// Fraudulent approvals can be challenged during that time.
+	type ReviewerUnbondingPeriod: Get<Self::BlockNumber>;
+
+	/// How many blocks must pass after a rejected review until a new review can be requested?
+	type ReviewCooldown: Get<Self::BlockNumber>;
+
+	/// Id of the governance pot that pays the reviewer rewards
+	type TreasuryModuleId: Get<ModuleId>;
+
+	/// How much does every reviewer that voted on a concluded review receive from the
+	/// governance pot?
+	type ReviewerReward: Get<BalanceOf<Self>>;
+
+	/// How many reviewers participate in a peer review at most? (required for weight calculation)
+	type MaxReviewers: Get<u32>;
+
+	/// How many reviews can a reviewer approve in a single batch?
+	type MaxApprovalBatch: Get<u32>;
+
+	/// Priority of the regional reviewer assignments submitted by the off-chain worker
+	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// How many blocks can the assigned reviewers vote on a review? Afterwards the review
+	/// expires and can be requested again, the reviewers that did not vote lose reputation.
+	type ReviewTimeout: Get<Self::BlockNumber>;
+
+	/// How much does the subject reserve while its review is open? The deposit is returned
+	/// once the review is concluded or expired.
+	type ReviewDeposit: Get<BalanceOf<Self>>;
+
+	/// How many blocks must pass between two review requests of the same identity?
+	/// Limits how often an identity occupies the reviewer pool.
+	type ReviewRequestInterval: Get<Self::BlockNumber>;
+
+	/// How many open reviews can await the vote of a reviewer at once? Reviewers with a full
+	/// inbox are not assigned to further reviews.
+	type MaxPendingReviews: Get<u32>;
+
+	/// How much does the subject reserve to appeal a rejected review? The deposit is returned
+	/// if the appeal is approved or expires and burned if the appeal is rejected.
+	type ReviewAppealDeposit: Get<BalanceOf<Self>>;
+
+	/// Requirements of the identity levels, the first entry applies to level 1. Levels without
+	/// an entry are reviewed by MaxReviewers reviewers without further requirements.
+	type LevelRequirements: Get<Vec<LevelRequirement<Self::BlockNumber>>>;
+
+	/// How many secondary addresses (e.g. hot wallets) can be linked to an identity?
+	type MaxLinkedAddresses: Get<u32>;
+
+	/// How many guardians can an identity appoint for its recovery?
+	type MaxGuardians: Get<u32>;
+
+	/// How many blocks must pass after the guardians approved a recovery until it can be
+	/// finalized? The identity can cancel the recovery during that time.
+	type RecoveryDelay: Get<Self::BlockNumber>;
+
+	/// How many percent of the assigned reviewers must approve a review to grant the level?
+	type ReviewApprovalThreshold: Get<Permill>;
+
+	/// How many blocks is an identity level valid until it decays and has to be re-verified?
+	type IdentityValidityPeriod: Get<Self::BlockNumber>;
+
+	/// How many blocks before the expiry are users notified to request a re-verification?
+	type ReverificationNotice: Get<Self::BlockNumber>;
+
+	/// Registry that credits the reviewers of audited reviews with reputation.
+	/// () disables the reputation tracking
+	type Reputation: ReputationRegistry<IdentityId<Self>>;
+
+	/// Weight information for the extrinsics of this pallet
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as CommunityIdentity {
+		/// Identity -> Identity data (level and proof)
+		pub Identities get(fn identities): map hasher(identity)
+			IdentityId<T> => Option<PhysicalIdentityData<T::Timestamp, T::AccountId, ProofType, T::BlockNumber>> = None;
+		/// Number of stored identities, including identities with level 0
+		pub IdentityCount get(fn identity_count): u32;
+		/// Identity level -> Number of identities with that level. Expired levels are counted
+		/// as DECAYED_IDENTITY_LEVEL, disputed and banned identities with their stored level.
+		pub IdentityCountByLevel get(fn identity_count_by_level): map hasher(identity)
+			IdentityLevel => u32;
+		/// Block -> Identities whose level decays in that block unless they were re-verified
+		pub LevelExpiries get(fn level_expiries): map hasher(identity)
+			T::BlockNumber => Vec<IdentityId<T>> = Vec::new();
+		/// Block -> Accounts that are notified in that block to request a re-verification
+		pub ExpiryNotices get(fn expiry_notices): map hasher(identity)
+			T::BlockNumber => Vec<T::AccountId> = Vec::new();
+		/// Identity -> Signing scheme that must be used for governance critical calls
+		pub Attestations get(fn attestations): map hasher(identity)
+			IdentityId<T> => Option<SigningScheme<T::AccountId>> = None;
+		/// Attested signer -> Identity
+		pub SignerToIdentity get(fn signer_to_identity): map hasher(identity)
+			T::AccountId => Option<IdentityId<T>> = None;
+		/// Identity -> Secondary addresses that act on behalf of the identity
+		pub LinkedAddresses get(fn linked_addresses): map hasher(identity)
+			IdentityId<T> => Vec<T::AccountId> = Vec::new();
+		/// Secondary address -> Identity it is linked to
+		pub AddressToIdentity get(fn address_to_identity): map hasher(identity)
+			T::AccountId => Option<IdentityId<T>> = None;
+		/// Identity -> Guardians that can recover the identity and how many of them must approve.
+		/// The reviewers of the last review recover identities without guardians.
+		pub Guardians get(fn guardians): map hasher(identity)
+			IdentityId<T> => Option<(Vec<T::AccountId>, u32)> = None;
+		/// Identity -> Running recovery of the identity
+		pub Recoveries get(fn recoveries): map hasher(identity)
+			IdentityId<T> => Option<Recovery<T::AccountId, T::BlockNumber>> = None;
+		/// Identity -> Main address of a recovered identity
+		pub RecoveredAddresses get(fn recovered_addresses): map hasher(identity)
+			IdentityId<T> => Option<T::AccountId> = None;
+		/// Address -> Identity that offered to link the address, the address has to accept it
+		pub LinkOffers get(fn link_offers): map hasher(identity)
+			T::AccountId => Option<IdentityId<T>> = None;
+		/// Identity -> Public display handle and contact document
+		pub Metadata get(fn metadata): map hasher(identity)
+			IdentityId<T> => Option<IdentityMetadata> = None;
+		/// Identity -> Region in which the identity can meet for physical verifications
+		pub Regions get(fn regions): map hasher(identity)
+			IdentityId<T> => Option<RegionCode> = None;
+		/// Ticket -> Were the reviewers reassigned from the region of the subject?
+		pub RegionalAssignments get(fn regional_assignments): map hasher(identity)
+			TicketId => bool = false;
+		/// Storage layout version, used to determine which migrations have to be applied
+		StorageVersion build(|_| Releases::V8LevelCounts): Releases;
+		/// Index of the next peer review ticket
+		pub NextTicketId get(fn next_ticket_id): TicketId;
+		/// Ticket -> Peer review request, assigned reviewers and progress
+		pub Tickets get(fn tickets): map hasher(identity)
+			TicketId => Option<ReviewTicket<T::Timestamp, T::AccountId, T::BlockNumber>> = None;
+		/// Account -> Ticket of its open peer review request
+		pub PendingReviews get(fn pending_reviews): map hasher(identity)
+			T::AccountId => Option<TicketId> = None;
+		/// Identity -> Ticket of the review that granted its current level
+		pub LastReviews get(fn last_reviews): map hasher(identity)
+			IdentityId<T> => Option<TicketId> = None;
+		/// Ticket -> Deposit the subject reserved for its open review
+		pub ReviewDeposits get(fn review_deposits): map hasher(identity)
+			TicketId => BalanceOf<T> = Zero::zero();
+		/// Ticket -> Deposit the subject reserved to appeal the rejection of the review
+		pub AppealDeposits get(fn appeal_deposits): map hasher(identity)
+			TicketId => BalanceOf<T> = Zero::zero();
+		/// Block -> Tickets that expire in that block unless they were concluded
+		pub ReviewExpiries get(fn review_expiries): map hasher(identity)
+			T::BlockNumber => Vec<TicketId> = Vec::new();
+		/// (Ticket, Reviewer) -> Vote of an assigned reviewer
+		pub ReviewVotes get(fn review_votes): double_map hasher(identity) TicketId,
+			hasher(identity) T::AccountId => Option<ReviewVote> = None;
+		/// Ticket -> Hash of the verification statement the subject confirmed
+		pub VerificationStatements get(fn verification_statements): map hasher(identity)
+			TicketId => Option<ProofType> = None;
+		/// Reviewer identity -> Open reviews that await the vote of the reviewer
+		/// (at most MaxPendingReviews)
+		pub PendingByReviewer get(fn pending_by_reviewer): map hasher(identity)
+			IdentityId<T> => Vec<TicketId> = Vec::new();
+		/// Identity -> Reviewers that support the revocation and their evidence
+		pub RevocationSupport get(fn revocation_support): map hasher(identity)
+			IdentityId<T> => Vec<(T::AccountId, DocumentCID)> = Vec::new();
+		/// Identity -> Dispute about the revocation of the identity
+		pub Disputes get(fn disputes): map hasher(identity)
+			IdentityId<T> => Option<Dispute<T::BlockNumber>> = None;
+		/// Identity -> Whether the council banned the identity, banned identities have level 0
+		pub Banned get(fn banned): map hasher(identity)
+			IdentityId<T> => bool = false;
+		/// Identity -> Block in which the identity level was upgraded the last time
+		pub LastUpgrades get(fn last_upgrades): map hasher(identity)
+			IdentityId<T> => Option<T::BlockNumber> = None;
+		/// Identity level -> Accounts with that level, they review requests for lower levels
+		pub ReviewerPool get(fn reviewer_pool): map hasher(identity)
+			IdentityLevel => Vec<T::AccountId> = Vec::new();
+		/// Account -> Block until a new peer review cannot be requested
+		pub ReviewCooldowns get(fn review_cooldowns): map hasher(identity)
+			T::AccountId => Option<T::BlockNumber> = None;
+		/// Identity -> Block in which the identity requested its last peer review
+		pub LastReviewRequests get(fn last_review_requests): map hasher(identity)
+			IdentityId<T> => Option<T::BlockNumber> = None;
+
+		/// Review process -> Participants and outcome of the completed review
+		pub CompletedReviews get(fn completed_reviews): map hasher(identity)
+			TicketId => Option<ReviewRecord<T::Timestamp, T::AccountId>> = None;
+		/// Reviewer -> Bonded deposit of the reviewer
+		pub ReviewerBonds get(fn reviewer_bonds): map hasher(identity)
+			T::AccountId => Option<ReviewerBond<BalanceOf<T>, T::BlockNumber>> = None;
+		/// Reviewers that were slashed for approving a fraudulent review and are permanently
+		/// removed from the reviewer pool
+		pub ExcludedReviewers get(fn excluded_reviewers): map hasher(identity)
+			T::AccountId => bool = false;
+		/// Review process -> Reviewers that voted on a concluded review and did not claim their
+		/// reward yet
+		pub ReviewRewards get(fn review_rewards): map hasher(identity)
+			TicketId => Vec<T::AccountId> = Vec::new();
+		/// Review process -> Auditor that pulled the review for a manual audit
+		pub Audits get(fn audits): map hasher(identity)
+			TicketId => Option<T::AccountId> = None;
+		/// (Audit era, number of audits that were started in that era)
+		pub AuditsInEra get(fn audits_in_era): (T::BlockNumber, u32) = (T::BlockNumber::zero(), 0);
+		/// Reviewer -> Outcomes of the audits of reviews the reviewer has performed
+		pub Reputation get(fn reputation): map hasher(identity)
+			IdentityId<T> => ReviewerReputation = ReviewerReputation::default();
+	}
+	add_extra_genesis {
+		/// Accounts that are granted an identity level without peer review
+		config(bootstrap): Vec<(T::AccountId, IdentityLevel)>;
+		/// Accounts that were verified before the launch, including the proof of the verification.
+		/// Their level is not limited by MaxBootstrapLevel, they form the initial reviewer pool.
+		config(identities): Vec<(T::AccountId, IdentityLevel, ProofType)>;
+		build(|config: &GenesisConfig<T>| {
+			for (account, level) in config.bootstrap.iter() {
+				<Module<T>>::do_bootstrap_identity(account.clone(), *level)
+					.expect("Bootstrap identity level must not exceed MaxBootstrapLevel");
+			}
+
+			for (account, level, proof) in config.identities.iter() {
+				assert!(*level > 0, "Genesis identities must have an identity level above 0");
+				assert!(!<Identities<T>>::contains_key(account), "Genesis identities must be unique");
+				<Module<T>>::insert_identity(account.clone(), *level, *proof, ProofKind::Genesis);
+			}
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+		Balance = BalanceOf<T>,
+	{
+		/// A peer review was requested and reviewers were assigned
+		/// \[Subject, Ticket, Level, Reviewers\]
+		ReviewRequested(AccountId, TicketId, IdentityLevel, Vec<AccountId>),
+		/// A reviewer was assigned to the review of a subject \[Ticket, Reviewer, Subject\]
+		ReviewerAssigned(TicketId, AccountId, AccountId),
+		/// The subject appealed a rejected review, fresh reviewers were assigned
+		/// \[Ticket, Subject, Reviewers\]
+		ReviewAppealed(TicketId, AccountId, Vec<AccountId>),
+		/// The appeal was rejected as well, the appeal deposit was burned
+		/// \[Ticket, Subject, Burned\]
+		AppealRejected(TicketId, AccountId, Balance),
+		/// An assigned reviewer voted on a review \[Subject, Reviewer, Approved\]
+		ReviewVoted(AccountId, AccountId, bool),
+		/// The subject confirmed the statement of its verification \[Ticket, Statement\]
+		VerificationConfirmed(TicketId, ProofType),
+		/// A batched approval was processed, the error is None if the approval was recorded
+		/// \[Reviewer, Ticket, Error\]
+		BatchedApproval(AccountId, TicketId, Option<DispatchError>),
+		/// Enough reviewers approved the review, the level was granted \[Subject, Level\]
+		IdentityApproved(AccountId, IdentityLevel),
+		/// The approval threshold can't be reached anymore, the level was not granted
+		/// \[Subject, Level\]
+		IdentityRejected(AccountId, IdentityLevel),
+		/// The reviewers did not conclude the review in time, the deposit was returned
+		/// \[Ticket, Subject, Unresponsive reviewers\]
+		ReviewExpired(TicketId, AccountId, Vec<AccountId>),
+		/// The identity level expires soon, the account should request a re-verification
+		/// \[Account, Level, Expiry\]
+		ReverificationDue(AccountId, IdentityLevel, BlockNumber),
+		/// A reviewer supports the revocation of an identity \[Identity, Reviewer, Evidence\]
+		RevocationSupported(AccountId, AccountId, DocumentCID),
+		/// The revocation of an identity is disputed until the appeal window closes
+		/// \[Identity, AppealUntil\]
+		IdentityDisputed(AccountId, BlockNumber),
+		/// The subject appealed the revocation \[Identity, Appeal\]
+		RevocationAppealed(AccountId, DocumentCID),
+		/// The identity level was permanently reduced to zero \[Identity\]
+		IdentityRevoked(AccountId),
+		/// The council banned an identity \[Identity\]
+		IdentityBanned(AccountId),
+		/// The council lifted the ban of an identity \[Identity\]
+		IdentityUnbanned(AccountId),
+		/// The revocation was dismissed, the identity regains its rights \[Identity\]
+		DisputeDismissed(AccountId),
+		/// A reviewer claimed the reward for a concluded review \[Ticket, Reviewer, Reward\]
+		ReviewRewardPaid(TicketId, AccountId, Balance),
+		/// An account bonded a deposit to review identities \[Reviewer, Bond\]
+		ReviewerBonded(AccountId, Balance),
+		/// A reviewer started unbonding and is not assigned to reviews anymore
+		/// \[Reviewer, UnbondingUntil\]
+		ReviewerUnbonding(AccountId, BlockNumber),
+		/// A reviewer withdrew the bond \[Reviewer, Bond\]
+		BondWithdrawn(AccountId, Balance),
+		/// The council proved a review fraudulent, the approving reviewers were slashed
+		/// \[Ticket, Evidence, Approvers\]
+		ReviewChallenged(TicketId, DocumentCID, Vec<AccountId>),
+		/// A reviewer was slashed and removed from the reviewer pool \[Reviewer, Slashed\]
+		ReviewerSlashed(AccountId, Balance),
+		/// An identity offered to link a secondary address \[Identity, Address\]
+		LinkOffered(AccountId, AccountId),
+		/// A secondary address was linked to an identity \[Identity, Address\]
+		AddressLinked(AccountId, AccountId),
+		/// A secondary address was unlinked from an identity \[Identity, Address\]
+		AddressUnlinked(AccountId, AccountId),
+		/// An identity appointed guardians for its recovery \[Identity, Guardians, Threshold\]
+		GuardiansSet(AccountId, Vec<AccountId>, u32),
+		/// A guardian approved the recovery of an identity \[Identity, Guardian, NewAccount\]
+		RecoveryVoted(AccountId, AccountId, AccountId),
+		/// Enough guardians approved the recovery, it can be finalized after the delay
+		/// \[Identity, NewAccount, ExecutableFrom\]
+		RecoveryApproved(AccountId, AccountId, BlockNumber),
+		/// The identity cancelled its recovery \[Identity\]
+		RecoveryCancelled(AccountId),
+		/// The identity was recovered, the new account is its main address \[Identity, NewAccount\]
+		IdentityRecovered(AccountId, AccountId),
+		/// An identity published its display handle and contact document \[Identity\]
+		MetadataSet(AccountId),
+		/// An identity removed its metadata \[Identity\]
+		MetadataCleared(AccountId),
+		/// An identity declared or removed its region \[Identity, Region\]
+		RegionSet(AccountId, Option<RegionCode>),
+		/// The reviewers of a review were reassigned from the region of the subject
+		/// \[Ticket, Reviewers\]
+		RegionalReviewersAssigned(TicketId, Vec<AccountId>),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The review is already being audited.
+		AlreadyUnderAudit,
+		/// The reviewers of the review were already reassigned or started voting.
+		AssignmentFixed,
+		/// The subject already appealed the revocation.
+		AlreadyAppealed,
+		/// The identity is already disputed.
+		AlreadyDisputed,
+		/// The reviewer already supports the revocation of the identity.
+		AlreadySupported,
+		/// The appeal window of the revocation is closed.
+		AppealWindowClosed,
+		/// The appeal window is still open or the appeal awaits the decision of the council.
+		AppealPending,
+		/// The reviewer already voted on the review.
+		AlreadyReviewed,
+		/// The account already bonded a deposit as reviewer.
+		AlreadyBonded,
+		/// The guardian already approved the recovery.
+		AlreadyApprovedRecovery,
+		/// The reviewer is already unbonding.
+		AlreadyUnbonding,
+		/// The signer is already attested by an identity.
+		AlreadyAttested,
+		/// The identity is already banned
+		AlreadyBanned,
+		/// The address already belongs to an identity, as identity, signer or linked address.
+		AddressInUse,
+		/// The identity requires governance critical calls to be signed by its attested signer.
+		AttestedSignerRequired,
+		/// The identity has not attested a signing scheme.
+		NoAttestation,
+		/// The maximum number of audits in this era was reached.
+		AuditLimitReached,
+		/// The bond is locked until the unbonding period is over.
+		BondLocked,
+		/// The identity level exceeds the level that can be granted without peer review.
+		BootstrapLevelTooHigh,
+		/// The guardians must be distinct, at most MaxGuardians and the threshold must be between
+		/// 1 and the number of guardians.
+		InvalidGuardians,
+		/// The region of the subject has fewer eligible reviewers than reviewers are assigned.
+		NotEnoughRegionalReviewers,
+		/// The requested identity level must be exactly one above the current level, or equal to
+		/// it once the re-verification is due.
+		InvalidIdentityLevel,
+		/// The proof does not match the verification statement the subject confirmed.
+		InvalidProof,
+		/// Identity levels are climbed one at a time, the requested level is too high.
+		LevelSkipNotAllowed,
+		/// No identity with a level above the requested level is available as reviewer.
+		NoReviewersAvailable,
+		/// The account is not allowed to audit reviews.
+		NotAnAuditor,
+		/// The account is not assigned to the review or its level is too low to revoke the identity.
+		NotAReviewer,
+		/// The review was not rejected, was appealed already or the appeal window is closed.
+		NotAppealable,
+		/// The account has no identity or the identity has no level that could be revoked.
+		NoIdentity,
+		/// No identity offered to link the address.
+		NoLinkOffer,
+		/// The account has no unclaimed reward for the review.
+		NoRewardClaimable,
+		/// The account did not bond a deposit as reviewer.
+		NotBonded,
+		/// The identity is not disputed.
+		NotDisputed,
+		/// The identity is not banned
+		NotBanned,
+		/// The account is not a guardian of the identity.
+		NotAGuardian,
+		/// The identity is not being recovered.
+		NoRecovery,
+		/// The subject did not declare a region.
+		NoRegion,
+		/// The address is not linked to the identity.
+		NotLinked,
+		/// Only the identity itself or its attested signer can manage the metadata.
+		NotIdentityOwner,
+		/// The review is not being audited by the caller.
+		NotTheAuditor,
+		/// Only the subject of the review or its attested signer can confirm the verification.
+		NotTheSubject,
+		/// The account already has an open peer review request.
+		ReviewAlreadyRequested,
+		/// The account was removed from the reviewer pool for approving a fraudulent review.
+		ReviewerExcluded,
+		/// The recovery was not approved by enough guardians or its delay is not over.
+		RecoveryNotExecutable,
+		/// A guardian approved the recovery to another account.
+		RecoveryTargetMismatch,
+		/// The cooldown after a rejected review is still active.
+		ReviewCooldownActive,
+		/// The identity requested a peer review less than ReviewRequestInterval blocks ago.
+		ReviewRequestTooFrequent,
+		/// The review does not exist or was not completed yet.
+		ReviewNotExistant,
+		/// The review was already concluded or expired.
+		ReviewClosed,
+		/// The reviewers did not conclude the review in time.
+		ReviewExpired,
+		/// The reviewer belongs to the identity under review.
+		SelfReviewForbidden,
+		/// The subject already confirmed a verification statement.
+		StatementAlreadyConfirmed,
+		/// The identity already linked MaxLinkedAddresses addresses.
+		TooManyLinkedAddresses,
+		/// The batch contains more than MaxApprovalBatch approvals.
+		TooManyApprovals,
+		/// Not enough blocks passed since the previous upgrade of the identity level.
+		UpgradeCooldownActive,
+		/// The subject did not confirm the verification statement yet.
+		VerificationNotConfirmed,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Which identity level can be granted at most without peer review?
+		const MaxBootstrapLevel: IdentityLevel = T::MaxBootstrapLevel::get();
+
+		/// How many blocks does an audit era last?
+		const AuditEraLength: T::BlockNumber = T::AuditEraLength::get();
+
+		/// How many reviews can be pulled for an audit per era?
+		const MaxAuditsPerEra: u32 = T::MaxAuditsPerEra::get();
+
+		/// How much must reviewers bond to be assigned to peer reviews? The bond is slashed if they
+		/// approved a fraudulent review.
+		const ReviewerStake: BalanceOf<T> = T::ReviewerStake::get();
+
+		/// How many blocks must pass after unbonding until reviewers can withdraw their bond?
+		const ReviewerUnbondingPeriod: T::BlockNumber = T::ReviewerUnbondingPeriod::get();
+
+		/// How many blocks must pass after a rejected review until a new review can be requested?
+		const ReviewCooldown: T::BlockNumber = T::ReviewCooldown::get();
+
+		/// Id of the governance pot that pays the reviewer rewards
+		const TreasuryModuleId: ModuleId = T::TreasuryModuleId::get();
+
+		/// How much does every reviewer that voted on a concluded review receive from the
+		/// governance pot?
+		const ReviewerReward: BalanceOf<T> = T::ReviewerReward::get();
+
+		/// How many reviewers participate in a peer review at most? (required for weight calculation)
+		const MaxReviewers: u32 = T::MaxReviewers::get();
+
+		/// How many reviews can a reviewer approve in a single batch?
+		const MaxApprovalBatch: u32 = T::MaxApprovalBatch::get();
+
+		/// Priority of the regional reviewer assignments submitted by the off-chain worker
+		const UnsignedPriority: TransactionPriority = T::UnsignedPriority::get();
+
+		/// How many blocks can the assigned reviewers vote on a review?
+		const ReviewTimeout: T::BlockNumber = T::ReviewTimeout::get();
+
+		/// How much does the subject reserve while its review is open?
+		const ReviewDeposit: BalanceOf<T> = T::ReviewDeposit::get();
+
+		/// How many blocks must pass between two review requests of the same identity?
+		const ReviewRequestInterval: T::BlockNumber = T::ReviewRequestInterval::get();
+
+		/// How many open reviews can await the vote of a reviewer at once?
+		const MaxPendingReviews: u32 = T::MaxPendingReviews::get();
+
+		/// How much does the subject reserve to appeal a rejected review?
+		const ReviewAppealDeposit: BalanceOf<T> = T::ReviewAppealDeposit::get();
+
+		/// Requirements of the identity levels, the first entry applies to level 1
+		const LevelRequirements: Vec<LevelRequirement<T::BlockNumber>> = T::LevelRequirements::get();
+
+		/// How many secondary addresses (e.g. hot wallets) can be linked to an identity?
+		const MaxLinkedAddresses: u32 = T::MaxLinkedAddresses::get();
+
+		/// How many guardians can an identity appoint for its recovery?
+		const MaxGuardians: u32 = T::MaxGuardians::get();
+
+		/// How many blocks must pass after the guardians approved a recovery until it can be
+		/// finalized?
+		const RecoveryDelay: T::BlockNumber = T::RecoveryDelay::get();
+
+		/// How many percent of the assigned reviewers must approve a review to grant the level?
+		const ReviewApprovalThreshold: Permill = T::ReviewApprovalThreshold::get();
+
+		/// How many blocks is an identity level valid until it decays and has to be re-verified?
+		const IdentityValidityPeriod: T::BlockNumber = T::IdentityValidityPeriod::get();
+
+		/// How many blocks before the expiry are users notified to request a re-verification?
+		const ReverificationNotice: T::BlockNumber = T::ReverificationNotice::get();
+
+		/// How many reviewers with a level above the level of an identity must support its
+		/// revocation to dispute it?
+		const RevocationQuorum: u32 = T::RevocationQuorum::get();
+
+		/// How many blocks can the subject of a revocation appeal it?
+		const AppealPeriod: T::BlockNumber = T::AppealPeriod::get();
+
+		/// Expire the reviews that were not concluded in time, recount the identities whose level
+		/// expired and notify the accounts whose identity level expires soon
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expired: Vec<TicketId> = <ReviewExpiries<T>>::take(now);
+			let swept: u64 = expired.len() as u64;
+
+			for review_process in expired.into_iter() {
+				Self::expire_review(review_process);
+			}
+
+			let decayed: Vec<IdentityId<T>> = <LevelExpiries<T>>::take(now);
+			let recounted: u64 = decayed.len() as u64;
+
+			for identity in decayed.into_iter() {
+				// Identities that were re-verified in the meantime expire later
+				if let Some(data) = <Identities<T>>::get(&identity) {
+					if data.expires + One::one() == now {
+						Self::move_in_level_count(Some(data.level), Some(data.level.min(DECAYED_IDENTITY_LEVEL)));
+					}
+				}
+			}
+
+			let accounts: Vec<T::AccountId> = <ExpiryNotices<T>>::take(now);
+			let notified: u64 = accounts.len() as u64;
+
+			for account in accounts.into_iter() {
+				let data = match <Identities<T>>::get(Self::do_get_identity_id(&account)) {
+					Some(data) => data,
+					None => continue,
+				};
+
+				// Identities that were re-verified in the meantime have a later notice
+				if data.expires.saturating_sub(T::ReverificationNotice::get()) <= now && data.level > 0 {
+					Self::deposit_event(RawEvent::ReverificationDue(account, data.level, data.expires));
+				}
+			}
+
+			// Every expired review reads the identities of the reviewers and writes their reputation
+			let reviewers: u64 = u64::from(T::MaxReviewers::get());
+			T::DbWeight::get().reads_writes(
+				notified.saturating_add(recounted.saturating_mul(3)).saturating_add(3)
+					.saturating_add(swept.saturating_mul(reviewers.saturating_mul(3) + 2)),
+				swept.saturating_mul(reviewers + 5).saturating_add(recounted.saturating_mul(2)).saturating_add(3),
+			)
+		}
+
+		/// Propose reviewers from the region of the subjects that requested a review
+		fn offchain_worker(now: T::BlockNumber) {
+			offchain::propose_regional_reviewers::<T>(now);
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		/// As root, grant an identity level without peer review
+		#[weight = T::WeightInfo::bootstrap_identity()]
+		fn bootstrap_identity(origin, account: T::AccountId, identity_level: IdentityLevel) {
+			ensure_root(origin)?;
+			Self::do_bootstrap_identity(account, identity_level)?;
+		}
+
+		/// Request a peer review to gain the next IdentityLevel at the proposed date `at`.
+		/// Reviewers that satisfy the LevelRequirements of the level are assigned randomly.
+		#[weight = T::WeightInfo::request_peer_review(T::MaxReviewers::get())]
+		fn request_peer_review(origin, identity_level: IdentityLevel, at: T::Timestamp) {
+			let caller = ensure_signed(origin)?;
+			Self::do_request_peer_review(caller, identity_level, at)?;
+			// What happens here is that it either returns the Err(e) or Ok(()), DispatchResult is implicit
+		}
+
+		/// As the subject of a review, confirm the hash of the verification statement that was
+		/// agreed on with the reviewers. Approvals must prove that they refer to this statement.
+		#[weight = T::WeightInfo::confirm_verification()]
+		pub fn confirm_verification(origin, review_process: TicketId, statement: ProofType) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			let ticket = <Tickets<T>>::get(review_process).ok_or(Error::<T>::ReviewNotExistant)?;
+			ensure!(ticket.status == ReviewStatus::Pending, Error::<T>::ReviewClosed);
+			ensure!(Self::do_get_identity_id(&caller) == Self::do_get_identity_id(&ticket.subject),
+				Error::<T>::NotTheSubject
+			);
+			ensure!(!<VerificationStatements>::contains_key(review_process), Error::<T>::StatementAlreadyConfirmed);
+			<VerificationStatements>::insert(review_process, statement);
+			Self::deposit_event(RawEvent::VerificationConfirmed(review_process, statement));
+		}
+
+		/// As an assigned reviewer, approve a reviewed PhysicalIdentity by supplying a proof share.
+		/// The level is granted once ReviewApprovalThreshold of the reviewers approved.
+		#[weight = T::WeightInfo::approve_identity(T::MaxReviewers::get())]
+		pub fn approve_identity(origin, review_process: TicketId, proof_data: ProofType) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_approve_identity(caller, review_process, proof_data)?;
+		}
+
+		/// As an assigned reviewer, approve up to MaxApprovalBatch reviews at once, e.g. after
+		/// a verification event. Failing approvals don't abort the batch, the outcome of every
+		/// approval is reported by a BatchedApproval event.
+		#[weight = T::WeightInfo::approve_identity(T::MaxReviewers::get())
+			.saturating_mul(approvals.len() as Weight)]
+		pub fn approve_identities(origin, approvals: Vec<(TicketId, ProofType)>) {
+			let caller = ensure_signed(origin)?;
+			ensure!(approvals.len() as u32 <= T::MaxApprovalBatch::get(), Error::<T>::TooManyApprovals);
+			Self::do_ensure_attested_signer(&caller)?;
+
+			for (review_process, proof_data) in approvals.into_iter() {
+				// Votes are validated before anything is written, a failed approval leaves no trace
+				let error: Option<DispatchError> =
+					Self::do_approve_identity(caller.clone(), review_process, proof_data).err();
+				Self::deposit_event(RawEvent::BatchedApproval(caller.clone(), review_process, error));
+			}
+		}
+		
+		/// As an assigned reviewer, reject a reviewed PhysicalIdentity. The review is rejected
+		/// once ReviewApprovalThreshold can't be reached anymore.
+		#[weight = T::WeightInfo::reject_identity(T::MaxReviewers::get())]
+		pub fn reject_identity(origin, review_process: TicketId) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_reject_identity(caller, review_process)?;
+		}
+
+		/// As the subject of a rejected review, appeal the rejection during the review cooldown.
+		/// ReviewAppealDeposit is reserved and fresh reviewers are assigned, more than reviewed
+		/// the rejected review. The rejection of an appeal is final and burns the deposit.
+		#[weight = T::WeightInfo::appeal_review(T::MaxReviewers::get())]
+		pub fn appeal_review(origin, review_process: TicketId) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_appeal_review(caller, review_process)?;
+		}
+
+		/// As a participant, report a missing participant
+		#[weight = T::WeightInfo::report_missing(missing.len() as u32)]
+		pub fn report_missing(origin, review_process: TicketId, missing: Vec<IdentityId<T>>) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_report_missing(review_process, missing)?;
+		}
+
+		/// As an auditor (council member), pull a completed review for a manual audit
+		#[weight = T::WeightInfo::audit_review()]
+		pub fn audit_review(origin, review_process: TicketId) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_audit_review(Self::do_get_identity_id(&caller), review_process)?;
+		}
+
+		/// As the auditor of a review, publish the outcome of the audit. If fraud is confirmed,
+		/// the identity of the subject is revoked and the bonds of the reviewers are slashed.
+		#[weight = T::WeightInfo::conclude_audit(T::MaxReviewers::get())]
+		pub fn conclude_audit(origin, review_process: TicketId, fraud: bool) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_conclude_audit(Self::do_get_identity_id(&caller), review_process, fraud)?;
+		}
+
+		/// Attest a signing scheme (e.g. a hardware wallet key or a multisig account). Once attested,
+		/// governance critical calls of the identity must be signed by the attested signer.
+		#[weight = T::WeightInfo::attest_signing_scheme(scheme.signatory_count())]
+		pub fn attest_signing_scheme(origin, scheme: SigningScheme<T::AccountId>) {
+			let caller = ensure_signed(origin)?;
+			Self::do_attest_signing_scheme(caller, scheme)?;
+		}
+
+		/// As the attested signer, remove the attestation of the identity
+		#[weight = T::WeightInfo::remove_attestation()]
+		pub fn remove_attestation(origin) {
+			let caller = ensure_signed(origin)?;
+			Self::do_remove_attestation(caller)?;
+		}
+
+		/// As the council, or as one of RevocationQuorum reviewers with a level above the level of
+		/// the identity, revoke an identity. The identity is disputed and can't propose or vote,
+		/// its subject can appeal within AppealPeriod.
+		#[weight = T::WeightInfo::revoke_identity()]
+		pub fn revoke_identity(origin, identity: IdentityId<T>, evidence: DocumentCID) {
+			let reviewer: Option<T::AccountId> = match T::CouncilOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			if let Some(caller) = reviewer.as_ref() {
+				Self::do_ensure_attested_signer(caller)?;
+			}
+
+			Self::do_revoke_identity(reviewer, identity, evidence)?;
+		}
+
+		/// As the subject of a disputed identity, appeal the revocation within the appeal window.
+		/// The council resolves the dispute afterwards.
+		#[weight = T::WeightInfo::appeal_revocation()]
+		pub fn appeal_revocation(origin, appeal: DocumentCID) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			let identity: IdentityId<T> = Self::do_get_identity_id(&caller);
+
+			<Disputes<T>>::try_mutate(&identity, |dispute| -> Result<(), DispatchError> {
+				let dispute = dispute.as_mut().ok_or(Error::<T>::NotDisputed)?;
+				ensure!(frame_system::Module::<T>::block_number() <= dispute.appeal_until,
+					Error::<T>::AppealWindowClosed
+				);
+				ensure!(dispute.appeal.is_none(), Error::<T>::AlreadyAppealed);
+				dispute.appeal = Some(appeal);
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::RevocationAppealed(identity, appeal));
+		}
+
+		/// As the council, resolve a dispute: either revoke the identity permanently or dismiss
+		/// the revocation
+		#[weight = T::WeightInfo::resolve_dispute()]
+		pub fn resolve_dispute(origin, identity: IdentityId<T>, revoke: bool) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(<Disputes<T>>::contains_key(&identity), Error::<T>::NotDisputed);
+
+			if revoke {
+				Self::finish_revocation(&identity);
+			} else {
+				<Disputes<T>>::remove(&identity);
+				Self::deposit_event(RawEvent::DisputeDismissed(identity));
+			}
+		}
+
+		/// Permanently revoke a disputed identity whose appeal window closed without an appeal
+		#[weight = T::WeightInfo::finalize_revocation()]
+		pub fn finalize_revocation(origin, identity: IdentityId<T>) {
+			ensure_signed(origin)?;
+			let dispute = <Disputes<T>>::get(&identity).ok_or(Error::<T>::NotDisputed)?;
+			ensure!(dispute.appeal.is_none() && frame_system::Module::<T>::block_number() > dispute.appeal_until,
+				Error::<T>::AppealPending
+			);
+			Self::finish_revocation(&identity);
+		}
+
+		/// As the council, ban an identity. Banned identities have level 0 until they are unbanned.
+		#[weight = T::WeightInfo::ban_identity()]
+		pub fn ban_identity(origin, identity: IdentityId<T>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(!<Banned<T>>::get(&identity), Error::<T>::AlreadyBanned);
+			<Banned<T>>::insert(&identity, true);
+			Self::deposit_event(RawEvent::IdentityBanned(identity));
+		}
+
+		/// As the council, lift the ban of an identity
+		#[weight = T::WeightInfo::unban_identity()]
+		pub fn unban_identity(origin, identity: IdentityId<T>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(<Banned<T>>::get(&identity), Error::<T>::NotBanned);
+			<Banned<T>>::remove(&identity);
+			Self::deposit_event(RawEvent::IdentityUnbanned(identity));
+		}
+
+		/// As a reviewer that voted on a concluded review, claim the reward from the governance pot
+		#[weight = T::WeightInfo::claim_review_reward()]
+		pub fn claim_review_reward(origin, review_process: TicketId) {
+			let caller = ensure_signed(origin)?;
+			Self::do_claim_review_reward(caller, review_process)?;
+		}
+
+		/// Bond ReviewerStake to be assigned to peer reviews of identities with a lower level
+		#[weight = T::WeightInfo::bond_reviewer()]
+		pub fn bond_reviewer(origin) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			Self::do_bond_reviewer(caller)?;
+		}
+
+		/// Stop being assigned to peer reviews. The bond can be withdrawn after
+		/// ReviewerUnbondingPeriod.
+		#[weight = T::WeightInfo::unbond_reviewer()]
+		pub fn unbond_reviewer(origin) {
+			let caller = ensure_signed(origin)?;
+			let until: T::BlockNumber = frame_system::Module::<T>::block_number() + T::ReviewerUnbondingPeriod::get();
+
+			<ReviewerBonds<T>>::try_mutate(&caller, |bond| -> Result<(), DispatchError> {
+				let bond = bond.as_mut().ok_or(Error::<T>::NotBonded)?;
+				ensure!(bond.unbonding_until.is_none(), Error::<T>::AlreadyUnbonding);
+				bond.unbonding_until = Some(until);
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::ReviewerUnbonding(caller, until));
+		}
+
+		/// Withdraw the bond once the unbonding period is over
+		#[weight = T::WeightInfo::withdraw_bond()]
+		pub fn withdraw_bond(origin) {
+			let caller = ensure_signed(origin)?;
+			let bond = <ReviewerBonds<T>>::get(&caller).ok_or(Error::<T>::NotBonded)?;
+			ensure!(bond.unbonding_until.map_or(false, |until| frame_system::Module::<T>::block_number() >= until),
+				Error::<T>::BondLocked
+			);
+
+			T::Currency::unreserve(&caller, bond.amount);
+			<ReviewerBonds<T>>::remove(&caller);
+			Self::deposit_event(RawEvent::BondWithdrawn(caller, bond.amount));
+		}
+
+		/// As the council, challenge a completed review whose subject was proven fake. The bonds of
+		/// the approving reviewers are slashed, they are removed from the reviewer pool and the
+		/// identity of the subject is revoked.
+		#[weight = T::WeightInfo::challenge_review(T::MaxReviewers::get())]
+		pub fn challenge_review(origin, review_process: TicketId, evidence: DocumentCID) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			let review = <CompletedReviews<T>>::take(&review_process).ok_or(Error::<T>::ReviewNotExistant)?;
+			// The challenge supersedes a running audit
+			<Audits<T>>::remove(&review_process);
+			Self::punish_fraudulent_review(&review_process, &review);
+			Self::deposit_event(RawEvent::ReviewChallenged(review_process, evidence, review.reviewers));
+		}
+
+		/// Offer to link a secondary address (e.g. a hot wallet) to the identity of the caller.
+		/// Once the address accepts, it acts on behalf of the identity and shares its limits.
+		#[weight = T::WeightInfo::link_address()]
+		pub fn link_address(origin, address: T::AccountId) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			let identity: IdentityId<T> = Self::do_get_identity_id(&caller);
+			Self::ensure_address_linkable(&identity, &address)?;
+			<LinkOffers<T>>::insert(&address, &identity);
+			Self::deposit_event(RawEvent::LinkOffered(identity, address));
+		}
+
+		/// Accept the offer of an identity to link the caller as secondary address
+		#[weight = T::WeightInfo::accept_link()]
+		pub fn accept_link(origin) {
+			let caller = ensure_signed(origin)?;
+			let identity: IdentityId<T> = <LinkOffers<T>>::get(&caller).ok_or(Error::<T>::NoLinkOffer)?;
+			// The address or the identity could have changed since the offer
+			Self::ensure_address_linkable(&identity, &caller)?;
+			<LinkOffers<T>>::remove(&caller);
+			<LinkedAddresses<T>>::append(&identity, &caller);
+			<AddressToIdentity<T>>::insert(&caller, &identity);
+			Self::deposit_event(RawEvent::AddressLinked(identity, caller));
+		}
+
+		/// Unlink a secondary address, either as the identity or as the linked address itself
+		#[weight = T::WeightInfo::unlink_address()]
+		pub fn unlink_address(origin, address: T::AccountId) {
+			let caller = ensure_signed(origin)?;
+
+			if caller != address {
+				Self::do_ensure_attested_signer(&caller)?;
+			}
+
+			let identity: IdentityId<T> = Self::do_get_identity_id(&caller);
+			ensure!(<AddressToIdentity<T>>::get(&address).as_ref() == Some(&identity), Error::<T>::NotLinked);
+			<AddressToIdentity<T>>::remove(&address);
+			<LinkedAddresses<T>>::mutate(&identity, |addresses| addresses.retain(|linked| *linked != address));
+			Self::deposit_event(RawEvent::AddressUnlinked(identity, address));
+		}
+
+		/// Appoint the guardians that can recover the identity if its main address is lost and
+		/// how many of them must approve a recovery. An empty list falls back to the reviewers.
+		#[weight = T::WeightInfo::set_guardians(guardians.len() as u32)]
+		pub fn set_guardians(origin, guardians: Vec<T::AccountId>, threshold: u32) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			let identity: IdentityId<T> = Self::do_get_identity_id(&caller);
+
+			if guardians.is_empty() {
+				<Guardians<T>>::remove(&identity);
+			} else {
+				let mut distinct: Vec<T::AccountId> = guardians.clone();
+				distinct.sort();
+				distinct.dedup();
+				ensure!(distinct.len() == guardians.len()
+					&& guardians.len() as u32 <= T::MaxGuardians::get()
+					&& threshold > 0 && threshold <= guardians.len() as u32
+					&& !guardians.contains(&identity),
+					Error::<T>::InvalidGuardians
+				);
+				<Guardians<T>>::insert(&identity, (&guardians, threshold));
+			}
+
+			Self::deposit_event(RawEvent::GuardiansSet(identity, guardians, threshold));
+		}
+
+		/// As a guardian, approve the recovery of an identity to a new account. Once enough
+		/// guardians approved, the recovery can be finalized after RecoveryDelay.
+		#[weight = T::WeightInfo::vote_recovery(T::MaxGuardians::get().max(T::MaxReviewers::get()))]
+		pub fn vote_recovery(origin, identity: IdentityId<T>, new_account: T::AccountId) {
+			let caller = ensure_signed(origin)?;
+			Self::do_vote_recovery(caller, identity, new_account)?;
+		}
+
+		/// Cancel a running recovery of the identity of the caller, e.g. if the main address was
+		/// not lost
+		#[weight = T::WeightInfo::cancel_recovery()]
+		pub fn cancel_recovery(origin) {
+			let caller = ensure_signed(origin)?;
+			Self::do_ensure_attested_signer(&caller)?;
+			let identity: IdentityId<T> = Self::do_get_identity_id(&caller);
+			ensure!(<Recoveries<T>>::take(&identity).is_some(), Error::<T>::NoRecovery);
+			Self::deposit_event(RawEvent::RecoveryCancelled(identity));
+		}
+
+		/// Finalize an approved recovery after RecoveryDelay. The identity keeps its IdentityId, so
+		/// its level and its records in other pallets remain valid, and the new account becomes
+		/// its main address and attested signer.
+		#[weight = T::WeightInfo::finalize_recovery()]
+		pub fn finalize_recovery(origin, identity: IdentityId<T>) {
+			ensure_signed(origin)?;
+			let recovery = <Recoveries<T>>::get(&identity).ok_or(Error::<T>::NoRecovery)?;
+			ensure!(recovery.executable_from.map_or(false, |from| frame_system::Module::<T>::block_number() >= from),
+				Error::<T>::RecoveryNotExecutable
+			);
+			Self::ensure_address_unused(&identity, &recovery.new_account)?;
+			<Recoveries<T>>::remove(&identity);
+			Self::do_recover_identity(identity, recovery.new_account);
+		}
+
+		/// Publish a display handle hash and a contact document of the identity
+		#[weight = T::WeightInfo::set_metadata()]
+		pub fn set_metadata(origin, metadata: IdentityMetadata) {
+			let caller = ensure_signed(origin)?;
+			let identity: IdentityId<T> = Self::ensure_identity_owner(&caller)?;
+			<Metadata<T>>::insert(&identity, metadata);
+			Self::deposit_event(RawEvent::MetadataSet(identity));
+		}
+
+		/// Remove the metadata of the identity
+		#[weight = T::WeightInfo::clear_metadata()]
+		pub fn clear_metadata(origin) {
+			let caller = ensure_signed(origin)?;
+			let identity: IdentityId<T> = Self::ensure_identity_owner(&caller)?;
+			<Metadata<T>>::remove(&identity);
+			Self::deposit_event(RawEvent::MetadataCleared(identity));
+		}
+
+		/// Declare the region in which the identity can meet for physical verifications.
+		/// Reviews of identities with a region prefer reviewers from the same region.
+		#[weight = T::WeightInfo::set_region()]
+		pub fn set_region(origin, region: Option<RegionCode>) {
+			let caller = ensure_signed(origin)?;
+			let identity: IdentityId<T> = Self::ensure_identity_owner(&caller)?;
+			<Regions<T>>::mutate_exists(&identity, |stored| *stored = region);
+			Self::deposit_event(RawEvent::RegionSet(identity, region));
+		}
+
+		/// Replace the randomly assigned reviewers of an open review by reviewers that are
+		/// randomly picked from the eligible reviewers of the subject's region. Submitted by
+		/// the off-chain worker.
+		#[weight = T::WeightInfo::assign_regional_reviewers(
+			T::MaxReviewers::get().saturating_mul(REGIONAL_CANDIDATE_FACTOR)
+		)]
+		fn assign_regional_reviewers(origin, review_process: TicketId) {
+			ensure_none(origin)?;
+			let (subject, reviewers) = Self::ensure_regional_assignment(review_process)?;
+			<Tickets<T>>::mutate(review_process, |ticket| {
+				if let Some(ticket) = ticket {
+					for reviewer in ticket.reviewers.iter() {
+						Self::remove_from_inbox(review_process, reviewer);
+					}
+
+					ticket.reviewers = reviewers.clone();
+				}
+			});
+			<RegionalAssignments>::insert(review_process, true);
+			Self::add_to_inboxes(review_process, &reviewers);
+
+			for reviewer in reviewers.iter() {
+				Self::deposit_event(RawEvent::ReviewerAssigned(review_process, reviewer.clone(), subject.clone()));
+			}
+
+			Self::deposit_event(RawEvent::RegionalReviewersAssigned(review_process, reviewers));
+		}
+	}
 }
 
-/// Structure that contains the identity ID, level and proof
-#[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct PhysicalIdentityData<Timestamp, AccountId, ProofData> where
-	ProofData: Codec + Clone + Debug + Eq + PartialEq,
-	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
-	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
-{
-	identity: AccountId,
-	level: IdentityLevel,
-	proof: PhysicalProof<Timestamp, ProofData>,
-}
+impl<T: Trait> Module<T> {
+	fn do_bootstrap_identity(account: T::AccountId, identity_level: IdentityLevel)
+		-> Result<(), DispatchError>
+	{
+		ensure!(identity_level <= T::MaxBootstrapLevel::get(), Error::<T>::BootstrapLevelTooHigh);
+		Self::insert_identity(account, identity_level, ProofType::default(), ProofKind::Bootstrap);
+		Ok(())
+	}
+
+	/// Store the identity of an account, replacing any previous identity data
+	fn insert_identity(account: T::AccountId, level: IdentityLevel, proof: ProofType, kind: ProofKind) {
+		let identity: IdentityId<T> = Self::do_get_identity_id(&account);
+		Self::move_in_reviewer_pool(&account, Self::stored_identity_level(&identity), level);
+		let previous: Option<IdentityLevel> = <Identities<T>>::get(&identity)
+			.map(|data| Self::counted_level(data.level, data.expires));
+		Self::move_in_level_count(previous, Some(level));
+		let expires: T::BlockNumber = frame_system::Module::<T>::block_number() + T::IdentityValidityPeriod::get();
+		<ExpiryNotices<T>>::append(Self::notice_block(expires), &account);
+
+		if level > DECAYED_IDENTITY_LEVEL {
+			<LevelExpiries<T>>::append(expires + One::one(), &identity);
+		}
+
+		let data = PhysicalIdentityData {
+			identity: account,
+			level,
+			proof: PhysicalProof {
+				proof,
+				date: T::Time::now(),
+				kind,
+			},
+			expires,
+		};
+
+		if previous.is_none() {
+			<IdentityCount>::mutate(|count| *count = count.saturating_add(1));
+		}
+
+		<Identities<T>>::insert(identity, data);
+	}
+
+	/// Level with which an identity is counted in IdentityCountByLevel: the stored level, which
+	/// decays to DECAYED_IDENTITY_LEVEL once it expired
+	fn counted_level(level: IdentityLevel, expires: T::BlockNumber) -> IdentityLevel {
+		if frame_system::Module::<T>::block_number() > expires {
+			level.min(DECAYED_IDENTITY_LEVEL)
+		} else {
+			level
+		}
+	}
+
+	/// Move an identity from the counter of its old level to the counter of its new level.
+	/// None stands for an identity that is not (or no longer) stored.
+	fn move_in_level_count(old: Option<IdentityLevel>, new: Option<IdentityLevel>) {
+		if old == new {
+			return;
+		}
+
+		if let Some(old) = old {
+			<IdentityCountByLevel>::mutate_exists(old, |count| {
+				*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
+		}
 
-/// Configure the pallet by specifying the parameters and types on which it depends.
-pub trait Trait: frame_system::Trait {
-	// type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
-	type Timestamp: Parameter + Default + AtLeast32Bit
-		+ Scale<Self::BlockNumber, Output = Self::Timestamp> + Copy;
-}
+		if let Some(new) = new {
+			<IdentityCountByLevel>::mutate(new, |count| *count = count.saturating_add(1));
+		}
+	}
 
-decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		/// Request a peer review to gain a specific IdentityLev
-		#[weight = 10_000]
-		fn request_peer_review(origin, identity_level: IdentityLevel, at: T::Timestamp) {
-			let caller = ensure_signed(origin)?;
-			Self::do_request_peer_review(caller, identity_level, at)?;
-			// What happens here is that it either returns the Err(e) or Ok(()), DispatchResult is implicit
+	fn do_identity_count_from_level(min_level: IdentityLevel) -> u32 {
+		<IdentityCountByLevel>::iter()
+			.filter(|(level, _)| *level >= min_level)
+			.fold(0u32, |total, (_, count)| total.saturating_add(count))
+	}
+
+	/// Block in which the account is notified about the expiry of its identity level
+	fn notice_block(expires: T::BlockNumber) -> T::BlockNumber {
+		let earliest: T::BlockNumber = frame_system::Module::<T>::block_number() + T::BlockNumber::from(1u32);
+		expires.saturating_sub(T::ReverificationNotice::get()).max(earliest)
+	}
+
+	/// Is the re-verification of the identity due, because its level expires soon or expired?
+	fn reverification_due(identity: &IdentityId<T>) -> bool {
+		<Identities<T>>::get(identity).map_or(false, |data|
+			frame_system::Module::<T>::block_number() + T::ReverificationNotice::get() >= data.expires
+		)
+	}
+
+	/// Identity level as it is stored, 0 for accounts without an identity
+	fn stored_identity_level(identity: &IdentityId<T>) -> IdentityLevel {
+		<Identities<T>>::get(identity).map_or(0, |data| data.level)
+	}
+
+	/// Move an account from the reviewer pool of its old identity level into the pool of the
+	/// new level. Accounts with level 0 are not part of any pool.
+	fn move_in_reviewer_pool(account: &T::AccountId, old: IdentityLevel, new: IdentityLevel) {
+		if old == new {
+			return;
 		}
 
-		/// As a reviewer, approve a reviewed PhysicalIdentity by supplying a proof
-		#[weight = 10_000]
-		pub fn approve_identity(origin, review_process: Ticket<T>, proof_data: ProofType) {
-			let _ = ensure_signed(origin)?;
-			Self::do_approve_identity(review_process, proof_data)?;
+		if old > 0 {
+			<ReviewerPool<T>>::mutate_exists(old, |pool| {
+				if let Some(accounts) = pool {
+					accounts.retain(|reviewer| reviewer != account);
+
+					if accounts.is_empty() {
+						*pool = None;
+					}
+				}
+			});
 		}
-		
-		/// As a reviewer, reject a reviewed PhysicalIdentity
-		#[weight = 10_000]
-		pub fn reject_identity(origin, review_process: Ticket<T>) {
-			let _ = ensure_signed(origin)?;
-			Self::do_reject_identity(review_process)?;
+
+		// Slashed reviewers never return into the pool
+		if new > 0 && !<ExcludedReviewers<T>>::get(account) {
+			<ReviewerPool<T>>::append(new, account);
+		}
+	}
+
+	/// Requirements to gain the identity level by peer review
+	fn level_requirement(level: IdentityLevel) -> LevelRequirement<T::BlockNumber> {
+		T::LevelRequirements::get()
+			.get((level as usize).saturating_sub(1))
+			.cloned()
+			.unwrap_or_else(|| LevelRequirement {
+				reviewers: T::MaxReviewers::get(),
+				min_reviewer_level: 0,
+				upgrade_cooldown: Zero::zero(),
+			})
+	}
+
+	/// Did the account upgrade its identity level too recently to request `level`?
+	/// Re-verifications of the current level are not limited.
+	fn upgrade_cooldown_active(account: &T::AccountId, level: IdentityLevel) -> bool {
+		let identity: IdentityId<T> = Self::do_get_identity_id(account);
+
+		if level <= Self::stored_identity_level(&identity) {
+			return false;
 		}
 
-		/// As a participant, report a missing participant
-		#[weight = 10_000]
-		pub fn report_missing(origin, review_process: Ticket<T>, missing: Vec<IdentityId<T>>) {
-			let _ = ensure_signed(origin)?;
-			Self::do_report_missing(review_process, missing)?;
+		<LastUpgrades<T>>::get(&identity).map_or(false, |upgraded| {
+			frame_system::Module::<T>::block_number() < upgraded + Self::level_requirement(level).upgrade_cooldown
+		})
+	}
+
+	/// Minimum identity level and number of the reviewers of a review for `level`
+	fn reviewer_requirement(level: IdentityLevel) -> (IdentityLevel, u32) {
+		let requirement = Self::level_requirement(level);
+		// Reviewers always have a level above the requested level
+		let min_level: IdentityLevel = requirement.min_reviewer_level.max(level.saturating_add(1));
+		(min_level, requirement.reviewers.min(T::MaxReviewers::get()))
+	}
+
+	/// Can the account review the subject? Reviewers are bonded, have at least `min_level`
+	/// and belong to another identity.
+	fn is_eligible_reviewer(account: &T::AccountId, subject: &T::AccountId, min_level: IdentityLevel) -> bool {
+		let identity: IdentityId<T> = Self::do_get_identity_id(account);
+		identity != Self::do_get_identity_id(subject)
+			&& Self::is_bonded_reviewer(account)
+			// Expired reviewers only review once they were re-verified
+			&& Self::do_get_identity_level(&identity) >= min_level
+			&& (<PendingByReviewer<T>>::decode_len(&identity).unwrap_or(0) as u32) < T::MaxPendingReviews::get()
+	}
+
+	/// Accounts of the reviewer pool that can review the subject
+	fn reviewer_candidates(subject: &T::AccountId, min_level: IdentityLevel) -> Vec<T::AccountId> {
+		<ReviewerPool<T>>::iter()
+			.filter(|(pool_level, _)| *pool_level >= min_level)
+			.flat_map(|(_, accounts)| accounts.into_iter())
+			.filter(|account| Self::is_eligible_reviewer(account, subject, min_level))
+			.collect()
+	}
+
+	/// Randomly select bonded accounts that satisfy the LevelRequirements of `level`
+	fn select_reviewers(subject: &T::AccountId, level: IdentityLevel) -> Vec<T::AccountId> {
+		let (min_level, count) = Self::reviewer_requirement(level);
+		let seed: T::Hash = T::Randomness::random(&(b"review", subject).encode());
+		Self::pick_reviewers(seed, Self::reviewer_candidates(subject, min_level), count)
+	}
+
+	/// Pick `count` of the candidates, the order is determined by the seed
+	fn pick_reviewers(seed: T::Hash, mut candidates: Vec<T::AccountId>, count: u32) -> Vec<T::AccountId> {
+		let mut reviewers: Vec<T::AccountId> = Vec::new();
+
+		while !candidates.is_empty() && (reviewers.len() as u32) < count {
+			let entropy: T::Hash = T::Hashing::hash_of(&(seed, reviewers.len() as u32));
+			let mut index_bytes = [0u8; 4];
+			index_bytes.copy_from_slice(&entropy.as_ref()[..4]);
+			let index: usize = u32::from_le_bytes(index_bytes) as usize % candidates.len();
+			reviewers.push(candidates.swap_remove(index));
 		}
+
+		reviewers
 	}
-}
 
-impl<T: Trait> Module<T> {
-	fn do_request_peer_review(user: T::AccountId, _identity_level: IdentityLevel, _at: T::Timestamp)
-		-> Result<T::AccountId, DispatchError>
+	fn do_request_peer_review(user: T::AccountId, identity_level: IdentityLevel, at: T::Timestamp)
+		-> Result<TicketId, DispatchError>
 	{
-		// TODO implement
-		Ok(user)
+		Self::close_expired_review(&user);
+		ensure!(!<PendingReviews<T>>::contains_key(&user), Error::<T>::ReviewAlreadyRequested);
+		ensure!(!Self::review_cooldown_active(&user), Error::<T>::ReviewCooldownActive);
+		// Identity levels are climbed one at a time or re-verified once they expire soon
+		let identity: IdentityId<T> = Self::do_get_identity_id(&user);
+		let now: T::BlockNumber = frame_system::Module::<T>::block_number();
+		ensure!(<LastReviewRequests<T>>::get(&identity)
+			.map_or(true, |last| now >= last + T::ReviewRequestInterval::get()),
+			Error::<T>::ReviewRequestTooFrequent
+		);
+		let current: IdentityLevel = Self::stored_identity_level(&identity);
+		ensure!(identity_level <= current.saturating_add(1), Error::<T>::LevelSkipNotAllowed);
+		ensure!(current.checked_add(1) == Some(identity_level)
+			|| (identity_level == current && current > 0 && Self::reverification_due(&identity)),
+			Error::<T>::InvalidIdentityLevel
+		);
+		ensure!(!Self::upgrade_cooldown_active(&user, identity_level), Error::<T>::UpgradeCooldownActive);
+		let reviewers: Vec<T::AccountId> = Self::select_reviewers(&user, identity_level);
+		ensure!(!reviewers.is_empty(), Error::<T>::NoReviewersAvailable);
+		let deposit: BalanceOf<T> = T::ReviewDeposit::get();
+		T::Currency::reserve(&user, deposit)?;
+
+		let expires: T::BlockNumber = now + T::ReviewTimeout::get();
+		let ticket: TicketId = <NextTicketId>::mutate(|next| {
+			let ticket: TicketId = *next;
+			*next = next.saturating_add(1);
+			ticket
+		});
+		<Tickets<T>>::insert(ticket, ReviewTicket {
+			subject: user.clone(),
+			level: identity_level,
+			requested: now,
+			appointment: at,
+			reviewers: reviewers.clone(),
+			status: ReviewStatus::Pending,
+			expires,
+			appeal: ReviewAppeal::NotAppealed,
+		});
+		<ReviewDeposits<T>>::insert(ticket, deposit);
+		// Votes are accepted until the end of the expiry block
+		<ReviewExpiries<T>>::append(expires + One::one(), ticket);
+		<PendingReviews<T>>::insert(&user, ticket);
+		<ReviewCooldowns<T>>::remove(&user);
+		<LastReviewRequests<T>>::insert(&identity, now);
+		Self::add_to_inboxes(ticket, &reviewers);
+
+		for reviewer in reviewers.iter() {
+			Self::deposit_event(RawEvent::ReviewerAssigned(ticket, reviewer.clone(), user.clone()));
+		}
+
+		Self::deposit_event(RawEvent::ReviewRequested(user, ticket, identity_level, reviewers));
+		Ok(ticket)
+	}
+
+	/// Does the account have an open review request that did not expire yet?
+	fn has_open_review(account: &T::AccountId) -> bool {
+		<PendingReviews<T>>::get(account)
+			.and_then(<Tickets<T>>::get)
+			.map_or(false, |ticket| frame_system::Module::<T>::block_number() <= ticket.expires)
+	}
+
+	/// Close the open review request of the account if it expired before it was swept
+	fn close_expired_review(account: &T::AccountId) {
+		if let Some(review_process) = <PendingReviews<T>>::get(account) {
+			if !Self::has_open_review(account) {
+				Self::expire_review(review_process);
+			}
+		}
+	}
+
+	/// Expire an open review. The subject gets its deposit back and the assigned reviewers
+	/// that did not vote lose reputation.
+	fn expire_review(review_process: TicketId) {
+		let ticket = match <Tickets<T>>::get(review_process) {
+			Some(ticket) if ticket.status == ReviewStatus::Pending => ticket,
+			_ => return,
+		};
+		let unresponsive: Vec<T::AccountId> = ticket.reviewers.into_iter()
+			.filter(|reviewer| !<ReviewVotes<T>>::contains_key(review_process, reviewer))
+			.collect();
+
+		for reviewer in unresponsive.iter() {
+			T::Reputation::record(&Self::do_get_identity_id(reviewer), ReputationAction::MissedReview);
+		}
+
+		Self::close_review(review_process, &ticket.subject, ReviewStatus::Expired);
+		Self::deposit_event(RawEvent::ReviewExpired(review_process, ticket.subject, unresponsive));
+	}
+
+	/// Record the outcome of a review, return the deposit of the subject and remove the votes
+	fn close_review(review_process: TicketId, subject: &T::AccountId, status: ReviewStatus) {
+		T::Currency::unreserve(subject, <ReviewDeposits<T>>::take(review_process));
+
+		// The deposit of a rejected appeal is burned in reject_review
+		if status != ReviewStatus::Rejected {
+			T::Currency::unreserve(subject, <AppealDeposits<T>>::take(review_process));
+		}
+		<Tickets<T>>::mutate(review_process, |ticket| {
+			if let Some(ticket) = ticket {
+				ticket.status = status;
+
+				for reviewer in ticket.reviewers.iter() {
+					Self::remove_from_inbox(review_process, reviewer);
+				}
+			}
+		});
+		<PendingReviews<T>>::remove(subject);
+		<ReviewVotes<T>>::remove_prefix(review_process);
+		<VerificationStatements>::remove(review_process);
+	}
+
+	/// Add an open review to the inboxes of its assigned reviewers
+	fn add_to_inboxes(review_process: TicketId, reviewers: &[T::AccountId]) {
+		for reviewer in reviewers.iter() {
+			<PendingByReviewer<T>>::append(Self::do_get_identity_id(reviewer), review_process);
+		}
+	}
+
+	/// Remove a review from the inbox of a reviewer once the reviewer voted or the review closed
+	fn remove_from_inbox(review_process: TicketId, reviewer: &T::AccountId) {
+		<PendingByReviewer<T>>::mutate_exists(Self::do_get_identity_id(reviewer), |inbox| {
+			if let Some(tickets) = inbox {
+				tickets.retain(|ticket| *ticket != review_process);
+
+				if tickets.is_empty() {
+					*inbox = None;
+				}
+			}
+		});
+	}
+
+	/// Proof share an approving reviewer submits: the hash of the ticket, the reviewer and the
+	/// verification statement the subject confirmed. It binds every approval to the statement
+	/// the subject signed and to the reviewer that submits it.
+	pub fn review_proof(review_process: TicketId, reviewer: &T::AccountId, statement: &ProofType) -> ProofType {
+		let digest: T::Hash = T::Hashing::hash_of(&(b"review-proof", review_process, reviewer, statement));
+		let mut proof = ProofType::default();
+		let length: usize = proof.len().min(digest.as_ref().len());
+		proof[..length].copy_from_slice(&digest.as_ref()[..length]);
+		proof
 	}
 
-	fn do_approve_identity(_review_process: Ticket<T>, _proof_data: ProofType)
+	fn do_approve_identity(reviewer: T::AccountId, review_process: TicketId, proof_data: ProofType)
 		-> Result<(), DispatchError>
 	{
-		// TODO implement
+		Self::vote_review(reviewer, review_process, ReviewVote::Approve(proof_data))
+	}
+
+	fn do_reject_identity(reviewer: T::AccountId, review_process: TicketId) -> Result<(), DispatchError> {
+		Self::vote_review(reviewer, review_process, ReviewVote::Reject)
+	}
+
+	/// Record the vote of an assigned reviewer and conclude the review as soon as the outcome
+	/// is certain
+	fn vote_review(reviewer: T::AccountId, review_process: TicketId, vote: ReviewVote)
+		-> Result<(), DispatchError>
+	{
+		let ticket = <Tickets<T>>::get(review_process).ok_or(Error::<T>::ReviewNotExistant)?;
+		ensure!(ticket.status == ReviewStatus::Pending, Error::<T>::ReviewClosed);
+		ensure!(frame_system::Module::<T>::block_number() <= ticket.expires, Error::<T>::ReviewExpired);
+		ensure!(ticket.reviewers.contains(&reviewer), Error::<T>::NotAReviewer);
+		// The subject might have linked or recovered to an address that was assigned
+		ensure!(Self::do_get_identity_id(&reviewer) != Self::do_get_identity_id(&ticket.subject),
+			Error::<T>::SelfReviewForbidden
+		);
+		ensure!(!<ReviewVotes<T>>::contains_key(&review_process, &reviewer), Error::<T>::AlreadyReviewed);
+
+		if let ReviewVote::Approve(proof) = vote {
+			let statement: ProofType = <VerificationStatements>::get(review_process)
+				.ok_or(Error::<T>::VerificationNotConfirmed)?;
+			ensure!(proof == Self::review_proof(review_process, &reviewer, &statement), Error::<T>::InvalidProof);
+		}
+
+		<ReviewVotes<T>>::insert(&review_process, &reviewer, vote);
+		Self::remove_from_inbox(review_process, &reviewer);
+		Self::deposit_event(RawEvent::ReviewVoted(ticket.subject.clone(), reviewer, vote != ReviewVote::Reject));
+
+		let votes: Vec<(T::AccountId, ReviewVote)> = <ReviewVotes<T>>::iter_prefix(&review_process).collect();
+		let assigned: u32 = ticket.reviewers.len() as u32;
+		let rejections: u32 = votes.iter().filter(|(_, vote)| *vote == ReviewVote::Reject).count() as u32;
+		let approvals: u32 = votes.len() as u32 - rejections;
+		let threshold: Permill = T::ReviewApprovalThreshold::get();
+
+		if Permill::from_rational_approximation(approvals, assigned) >= threshold {
+			Self::record_review_rewards(&review_process, &votes);
+			Self::grant_reviewed_level(review_process, ticket, votes);
+		} else if Permill::from_rational_approximation(assigned - rejections, assigned) < threshold {
+			// Even if all remaining reviewers approve, the threshold is not reached
+			Self::record_review_rewards(&review_process, &votes);
+			Self::reject_review(review_process, ticket.subject, ticket.level);
+		}
+
 		Ok(())
 	}
 
-	fn do_reject_identity(_review_process: Ticket<T>) -> Result<(), DispatchError> {
-		// TODO implement
+	/// Grant the requested level of an approved review. The proof is the digest of the proof
+	/// shares of the approving reviewers, they are recorded for audits.
+	fn grant_reviewed_level(
+		review_process: TicketId,
+		ticket: ReviewTicket<T::Timestamp, T::AccountId, T::BlockNumber>,
+		votes: Vec<(T::AccountId, ReviewVote)>,
+	) {
+		let mut approvers: Vec<T::AccountId> = Vec::new();
+		let mut shares: Vec<ProofType> = Vec::new();
+
+		for (reviewer, vote) in votes.into_iter() {
+			if let ReviewVote::Approve(share) = vote {
+				approvers.push(reviewer);
+				shares.push(share);
+			}
+		}
+
+		let digest: T::Hash = T::Hashing::hash_of(&shares);
+		let mut proof = ProofType::default();
+		let length: usize = proof.len().min(digest.as_ref().len());
+		proof[..length].copy_from_slice(&digest.as_ref()[..length]);
+
+		let identity: IdentityId<T> = Self::do_get_identity_id(&ticket.subject);
+
+		if ticket.level > Self::stored_identity_level(&identity) {
+			<LastUpgrades<T>>::insert(&identity, frame_system::Module::<T>::block_number());
+		}
+
+		Self::insert_identity(ticket.subject.clone(), ticket.level, proof, ProofKind::PeerReview);
+		<CompletedReviews<T>>::insert(&review_process, ReviewRecord {
+			subject: ticket.subject.clone(),
+			reviewers: approvers,
+			level: ticket.level,
+			completed: T::Time::now(),
+		});
+		<LastReviews<T>>::insert(&identity, review_process);
+		Self::close_review(review_process, &ticket.subject, ReviewStatus::Approved);
+		Self::deposit_event(RawEvent::IdentityApproved(ticket.subject, ticket.level));
+	}
+
+	/// Close a rejected review and start the cooldown of the subject. The subject can appeal
+	/// a first rejection during the cooldown, the rejection of an appeal is final and burns the
+	/// appeal deposit.
+	fn reject_review(review_process: TicketId, subject: T::AccountId, level: IdentityLevel) {
+		let appealed: bool = <Tickets<T>>::get(review_process)
+			.map_or(false, |ticket| ticket.appeal == ReviewAppeal::Appealed);
+		Self::close_review(review_process, &subject, ReviewStatus::Rejected);
+		let until: T::BlockNumber = frame_system::Module::<T>::block_number() + T::ReviewCooldown::get();
+		<ReviewCooldowns<T>>::insert(&subject, until);
+
+		if appealed {
+			let deposit: BalanceOf<T> = <AppealDeposits<T>>::take(review_process);
+			let (_, unslashed) = T::Currency::slash_reserved(&subject, deposit);
+			Self::deposit_event(RawEvent::AppealRejected(review_process, subject.clone(), deposit.saturating_sub(unslashed)));
+		} else {
+			<Tickets<T>>::mutate(review_process, |ticket| {
+				if let Some(ticket) = ticket {
+					ticket.appeal = ReviewAppeal::Appealable(until);
+				}
+			});
+		}
+
+		Self::deposit_event(RawEvent::IdentityRejected(subject, level));
+	}
+
+	/// Reopen a rejected review with fresh reviewers. The reviewers of the rejected review are
+	/// not assigned again.
+	fn do_appeal_review(caller: T::AccountId, review_process: TicketId) -> Result<(), DispatchError> {
+		let ticket = <Tickets<T>>::get(review_process).ok_or(Error::<T>::ReviewNotExistant)?;
+		ensure!(Self::do_get_identity_id(&caller) == Self::do_get_identity_id(&ticket.subject),
+			Error::<T>::NotTheSubject
+		);
+		let now: T::BlockNumber = frame_system::Module::<T>::block_number();
+		ensure!(ticket.status == ReviewStatus::Rejected
+			&& matches!(ticket.appeal, ReviewAppeal::Appealable(until) if now <= until),
+			Error::<T>::NotAppealable
+		);
+		ensure!(!<PendingReviews<T>>::contains_key(&ticket.subject), Error::<T>::ReviewAlreadyRequested);
+		let (min_level, count) = Self::reviewer_requirement(ticket.level);
+		let count: u32 = count.saturating_mul(APPEAL_REVIEWER_FACTOR).min(T::MaxReviewers::get());
+		let candidates: Vec<T::AccountId> = Self::reviewer_candidates(&ticket.subject, min_level).into_iter()
+			.filter(|candidate| !ticket.reviewers.contains(candidate))
+			.collect();
+		let seed: T::Hash = T::Randomness::random(&(b"appeal", review_process).encode());
+		let reviewers: Vec<T::AccountId> = Self::pick_reviewers(seed, candidates, count);
+		ensure!(!reviewers.is_empty(), Error::<T>::NoReviewersAvailable);
+		let subject: T::AccountId = ticket.subject.clone();
+		let deposit: BalanceOf<T> = T::ReviewAppealDeposit::get();
+		T::Currency::reserve(&subject, deposit)?;
+
+		let expires: T::BlockNumber = now + T::ReviewTimeout::get();
+		<Tickets<T>>::insert(review_process, ReviewTicket {
+			requested: now,
+			reviewers: reviewers.clone(),
+			status: ReviewStatus::Pending,
+			expires,
+			appeal: ReviewAppeal::Appealed,
+			..ticket
+		});
+		<AppealDeposits<T>>::insert(review_process, deposit);
+		// Votes are accepted until the end of the expiry block
+		<ReviewExpiries<T>>::append(expires + One::one(), review_process);
+		<PendingReviews<T>>::insert(&subject, review_process);
+		<ReviewCooldowns<T>>::remove(&subject);
+		Self::add_to_inboxes(review_process, &reviewers);
+
+		for reviewer in reviewers.iter() {
+			Self::deposit_event(RawEvent::ReviewerAssigned(review_process, reviewer.clone(), subject.clone()));
+		}
+
+		Self::deposit_event(RawEvent::ReviewAppealed(review_process, subject, reviewers));
+		Ok(())
+	}
+
+	/// Let every reviewer that voted on a concluded review claim a reward
+	fn record_review_rewards(review_process: &TicketId, votes: &[(T::AccountId, ReviewVote)]) {
+		<ReviewRewards<T>>::mutate(review_process, |unclaimed| {
+			unclaimed.extend(votes.iter().map(|(reviewer, _)| reviewer.clone()))
+		});
+	}
+
+	/// Pay the reward of a reviewer for a concluded review, every vote is rewarded once
+	fn do_claim_review_reward(reviewer: T::AccountId, review_process: TicketId) -> Result<(), DispatchError> {
+		let mut unclaimed: Vec<T::AccountId> = <ReviewRewards<T>>::get(&review_process);
+		let position: usize = unclaimed.iter().position(|account| *account == reviewer)
+			.ok_or(Error::<T>::NoRewardClaimable)?;
+		let reward: BalanceOf<T> = T::ReviewerReward::get();
+		T::Currency::transfer(&Self::treasury_account(), &reviewer, reward, ExistenceRequirement::KeepAlive)?;
+		unclaimed.swap_remove(position);
+
+		if unclaimed.is_empty() {
+			<ReviewRewards<T>>::remove(&review_process);
+		} else {
+			<ReviewRewards<T>>::insert(&review_process, unclaimed);
+		}
+
+		Self::deposit_event(RawEvent::ReviewRewardPaid(review_process, reviewer, reward));
+		Ok(())
+	}
+
+	/// Account of the governance pot
+	pub fn treasury_account() -> T::AccountId {
+		T::TreasuryModuleId::get().into_account()
+	}
+
+	/// Is the cooldown after a rejected review of the account still active?
+	fn review_cooldown_active(account: &T::AccountId) -> bool {
+		<ReviewCooldowns<T>>::get(account)
+			.map_or(false, |until| frame_system::Module::<T>::block_number() < until)
+	}
+
+	fn do_report_missing(_review_process: TicketId, _missing: Vec<IdentityId<T>>)
+		-> Result<(), DispatchError>
+	{
+		Ok(())
+	}
+
+	fn do_audit_review(auditor: T::AccountId, review_process: TicketId) -> Result<(), DispatchError> {
+		ensure!(T::Auditors::contains(&auditor), Error::<T>::NotAnAuditor);
+		ensure!(<CompletedReviews<T>>::contains_key(&review_process), Error::<T>::ReviewNotExistant);
+		ensure!(!<Audits<T>>::contains_key(&review_process), Error::<T>::AlreadyUnderAudit);
+
+		let era: T::BlockNumber = frame_system::Module::<T>::block_number()
+			.checked_div(&T::AuditEraLength::get())
+			.unwrap_or_else(Zero::zero);
+		let (last_era, mut audits): (T::BlockNumber, u32) = <AuditsInEra<T>>::get();
+
+		if last_era != era {
+			audits = 0;
+		}
+
+		ensure!(audits < T::MaxAuditsPerEra::get(), Error::<T>::AuditLimitReached);
+		<AuditsInEra<T>>::put((era, audits + 1));
+		<Audits<T>>::insert(review_process, auditor);
+		Ok(())
+	}
+
+	fn do_conclude_audit(auditor: T::AccountId, review_process: TicketId, fraud: bool)
+		-> Result<(), DispatchError>
+	{
+		ensure!(<Audits<T>>::get(&review_process) == Some(auditor), Error::<T>::NotTheAuditor);
+		<Audits<T>>::remove(&review_process);
+		// Every review is audited at most once
+		let review = <CompletedReviews<T>>::take(&review_process).ok_or(Error::<T>::ReviewNotExistant)?;
+
+		if fraud {
+			Self::punish_fraudulent_review(&review_process, &review);
+			return Ok(());
+		}
+
+		for reviewer in review.reviewers.iter() {
+			let identity: IdentityId<T> = Self::do_get_identity_id(reviewer);
+			<Reputation<T>>::mutate(&identity, |reputation| {
+				reputation.confirmed = reputation.confirmed.saturating_add(1);
+			});
+			T::Reputation::record(&identity, ReputationAction::Reviewed);
+		}
+
+		Ok(())
+	}
+
+	/// Slash the approving reviewers of a fraudulent review and revoke the identity level that
+	/// was obtained by it. The reviewers forfeit their unclaimed rewards.
+	fn punish_fraudulent_review(review_process: &TicketId, review: &ReviewRecord<T::Timestamp, T::AccountId>) {
+		for reviewer in review.reviewers.iter() {
+			<Reputation<T>>::mutate(&Self::do_get_identity_id(reviewer), |reputation| {
+				reputation.fraudulent = reputation.fraudulent.saturating_add(1);
+			});
+			Self::slash_reviewer(reviewer);
+		}
+
+		<ReviewRewards<T>>::remove(review_process);
+		Self::revoke_level(&Self::do_get_identity_id(&review.subject));
+	}
+
+	/// Slash the bond of a reviewer and permanently remove it from the reviewer pool
+	fn slash_reviewer(reviewer: &T::AccountId) {
+		let bond: BalanceOf<T> = <ReviewerBonds<T>>::take(reviewer).map_or_else(Zero::zero, |bond| bond.amount);
+		let (_, unslashed) = T::Currency::slash_reserved(reviewer, bond);
+		<ExcludedReviewers<T>>::insert(reviewer, true);
+		let level: IdentityLevel = Self::stored_identity_level(&Self::do_get_identity_id(reviewer));
+		Self::move_in_reviewer_pool(reviewer, level, 0);
+		Self::deposit_event(RawEvent::ReviewerSlashed(reviewer.clone(), bond.saturating_sub(unslashed)));
+	}
+
+	fn do_bond_reviewer(reviewer: T::AccountId) -> Result<(), DispatchError> {
+		ensure!(!<ExcludedReviewers<T>>::get(&reviewer), Error::<T>::ReviewerExcluded);
+		ensure!(!<ReviewerBonds<T>>::contains_key(&reviewer), Error::<T>::AlreadyBonded);
+		ensure!(Self::stored_identity_level(&Self::do_get_identity_id(&reviewer)) > 0, Error::<T>::NoIdentity);
+
+		let amount: BalanceOf<T> = T::ReviewerStake::get();
+		T::Currency::reserve(&reviewer, amount)?;
+		<ReviewerBonds<T>>::insert(&reviewer, ReviewerBond { amount, unbonding_until: None });
+		Self::deposit_event(RawEvent::ReviewerBonded(reviewer, amount));
 		Ok(())
 	}
 
-	fn do_report_missing(_review_process: Ticket<T>, _missing: Vec<IdentityId<T>>)
+	/// Did the account bond a deposit as reviewer and is it not unbonding?
+	fn is_bonded_reviewer(account: &T::AccountId) -> bool {
+		<ReviewerBonds<T>>::get(account).map_or(false, |bond| bond.unbonding_until.is_none())
+	}
+
+	/// Reduce the level of an identity to zero and remove it from the reviewer pool
+	fn revoke_level(identity: &IdentityId<T>) {
+		<Identities<T>>::mutate(identity, |data| {
+			if let Some(data) = data {
+				Self::move_in_reviewer_pool(&data.identity, data.level, 0);
+				Self::move_in_level_count(Some(Self::counted_level(data.level, data.expires)), Some(0));
+				data.level = 0;
+			}
+		});
+	}
+
+	/// Support the revocation of an identity as a reviewer, or revoke it as the council
+	/// (`reviewer` is None). Opens a dispute once the council or RevocationQuorum reviewers
+	/// support the revocation.
+	fn do_revoke_identity(reviewer: Option<T::AccountId>, identity: IdentityId<T>, evidence: DocumentCID)
 		-> Result<(), DispatchError>
 	{
+		let level: IdentityLevel = Self::stored_identity_level(&identity);
+		ensure!(level > 0, Error::<T>::NoIdentity);
+		ensure!(!<Disputes<T>>::contains_key(&identity), Error::<T>::AlreadyDisputed);
+
+		let evidence: Vec<DocumentCID> = match reviewer {
+			None => [evidence].to_vec(),
+			Some(reviewer) => {
+				// Only reviewers that could have reviewed the level can revoke it
+				ensure!(Self::do_get_identity_level(&Self::do_get_identity_id(&reviewer)) > level,
+					Error::<T>::NotAReviewer
+				);
+				let mut support: Vec<(T::AccountId, DocumentCID)> = <RevocationSupport<T>>::get(&identity);
+				ensure!(support.iter().all(|(supporter, _)| *supporter != reviewer), Error::<T>::AlreadySupported);
+				support.push((reviewer.clone(), evidence));
+				Self::deposit_event(RawEvent::RevocationSupported(identity.clone(), reviewer, evidence));
+
+				if (support.len() as u32) < T::RevocationQuorum::get() {
+					<RevocationSupport<T>>::insert(&identity, support);
+					return Ok(());
+				}
+
+				<RevocationSupport<T>>::remove(&identity);
+				support.into_iter().map(|(_, evidence)| evidence).collect()
+			},
+		};
+
+		let appeal_until: T::BlockNumber = frame_system::Module::<T>::block_number() + T::AppealPeriod::get();
+		<Disputes<T>>::insert(&identity, Dispute { evidence, appeal_until, appeal: None });
+		Self::deposit_event(RawEvent::IdentityDisputed(identity, appeal_until));
 		Ok(())
 	}
 
+	/// Close the dispute and permanently reduce the level of the identity to zero
+	fn finish_revocation(identity: &IdentityId<T>) {
+		<Disputes<T>>::remove(identity);
+		Self::revoke_level(identity);
+		Self::deposit_event(RawEvent::IdentityRevoked(identity.clone()));
+	}
+
 	fn do_get_appointments(_identity: &IdentityId<T>) -> Vec<(T::Timestamp, Vec<IdentityId<T>>)> {
 		Default::default()
 	}
 
-	fn do_get_identity_level(_identity: &IdentityId<T>) -> IdentityLevel {
-		5
+	fn do_get_identity_level(identity: &IdentityId<T>) -> IdentityLevel {
+		// Disputed identities can't propose or vote until the dispute is resolved
+		if <Disputes<T>>::contains_key(identity) || <Banned<T>>::get(identity) {
+			return 0;
+		}
+
+		match <Identities<T>>::get(identity) {
+			Some(data) if frame_system::Module::<T>::block_number() > data.expires =>
+				data.level.min(DECAYED_IDENTITY_LEVEL),
+			Some(data) => data.level,
+			None => 0,
+		}
+	}
+
+	/// Effective identity level of an identity, 0 for unknown and disputed identities
+	pub fn identity_level_of(identity: &IdentityId<T>) -> IdentityLevel {
+		Self::do_get_identity_level(identity)
+	}
+
+	/// Proof of the current identity level of an identity
+	pub fn proof_of(identity: &IdentityId<T>) -> Option<PhysicalProof<T::Timestamp, ProofType>> {
+		<Identities<T>>::get(identity).map(|data| data.proof)
+	}
+
+	/// Does the address belong to a stored identity? Identities keep existing with level 0
+	/// after they were revoked or expired.
+	fn do_has_identity(address: &T::AccountId) -> bool {
+		<Identities<T>>::contains_key(Self::do_get_identity_id(address))
+	}
+
+	/// Identities with an effective level of at least `min_level`.
+	/// Iterates all identities, meant for snapshots that are taken rarely.
+	fn do_identities_from_level(min_level: IdentityLevel) -> Vec<IdentityId<T>> {
+		<Identities<T>>::iter()
+			.map(|(identity, _)| identity)
+			.filter(|identity| Self::do_get_identity_level(identity) >= min_level)
+			.collect()
 	}
 
 	fn do_get_identity_id(address: &T::AccountId) -> IdentityId<T> {
-		address.clone()
+		<SignerToIdentity<T>>::get(address)
+			.or_else(|| <AddressToIdentity<T>>::get(address))
+			.unwrap_or_else(|| address.clone())
+	}
+
+	/// Ensure that the address is the identity itself or its attested signer, not a linked
+	/// secondary address, and return the identity
+	fn ensure_identity_owner(address: &T::AccountId) -> Result<IdentityId<T>, DispatchError> {
+		Self::do_ensure_attested_signer(address)?;
+		ensure!(!<AddressToIdentity<T>>::contains_key(address), Error::<T>::NotIdentityOwner);
+		let identity: IdentityId<T> = Self::do_get_identity_id(address);
+		ensure!(<Identities<T>>::contains_key(&identity), Error::<T>::NoIdentity);
+		Ok(identity)
+	}
+
+	/// Ensure that the assigned reviewers of an open review can be replaced by reviewers from
+	/// the subject's region and randomly pick them among the eligible reviewers of the region.
+	/// Returns the subject and the new reviewers.
+	fn ensure_regional_assignment(review_process: TicketId)
+		-> Result<(T::AccountId, Vec<T::AccountId>), DispatchError>
+	{
+		let ticket = <Tickets<T>>::get(review_process).ok_or(Error::<T>::ReviewNotExistant)?;
+		ensure!(ticket.status == ReviewStatus::Pending, Error::<T>::ReviewClosed);
+		ensure!(frame_system::Module::<T>::block_number() <= ticket.expires, Error::<T>::ReviewExpired);
+		// Reviewers that started reviewing keep their assignment
+		ensure!(!<RegionalAssignments>::get(review_process)
+			&& <ReviewVotes<T>>::iter_prefix(review_process).next().is_none(),
+			Error::<T>::AssignmentFixed
+		);
+		let region: RegionCode = <Regions<T>>::get(Self::do_get_identity_id(&ticket.subject))
+			.ok_or(Error::<T>::NoRegion)?;
+		let count: u32 = ticket.reviewers.len() as u32;
+		let (min_level, _) = Self::reviewer_requirement(ticket.level);
+		// The candidates are derived on-chain, the submitter cannot choose the reviewers
+		let candidates: Vec<T::AccountId> = Self::reviewer_candidates(&ticket.subject, min_level)
+			.into_iter()
+			.filter(|candidate| <Regions<T>>::get(Self::do_get_identity_id(candidate)) == Some(region))
+			.take(T::MaxReviewers::get().saturating_mul(REGIONAL_CANDIDATE_FACTOR) as usize)
+			.collect();
+		ensure!(candidates.len() as u32 >= count, Error::<T>::NotEnoughRegionalReviewers);
+
+		let seed: T::Hash = T::Randomness::random(&(b"regional", review_process).encode());
+		Ok((ticket.subject, Self::pick_reviewers(seed, candidates, count)))
+	}
+
+	/// Ensure that the address does not belong to any identity yet
+	fn ensure_address_unused(identity: &IdentityId<T>, address: &T::AccountId) -> Result<(), DispatchError> {
+		ensure!(address != identity
+			&& !<Identities<T>>::contains_key(address)
+			&& !<SignerToIdentity<T>>::contains_key(address)
+			&& !<AddressToIdentity<T>>::contains_key(address),
+			Error::<T>::AddressInUse
+		);
+		Ok(())
+	}
+
+	/// Ensure that the address does not belong to any identity yet and that the identity can
+	/// link another address
+	fn ensure_address_linkable(identity: &IdentityId<T>, address: &T::AccountId) -> Result<(), DispatchError> {
+		Self::ensure_address_unused(identity, address)?;
+		ensure!((<LinkedAddresses<T>>::decode_len(identity).unwrap_or(0) as u32) < T::MaxLinkedAddresses::get(),
+			Error::<T>::TooManyLinkedAddresses
+		);
+		Ok(())
+	}
+
+	fn do_attest_signing_scheme(account: T::AccountId, scheme: SigningScheme<T::AccountId>)
+		-> Result<(), DispatchError>
+	{
+		let identity: IdentityId<T> = Self::do_get_identity_id(&account);
+		// An attested identity can only change its scheme with the attested signer
+		Self::do_ensure_attested_signer(&account)?;
+		ensure!(!<SignerToIdentity<T>>::contains_key(scheme.signer()), Error::<T>::AlreadyAttested);
+		// Addresses linked to other identities can't become the signer of this identity
+		ensure!(<AddressToIdentity<T>>::get(scheme.signer()).map_or(true, |linked| linked == identity),
+			Error::<T>::AddressInUse
+		);
+
+		if let Some(previous) = <Attestations<T>>::get(&identity) {
+			<SignerToIdentity<T>>::remove(previous.signer());
+		}
+
+		<SignerToIdentity<T>>::insert(scheme.signer(), &identity);
+		<Attestations<T>>::insert(identity, scheme);
+		Ok(())
+	}
+
+	fn do_remove_attestation(signer: T::AccountId) -> Result<(), DispatchError> {
+		let identity: IdentityId<T> = <SignerToIdentity<T>>::take(&signer).ok_or(Error::<T>::NoAttestation)?;
+		<Attestations<T>>::remove(identity);
+		Ok(())
+	}
+
+	/// If the identity of the address attested a signing scheme, ensure that the address is the
+	/// attested signer
+	fn do_ensure_attested_signer(address: &T::AccountId) -> Result<(), DispatchError> {
+		let identity: IdentityId<T> = Self::do_get_identity_id(address);
+
+		match <Attestations<T>>::get(&identity) {
+			Some(scheme) if scheme.signer() != address => Err(Error::<T>::AttestedSignerRequired.into()),
+			_ => Ok(()),
+		}
 	}
 
 	fn do_get_address(identity: &IdentityId<T>) -> T::AccountId {
-		identity.clone()
+		<RecoveredAddresses<T>>::get(identity).unwrap_or_else(|| identity.clone())
+	}
+
+	/// Guardians of the identity and how many of them must approve a recovery. Falls back to the
+	/// reviewers of the last review of the identity, of which the majority must approve.
+	fn guardians_of(identity: &IdentityId<T>) -> Option<(Vec<T::AccountId>, u32)> {
+		<Guardians<T>>::get(identity).or_else(|| {
+			<LastReviews<T>>::get(identity).and_then(<CompletedReviews<T>>::get).map(|review| {
+				let threshold: u32 = review.reviewers.len() as u32 / 2 + 1;
+				(review.reviewers, threshold)
+			})
+		})
+	}
+
+	fn do_vote_recovery(guardian: T::AccountId, identity: IdentityId<T>, new_account: T::AccountId)
+		-> Result<(), DispatchError>
+	{
+		let (guardians, threshold) = Self::guardians_of(&identity).ok_or(Error::<T>::NotAGuardian)?;
+		ensure!(guardians.contains(&guardian), Error::<T>::NotAGuardian);
+		Self::ensure_address_unused(&identity, &new_account)?;
+
+		let mut recovery = <Recoveries<T>>::get(&identity).unwrap_or_else(|| Recovery {
+			new_account: new_account.clone(),
+			approvals: Vec::new(),
+			executable_from: None,
+		});
+		ensure!(recovery.new_account == new_account, Error::<T>::RecoveryTargetMismatch);
+		ensure!(!recovery.approvals.contains(&guardian), Error::<T>::AlreadyApprovedRecovery);
+		recovery.approvals.push(guardian.clone());
+		Self::deposit_event(RawEvent::RecoveryVoted(identity.clone(), guardian, new_account.clone()));
+
+		if recovery.executable_from.is_none() && recovery.approvals.len() as u32 >= threshold {
+			let from: T::BlockNumber = frame_system::Module::<T>::block_number() + T::RecoveryDelay::get();
+			recovery.executable_from = Some(from);
+			Self::deposit_event(RawEvent::RecoveryApproved(identity.clone(), new_account, from));
+		}
+
+		<Recoveries<T>>::insert(&identity, recovery);
+		Ok(())
+	}
+
+	/// Make the new account the main address and attested signer of the identity. The lost
+	/// address can't sign governance critical calls of the identity anymore.
+	fn do_recover_identity(identity: IdentityId<T>, new_account: T::AccountId) {
+		let lost: T::AccountId = Self::do_get_address(&identity);
+
+		if let Some(previous) = <Attestations<T>>::get(&identity) {
+			<SignerToIdentity<T>>::remove(previous.signer());
+		}
+
+		<SignerToIdentity<T>>::insert(&new_account, &identity);
+		<Attestations<T>>::insert(&identity, SigningScheme::Key(new_account.clone()));
+		<RecoveredAddresses<T>>::insert(&identity, &new_account);
+
+		// The new account reviews in place of the lost address
+		let level: IdentityLevel = Self::stored_identity_level(&identity);
+		Self::move_in_reviewer_pool(&lost, level, 0);
+		Self::move_in_reviewer_pool(&new_account, 0, level);
+		<Identities<T>>::mutate(&identity, |data| {
+			if let Some(data) = data {
+				data.identity = new_account.clone();
+			}
+		});
+		Self::deposit_event(RawEvent::IdentityRecovered(identity, new_account));
+	}
+
+	fn do_get_review_count(_identity: &IdentityId<T>) -> u32 {
+		// TODO implement
+		0
 	}
 }
 
 impl<T: Trait> traits::PeerReviewedPhysicalIdentity<ProofType> for Module<T> {
 	type Address = T::AccountId;
-	type Ticket = T::AccountId;
+	type Ticket = TicketId;
 	type Timestamp = T::Timestamp;
 	type IdentityLevel = IdentityLevel;
 	type IdentityId = IdentityId<T>;
@@ -157,16 +2347,16 @@ impl<T: Trait> traits::PeerReviewedPhysicalIdentity<ProofType> for Module<T> {
 		Self::do_request_peer_review(user, identity_level, at)
 	}
 
-	/// As a reviewer, approve a reviewed PhysicalIdentity by supplying a proof
-	fn approve_identity(review_process: Self::Ticket, proof_data: ProofType)
+	/// As an assigned reviewer, approve a reviewed PhysicalIdentity by supplying a proof share
+	fn approve_identity(reviewer: Self::Address, review_process: Self::Ticket, proof_data: ProofType)
 		-> Result<(), DispatchError>
 	{
-		Self::do_approve_identity(review_process, proof_data)
+		Self::do_approve_identity(reviewer, review_process, proof_data)
 	}
 
-	/// As a reviewer, reject a reviewed PhysicalIdentity
-	fn reject_identity(review_process: Self::Ticket) -> Result<(), DispatchError> {
-		Self::do_reject_identity(review_process)
+	/// As an assigned reviewer, reject a reviewed PhysicalIdentity
+	fn reject_identity(reviewer: Self::Address, review_process: Self::Ticket) -> Result<(), DispatchError> {
+		Self::do_reject_identity(reviewer, review_process)
 	}
 
 	/// As a participant, report a missing participant
@@ -184,7 +2374,6 @@ impl<T: Trait> traits::PeerReviewedPhysicalIdentity<ProofType> for Module<T> {
 
 	/// Receive the identity level of a specific PhysicalIdentity.
 	fn get_identity_level(identity: &Self::IdentityId) -> Self::IdentityLevel {
-		// TODO: implement
 		Self::do_get_identity_level(identity)
 	}
 
@@ -197,4 +2386,82 @@ impl<T: Trait> traits::PeerReviewedPhysicalIdentity<ProofType> for Module<T> {
 	fn get_address(identity: &Self::IdentityId) -> Self::Address {
 		Self::do_get_address(identity)
 	}
+
+	/// Get the number of reviews a DDI has performed
+	fn get_review_count(identity: &Self::IdentityId) -> u32 {
+		Self::do_get_review_count(identity)
+	}
+
+	/// Ensure that governance critical calls of an identity with an attested signing scheme
+	/// are signed by the attested signer
+	fn ensure_attested_signer(address: &Self::Address) -> Result<(), DispatchError> {
+		Self::do_ensure_attested_signer(address)
+	}
+
+	/// Is the revocation of the identity disputed?
+	fn is_disputed(identity: &Self::IdentityId) -> bool {
+		<Disputes<T>>::contains_key(identity)
+	}
+
+	fn is_banned(identity: &Self::IdentityId) -> bool {
+		<Banned<T>>::get(identity)
+	}
+
+	/// Get the display handle hash and contact document of an identity
+	fn get_metadata(identity: &Self::IdentityId) -> Option<IdentityMetadata> {
+		<Metadata<T>>::get(identity)
+	}
+
+	/// Does the address belong to an identity, regardless of its level?
+	fn has_identity(address: &Self::Address) -> bool {
+		Self::do_has_identity(address)
+	}
+
+	/// Get the number of stored identities
+	fn identity_count() -> u32 {
+		<IdentityCount>::get()
+	}
+
+	/// Get the identities with an effective level of at least `min_level`
+	fn identities_from_level(min_level: Self::IdentityLevel) -> Vec<Self::IdentityId> {
+		Self::do_identities_from_level(min_level)
+	}
+
+	/// Get the number of identities with a level of at least `min_level` from the level counters
+	fn identity_count_from_level(min_level: Self::IdentityLevel) -> u32 {
+		Self::do_identity_count_from_level(min_level)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_identity_level(identity: &Self::IdentityId, level: Self::IdentityLevel) {
+		Self::insert_identity(identity.clone(), level, ProofType::default(), ProofKind::Bootstrap);
+	}
+}
+
+impl<T: Trait> ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	/// Accept the regional reviewer assignments of the local off-chain worker. The reviewers
+	/// are picked on-chain, the assignments are not propagated to limit the unsigned load.
+	fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		let review_process = match call {
+			Call::assign_regional_reviewers(review_process) => review_process,
+			_ => return InvalidTransaction::Call.into(),
+		};
+
+		if source == TransactionSource::External {
+			return InvalidTransaction::Call.into();
+		}
+
+		if Self::ensure_regional_assignment(*review_process).is_err() {
+			return InvalidTransaction::Stale.into();
+		}
+
+		ValidTransaction::with_tag_prefix("RegionalReviewers")
+			.priority(T::UnsignedPriority::get())
+			.and_provides(review_process)
+			.longevity(5)
+			.propagate(false)
+			.build()
+	}
 }
@@ -0,0 +1,320 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the community identity pallet
+
+use codec::{Decode, Encode};
+use frame_support::{
+	sp_std::cell::Cell,
+	storage::migration::{StorageIterator, take_storage_value},
+	traits::Get,
+	weights::Weight,
+	IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use crate::*;
+
+/// Name of the pallet storage prefix
+const PALLET: &[u8] = b"CommunityIdentity";
+
+/// Identity data as it was stored before identities expired
+#[derive(Decode, Encode)]
+struct IdentityWithoutExpiry<Timestamp, AccountId> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+{
+	identity: AccountId,
+	level: IdentityLevel,
+	proof: PhysicalProof<Timestamp, ProofType>,
+}
+
+/// Open review request as it was stored under the subject's account before tickets had an index
+#[derive(Decode, Encode)]
+struct TicketWithoutStatus<Timestamp, AccountId, BlockNumber> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+{
+	subject: AccountId,
+	level: IdentityLevel,
+	requested: BlockNumber,
+	appointment: Timestamp,
+	reviewers: Vec<AccountId>,
+}
+
+/// Review ticket as it was stored before rejections could be appealed
+#[derive(Decode, Encode)]
+struct TicketWithoutAppeal<Timestamp, AccountId, BlockNumber> where
+	AccountId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+{
+	subject: AccountId,
+	level: IdentityLevel,
+	requested: BlockNumber,
+	appointment: Timestamp,
+	reviewers: Vec<AccountId>,
+	status: ReviewStatus,
+	expires: BlockNumber,
+}
+
+/// Apply all migrations that are required to reach the current storage layout.
+/// Review requests of the first layout had no reviewers assigned, they are dropped and
+/// have to be requested again. The reviewer pool is built from the stored identities.
+/// Existing identities are valid for IdentityValidityPeriod from the upgrade on.
+/// Reviews that were stored under the subject's account receive a ticket index.
+/// The stored identities are counted. Open reviews are added to the inboxes of the reviewers
+/// that did not vote yet. Existing rejections can't be appealed. The identities are counted
+/// per level.
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+	let version: Releases = StorageVersion::get();
+
+	if version == Releases::V8LevelCounts {
+		return weight;
+	}
+
+	if version < Releases::V3IdentityExpiry {
+		weight = weight.saturating_add(migrate_identity_expiry::<T>(version));
+	}
+
+	// Tickets that are created by the ticket migration already have the current layout
+	if version < Releases::V4Tickets {
+		weight = weight.saturating_add(migrate_tickets::<T>());
+	} else if version < Releases::V7ReviewAppeals {
+		weight = weight.saturating_add(migrate_ticket_appeals::<T>());
+	}
+
+	if version < Releases::V5IdentityCount {
+		weight = weight.saturating_add(count_identities::<T>());
+	}
+
+	if version < Releases::V6ReviewerInbox {
+		weight = weight.saturating_add(fill_reviewer_inboxes::<T>());
+	}
+
+	weight = weight.saturating_add(count_levels::<T>());
+	StorageVersion::put(Releases::V8LevelCounts);
+	weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Count the identities per level and schedule the decay of the levels that did not expire yet
+fn count_levels<T: Trait>() -> Weight {
+	let now: T::BlockNumber = frame_system::Module::<T>::block_number();
+	let mut counted: u64 = 0;
+	let mut scheduled: u64 = 0;
+
+	for (identity, data) in <Identities<T>>::iter() {
+		counted = counted.saturating_add(1);
+		let level: IdentityLevel = Module::<T>::counted_level(data.level, data.expires);
+		<IdentityCountByLevel>::mutate(level, |count| *count = count.saturating_add(1));
+
+		if data.expires >= now && data.level > DECAYED_IDENTITY_LEVEL {
+			<LevelExpiries<T>>::append(data.expires + One::one(), &identity);
+			scheduled = scheduled.saturating_add(1);
+		}
+	}
+
+	T::DbWeight::get().reads_writes(counted, counted.saturating_add(scheduled))
+}
+
+/// Add the appeal state to the stored tickets, rejections before the upgrade can't be appealed
+fn migrate_ticket_appeals<T: Trait>() -> Weight {
+	let translated: Cell<u64> = Cell::new(0);
+
+	<Tickets<T>>::translate::<TicketWithoutAppeal<T::Timestamp, T::AccountId, T::BlockNumber>, _>(|_, old| {
+		translated.set(translated.get() + 1);
+		Some(ReviewTicket {
+			subject: old.subject,
+			level: old.level,
+			requested: old.requested,
+			appointment: old.appointment,
+			reviewers: old.reviewers,
+			status: old.status,
+			expires: old.expires,
+			appeal: ReviewAppeal::NotAppealed,
+		})
+	});
+
+	let entries: u64 = translated.get();
+	T::DbWeight::get().reads_writes(entries, entries)
+}
+
+/// Add every open review to the inboxes of the assigned reviewers that did not vote yet
+fn fill_reviewer_inboxes<T: Trait>() -> Weight {
+	let mut reads: u64 = 0;
+	let mut writes: u64 = 0;
+
+	for (review_process, ticket) in <Tickets<T>>::iter() {
+		reads = reads.saturating_add(1);
+
+		if ticket.status != ReviewStatus::Pending {
+			continue;
+		}
+
+		for reviewer in ticket.reviewers.iter() {
+			reads = reads.saturating_add(1);
+
+			if !<ReviewVotes<T>>::contains_key(review_process, reviewer) {
+				<PendingByReviewer<T>>::append(Module::<T>::do_get_identity_id(reviewer), review_process);
+				writes = writes.saturating_add(1);
+			}
+		}
+	}
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Initialize the identity counter with the number of stored identities
+fn count_identities<T: Trait>() -> Weight {
+	let count: u32 = <Identities<T>>::iter().count() as u32;
+	<IdentityCount>::put(count);
+	T::DbWeight::get().reads_writes(u64::from(count), 1)
+}
+
+/// Drop the review requests of the first layout, build the reviewer pool and let the stored
+/// identities expire
+fn migrate_identity_expiry<T: Trait>(version: Releases) -> Weight {
+	let mut weight: Weight = 0;
+
+	if version == Releases::V1ReviewRequests {
+		let dropped: u64 = StorageIterator::<T::BlockNumber>::new(PALLET, b"ReviewRequests").drain().count() as u64;
+		weight = weight.saturating_add(T::DbWeight::get().reads_writes(dropped, dropped));
+	}
+
+	// translate requires a Fn closure, hence the Cell
+	let translated: Cell<u64> = Cell::new(0);
+	let expires: T::BlockNumber = frame_system::Module::<T>::block_number() + T::IdentityValidityPeriod::get();
+	let notice: T::BlockNumber = Module::<T>::notice_block(expires);
+
+	<Identities<T>>::translate::<IdentityWithoutExpiry<T::Timestamp, T::AccountId>, _>(|_, old| {
+		translated.set(translated.get() + 1);
+
+		if version == Releases::V1ReviewRequests && old.level > 0 {
+			<ReviewerPool<T>>::append(old.level, &old.identity);
+		}
+
+		<ExpiryNotices<T>>::append(notice, &old.identity);
+		Some(PhysicalIdentityData {
+			identity: old.identity,
+			level: old.level,
+			proof: old.proof,
+			expires,
+		})
+	});
+
+	let entries: u64 = translated.get();
+	weight.saturating_add(T::DbWeight::get().reads_writes(entries, entries.saturating_mul(3)))
+}
+
+/// Move the open, completed, audited and unrewarded reviews from the subject's account to
+/// ticket indices. Every old entry is read before the first new entry is written, because the
+/// old and new entries share the same storage prefixes.
+fn migrate_tickets<T: Trait>() -> Weight {
+	let now: T::BlockNumber = frame_system::Module::<T>::block_number();
+	let pending: Vec<TicketWithoutStatus<T::Timestamp, T::AccountId, T::BlockNumber>> =
+		StorageIterator::<TicketWithoutStatus<T::Timestamp, T::AccountId, T::BlockNumber>>::new(PALLET, b"PendingReviews")
+			.drain()
+			.map(|(_, ticket)| ticket)
+			.collect();
+	// The votes were keyed by (subject, reviewer) with the identity hasher
+	let votes: Vec<Vec<(T::AccountId, ReviewVote)>> = pending.iter().map(|ticket| {
+		ticket.reviewers.iter().filter_map(|reviewer| {
+			take_storage_value::<ReviewVote>(PALLET, b"ReviewVotes", &(&ticket.subject, reviewer).encode())
+				.map(|vote| (reviewer.clone(), vote))
+		}).collect()
+	}).collect();
+	let completed: Vec<ReviewRecord<T::Timestamp, T::AccountId>> =
+		StorageIterator::<ReviewRecord<T::Timestamp, T::AccountId>>::new(PALLET, b"CompletedReviews")
+			.drain()
+			.map(|(_, record)| record)
+			.collect();
+	let audits: Vec<Option<T::AccountId>> = completed.iter()
+		.map(|record| take_storage_value::<T::AccountId>(PALLET, b"Audits", &record.subject.encode()))
+		.collect();
+	let completed_rewards: Vec<Option<Vec<T::AccountId>>> = completed.iter()
+		.map(|record| take_storage_value::<Vec<T::AccountId>>(PALLET, b"ReviewRewards", &record.subject.encode()))
+		.collect();
+	// Only the unclaimed rewards of rejected reviews remain
+	let rejected_rewards: Vec<(T::AccountId, Vec<T::AccountId>)> =
+		StorageIterator::<Vec<T::AccountId>>::new(PALLET, b"ReviewRewards")
+			.drain()
+			.filter_map(|(key, reviewers)| T::AccountId::decode(&mut &key[..]).ok().map(|subject| (subject, reviewers)))
+			.collect();
+
+	let mut next: TicketId = <NextTicketId>::get();
+	let entries: u64 = (pending.len() + completed.len() + rejected_rewards.len()) as u64;
+
+	for (old, votes) in pending.into_iter().zip(votes.into_iter()) {
+		for (reviewer, vote) in votes.into_iter() {
+			<ReviewVotes<T>>::insert(next, reviewer, vote);
+		}
+
+		let expires: T::BlockNumber = now + T::ReviewTimeout::get();
+		<ReviewExpiries<T>>::append(expires + One::one(), next);
+		<PendingReviews<T>>::insert(&old.subject, next);
+		<Tickets<T>>::insert(next, ReviewTicket {
+			subject: old.subject,
+			level: old.level,
+			requested: old.requested,
+			appointment: old.appointment,
+			reviewers: old.reviewers,
+			status: ReviewStatus::Pending,
+			expires,
+			appeal: ReviewAppeal::NotAppealed,
+		});
+		next = next.saturating_add(1);
+	}
+
+	for ((record, auditor), rewards) in completed.into_iter().zip(audits.into_iter()).zip(completed_rewards.into_iter()) {
+		if let Some(auditor) = auditor {
+			<Audits<T>>::insert(next, auditor);
+		}
+
+		if let Some(rewards) = rewards {
+			<ReviewRewards<T>>::insert(next, rewards);
+		}
+
+		<LastReviews<T>>::insert(Module::<T>::do_get_identity_id(&record.subject), next);
+		<Tickets<T>>::insert(next, ReviewTicket {
+			subject: record.subject.clone(),
+			level: record.level,
+			requested: now,
+			appointment: record.completed,
+			reviewers: record.reviewers.clone(),
+			status: ReviewStatus::Approved,
+			expires: now,
+			appeal: ReviewAppeal::NotAppealed,
+		});
+		<CompletedReviews<T>>::insert(next, record);
+		next = next.saturating_add(1);
+	}
+
+	for (subject, rewards) in rejected_rewards.into_iter() {
+		<Tickets<T>>::insert(next, ReviewTicket {
+			subject,
+			level: 0,
+			requested: now,
+			appointment: T::Timestamp::default(),
+			reviewers: rewards.clone(),
+			status: ReviewStatus::Rejected,
+			expires: now,
+			appeal: ReviewAppeal::NotAppealed,
+		});
+		<ReviewRewards<T>>::insert(next, rewards);
+		next = next.saturating_add(1);
+	}
+
+	<NextTicketId>::put(next);
+	T::DbWeight::get().reads_writes(entries.saturating_mul(4) + 1, entries.saturating_mul(5) + 1)
+}
@@ -0,0 +1,41 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Off-chain worker of the community identity pallet. Physical verifications require the
+//! reviewers to meet the subject, therefore the worker requests reviewers from the region
+//! the subject declared. The reviewers are picked on-chain among the eligible reviewers of
+//! the region. The randomly assigned reviewers stay if the region has too few of them.
+
+use frame_system::offchain::SubmitTransaction;
+use crate::*;
+
+/// Request a regional assignment for every subject with a region that requested a review in
+/// this block and whose region has enough eligible reviewers
+pub(crate) fn propose_regional_reviewers<T: Trait>(now: T::BlockNumber) {
+	for (_, review_process) in <PendingReviews<T>>::iter() {
+		match <Tickets<T>>::get(review_process) {
+			Some(ticket) if ticket.requested == now => (),
+			_ => continue,
+		}
+
+		// No region or not enough reviewers in the region, the random assignment stays
+		if Module::<T>::ensure_regional_assignment(review_process).is_err() {
+			continue;
+		}
+
+		let call = Call::assign_regional_reviewers(review_process);
+		// The assignment is only an improvement, the random assignment stays if it fails
+		let _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+	}
+}
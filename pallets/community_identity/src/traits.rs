@@ -17,6 +17,7 @@ use frame_support::{
 	sp_runtime::traits::AtLeast32Bit,
 };
 use num_traits::Num;
+use crate::IdentityMetadata;
 
 /// Trait for identity modules that want to support peer reviewed physical identities
 ///
@@ -26,7 +27,8 @@ use num_traits::Num;
 /// 3. Offchain: Select date and video chat plattform
 /// 4. Review process: Reviewers check if the person knows what is happening and if its a realtime transmission
 /// 5. Reviewers use software to determine biometric data
-/// 6. Reviewers call ```approve_identity``` (including biometric data) or ```reject_identity```
+/// 6. Reviewers call ```approve_identity``` (including biometric data) or ```reject_identity```,
+///    the level is granted if a threshold of the reviewers approved
 ///
 /// Important: Force every user to redo this process in a specified period or reduce identity level to 1
 /// due to possibly changing biometric data
@@ -46,10 +48,12 @@ pub trait PeerReviewedPhysicalIdentity<ProofData>
 	/// Request a peer review to gain a specific IdentityLevel
 	fn request_peer_review(user: Self::Address, identity_level: Self::IdentityLevel, at: Self::Timestamp) 
 		-> Result<Self::Ticket, DispatchError>;
-	/// As a reviewer, approve a reviewed PhysicalIdentity by supplying a proof
-	fn approve_identity(review_process: Self::Ticket, proof_data: ProofData) -> Result<(), DispatchError>;
-	/// As a reviewer, reject a reviewed PhysicalIdentity
-	fn reject_identity(review_process: Self::Ticket) -> Result<(), DispatchError>;
+	/// As an assigned reviewer, approve a reviewed PhysicalIdentity by supplying a proof share.
+	/// The proof binds the reviewer to the verification statement the subject confirmed.
+	fn approve_identity(reviewer: Self::Address, review_process: Self::Ticket, proof_data: ProofData)
+		-> Result<(), DispatchError>;
+	/// As an assigned reviewer, reject a reviewed PhysicalIdentity
+	fn reject_identity(reviewer: Self::Address, review_process: Self::Ticket) -> Result<(), DispatchError>;
 	/// As a participant, report a missing participant
 	fn report_missing(review_process: Self::Ticket, missing: Vec<Self::IdentityId>) -> Result<(), DispatchError>;
 	/// Get the appointments for a DDI (when the DDI has to participate in an audit)
@@ -60,4 +64,52 @@ pub trait PeerReviewedPhysicalIdentity<ProofData>
 	fn get_identity_id(address: &Self::Address) -> Self::IdentityId;
 	/// Get (main) address for an IdentityId
 	fn get_address(identity: &Self::IdentityId) -> Self::Address;
+	/// Get the number of reviews a DDI has performed
+	fn get_review_count(identity: &Self::IdentityId) -> u32;
+	/// Ensure that governance critical calls of an identity with an attested signing scheme
+	/// are signed by the attested signer
+	fn ensure_attested_signer(address: &Self::Address) -> Result<(), DispatchError>;
+	/// Is the revocation of the identity disputed? Disputed identities must not propose or vote
+	fn is_disputed(identity: &Self::IdentityId) -> bool;
+	/// Did the council ban the identity? Banned identities have level 0
+	fn is_banned(identity: &Self::IdentityId) -> bool;
+	/// Get the public metadata of an identity, e.g. to show it next to its proposals
+	fn get_metadata(identity: &Self::IdentityId) -> Option<IdentityMetadata>;
+	/// Does the address belong to an identity? Distinguishes accounts without identity from
+	/// identities with level 0
+	fn has_identity(address: &Self::Address) -> bool;
+	/// Get the number of identities, including identities with level 0
+	fn identity_count() -> u32;
+	/// Get the identities with an effective level of at least `min_level`, e.g. to take a
+	/// snapshot of the eligible voters. Iterates all identities.
+	fn identities_from_level(min_level: Self::IdentityLevel) -> Vec<Self::IdentityId>;
+	/// Get the number of identities with a level of at least `min_level`, e.g. to compute the
+	/// turnout of a vote. Expired levels are decayed, disputed and banned identities are counted.
+	/// Does not iterate the identities.
+	fn identity_count_from_level(min_level: Self::IdentityLevel) -> u32;
+	/// Grant an identity level without peer review, so benchmarks of dependent pallets can
+	/// act as identified users
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_identity_level(identity: &Self::IdentityId, level: Self::IdentityLevel);
+}
+
+/// Registry of identity levels attested outside of the peer review, e.g. by an oracle that
+/// relays KYC checks or by root. Combined with the peer reviewed identities by
+/// `adapter::WithExternalAttestations`.
+pub trait ExternalAttestations<IdentityId, IdentityLevel> {
+	/// Get the level the registry attests to an identity, None if it attests nothing
+	fn attested_level(identity: &IdentityId) -> Option<IdentityLevel>;
+	/// Get every identity the registry attests a level to
+	fn attested_identities() -> Vec<(IdentityId, IdentityLevel)>;
+}
+
+/// No external registry, only peer reviewed identities exist
+impl<IdentityId, IdentityLevel> ExternalAttestations<IdentityId, IdentityLevel> for () {
+	fn attested_level(_identity: &IdentityId) -> Option<IdentityLevel> {
+		None
+	}
+
+	fn attested_identities() -> Vec<(IdentityId, IdentityLevel)> {
+		Vec::new()
+	}
 }
@@ -13,7 +13,12 @@
 // limitations under the License.
 
 #![cfg_attr(not(feature = "std"), no_std)]
-use frame_support::{decl_module, decl_storage, dispatch::{DispatchError, Vec}};
+use codec::{Decode, Encode};
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, dispatch::{DispatchError, Vec}, ensure,
+	sp_runtime::{Permill, traits::Zero}, sp_std,
+	traits::{Contains, Currency, Get, OnUnbalanced, ReservableCurrency},
+	weights::Weight,
+};
 use frame_system::{ensure_signed, ensure_root};
 use pallet_community_identity::{ProofType, IdentityId, IdentityLevel, traits::PeerReviewedPhysicalIdentity};
 #[cfg(test)]
@@ -25,42 +30,251 @@ pub mod traits;
 
 pub type Ticket = u64;
 pub type BlockNumber<T> = <T as frame_system::Trait>::BlockNumber;
-// TODO: Change from Vec<u8> to fixed length type
-pub type DocumentCID = Vec<u8>;
+/// Sha2-256 digest of the document's content identifier
+pub type DocumentCID = [u8; 32];
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+
+/// Position of a council member in a poll
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum CouncilVote {
+	/// Accept the proposal
+	Aye,
+	/// Reject the proposal, requires a justification document
+	Nay,
+	/// Participate in the poll without taking a side. Counts toward the quorum, but not
+	/// toward the share of rejecting votes.
+	Abstain,
+}
 
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
-	// type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
 	/// Define Identity type. Must implement PeerReviewedPhysicalIdentity trait
 	type Identity: PeerReviewedPhysicalIdentity<ProofType, IdentityId = IdentityId<Self>,
 						IdentityLevel = IdentityLevel, Address = Self::AccountId>;
+
+	/// Type that manages balances
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// How much has to be reserved to become a council candidate?
+	type CandidacyBond: Get<BalanceOf<Self>>;
+
+	/// How many percent of the votes must a candidate receive to get the full bond back?
+	type CandidacyApprovalFloor: Get<Permill>;
+
+	/// How many percent of the bond of a candidate below the approval floor are slashed?
+	type CandidacyBondSlash: Get<Permill>;
+
+	/// Receives the slashed candidacy bonds (e.g. the treasury)
+	type SlashedCandidacyBonds: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+	/// How many members can the council have at most? Also the number of seats of an election.
+	type MaxMembers: Get<u32>;
+
+	/// Which identity level is required to nominate yourself and to vote in council elections?
+	type ElectionIdentityLevel: Get<IdentityLevel>;
+
+	/// How many identities can vote in a council election?
+	type MaxVoters: Get<u32>;
+
+	/// After how many proposal rounds is the council elected?
+	type ElectionRounds: Get<u32>;
+
+	/// How many proposal rounds does the term of a council member last? Members whose term
+	/// expired leave the council and trigger an election.
+	type TermRounds: Get<u32>;
+}
+
+decl_event! {
+	pub enum Event<T> where ID = IdentityId<T> {
+		/// An identity was added to the council \[Member\]
+		MemberAdded(ID),
+		/// An identity was removed from the council \[Member\]
+		MemberRemoved(ID),
+		/// A council member was replaced by another identity \[Removed, Added\]
+		MemberSwapped(ID, ID),
+		/// The council members were replaced \[Members\]
+		MembersReset(Vec<ID>),
+		/// The identified users elected a new council \[Members\]
+		CouncilElected(Vec<ID>),
+		/// The term of a council member expired \[Member\]
+		TermExpired(ID),
+	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Council {
 		pub TicketNumber get(fn ticket): Ticket = 0;
+		/// Current council members
+		pub Members get(fn members) config(): Vec<IdentityId<T>> = Vec::new();
+		/// Member -> Proposal round in which the term of the member ends.
+		/// Genesis members serve until the first election.
+		pub MemberTermEnd get(fn member_term_end): map hasher(identity) IdentityId<T> => Option<u32> = None;
+		/// Index of the running proposal round, as reported by the proposal state machine
+		pub CurrentRound get(fn current_round): u32 = 0;
+		/// Ticket -> Aye (true) and Nay (false) votes of the council members
+		pub PollVotes get(fn poll_votes): map hasher(identity)
+			Ticket => Vec<(IdentityId<T>, bool)> = Vec::new();
+		/// (Ticket, Member) -> Position of the member in the poll, including abstentions
+		pub PollPositions get(fn poll_position): double_map hasher(identity) Ticket,
+			hasher(identity) IdentityId<T> => Option<CouncilVote> = None;
+		/// (Ticket, Member) -> Justification document of a rejecting vote
+		pub PollJustifications get(fn poll_justification): double_map hasher(identity) Ticket,
+			hasher(identity) IdentityId<T> => Option<DocumentCID> = None;
+		/// Candidate -> (Platform, reserved bond) of the candidates for the next council election
+		pub Candidates get(fn candidates): map hasher(identity)
+			IdentityId<T> => Option<(DocumentCID, T::AccountId, BalanceOf<T>)> = None;
+		/// Voter -> Candidates and members the voter approves in the next council election
+		pub Approvals get(fn approvals): map hasher(identity)
+			IdentityId<T> => Vec<IdentityId<T>> = Vec::new();
+		/// Identity -> Number of voters that approve the identity in the next council election
+		pub ApprovalTally get(fn approval_tally): map hasher(identity) IdentityId<T> => u32 = 0;
+		/// Number of identities that vote in the next council election
+		pub VoterCount get(fn voter_count): u32 = 0;
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The identity is already a council candidate
+		AlreadyACandidate,
+		/// The identity is already a council member
+		AlreadyACouncilMember,
+		/// The council would exceed MaxMembers
+		TooManyMembers,
+		/// The identity level is too low to nominate yourself or to vote in council elections
+		IdentityLevelTooLow,
+		/// A voter can approve at most MaxMembers candidates
+		TooManyApprovals,
+		/// MaxVoters identities vote in the next council election already
+		TooManyVoters,
+		/// The account cannot reserve enough funds
+		InsufficientBalance,
+		/// The identity is not a council candidate
+		NotACandidate,
+		/// The identity is not a council member
+		NotACouncilMember,
+		/// Poll does not exist
+		PollNotExistant,
+		/// A rejecting vote must carry the CID of a justification document
+		JustificationRequired,
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		/// As an identified user, vote for a council member
-		#[weight = 10_000]
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// How much has to be reserved to become a council candidate?
+		const CandidacyBond: BalanceOf<T> = T::CandidacyBond::get();
+
+		/// How many percent of the votes must a candidate receive to get the full bond back?
+		const CandidacyApprovalFloor: Permill = T::CandidacyApprovalFloor::get();
+
+		/// How many percent of the bond of a candidate below the approval floor are slashed?
+		const CandidacyBondSlash: Permill = T::CandidacyBondSlash::get();
+
+		/// How many members can the council have at most? Also the number of seats of an election.
+		const MaxMembers: u32 = T::MaxMembers::get();
+
+		/// Which identity level is required to nominate yourself and to vote in council elections?
+		const ElectionIdentityLevel: IdentityLevel = T::ElectionIdentityLevel::get();
+
+		/// How many identities can vote in a council election?
+		const MaxVoters: u32 = T::MaxVoters::get();
+
+		/// After how many proposal rounds is the council elected?
+		const ElectionRounds: u32 = T::ElectionRounds::get();
+
+		/// How many proposal rounds does the term of a council member last? Members whose term
+		/// expired leave the council and trigger an election.
+		const TermRounds: u32 = T::TermRounds::get();
+
+		/// As root, set the council members
+		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		fn set_members(origin, members: Vec<IdentityId<T>>) {
+			ensure_root(origin)?;
+			let mut members: Vec<IdentityId<T>> = members;
+			members.sort();
+			members.dedup();
+			ensure!(members.len() <= T::MaxMembers::get() as usize, Error::<T>::TooManyMembers);
+			Self::install_members(&members, false);
+			Self::deposit_event(RawEvent::MembersReset(members));
+		}
+
+		/// As root, add an identity to the council
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		fn add_member(origin, member: IdentityId<T>) {
+			ensure_root(origin)?;
+			let mut members: Vec<IdentityId<T>> = <Members<T>>::get();
+			ensure!(!members.contains(&member), Error::<T>::AlreadyACouncilMember);
+			ensure!(members.len() < T::MaxMembers::get() as usize, Error::<T>::TooManyMembers);
+			members.push(member.clone());
+			Self::install_members(&members, false);
+			Self::deposit_event(RawEvent::MemberAdded(member));
+		}
+
+		/// As root, remove an identity from the council. Its votes on running polls are not
+		/// counted anymore.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		fn remove_member(origin, member: IdentityId<T>) {
+			ensure_root(origin)?;
+			let mut members: Vec<IdentityId<T>> = <Members<T>>::get();
+			let position: usize = members.iter().position(|m| *m == member).ok_or(Error::<T>::NotACouncilMember)?;
+			members.remove(position);
+			Self::install_members(&members, false);
+			Self::deposit_event(RawEvent::MemberRemoved(member));
+		}
+
+		/// As root, replace a council member by another identity
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		fn swap_member(origin, remove: IdentityId<T>, add: IdentityId<T>) {
+			ensure_root(origin)?;
+			let mut members: Vec<IdentityId<T>> = <Members<T>>::get();
+			ensure!(!members.contains(&add), Error::<T>::AlreadyACouncilMember);
+			let position: usize = members.iter().position(|m| *m == remove).ok_or(Error::<T>::NotACouncilMember)?;
+			members[position] = add.clone();
+			Self::install_members(&members, false);
+			Self::deposit_event(RawEvent::MemberSwapped(remove, add));
+		}
+
+		/// As an identified user, nominate yourself as a council candidate by reserving the
+		/// CandidacyBond and publishing the CID of your platform
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2,2)]
+		fn submit_candidacy(origin, platform: DocumentCID) {
+			let caller = ensure_signed(origin)?;
+			Self::do_submit_candidacy(caller, platform)?;
+		}
+
+		/// As a council candidate, withdraw the candidacy and receive the bond back
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2,2)]
+		fn withdraw_candidacy(origin) {
+			let caller = ensure_signed(origin)?;
+			Self::do_withdraw_candidacy(T::Identity::get_identity_id(&caller))?;
+		}
+
+		/// As an identified user, approve a candidate in the next council election
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,1)]
 		fn vote_council_member(origin, candidate: IdentityId<T>) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_council_member(T::Identity::get_identity_id(&caller), candidate)?;
 		}
 
-		/// As an identified user, vote to reelect council
-		#[weight = 10_000]
+		/// As an identified user, approve every current council member in the next council election
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,1)]
 		fn vote_reelect_council(origin) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_reelect_council(T::Identity::get_identity_id(&caller))?;
 		}
 
-		/// As an identified user, vote to reelect a specific council member
-		#[weight = 10_000]
+		/// As an identified user, approve a current council member in the next council election
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,1)]
 		fn vote_reelect(origin, member: IdentityId<T>) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_reelect(T::Identity::get_identity_id(&caller), member)?;
@@ -73,49 +287,274 @@ decl_module! {
 			Self::do_add_poll(documents, until)?;
 		}
 
-		/// As a council member, vote for a poll
-		#[weight = 10_000]
-		fn vote_poll(origin, poll: Ticket, accept: bool) {
+		/// As a council member, vote Aye, Nay or Abstain in a poll. Nay votes must carry the
+		/// sha2-256 digest of the CID of a document that justifies the rejection.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3,3)]
+		fn vote_poll(origin, poll: Ticket, vote: CouncilVote, justification: Option<DocumentCID>) {
 			let caller = ensure_signed(origin)?;
-			Self::do_vote_poll(T::Identity::get_identity_id(&caller), poll, accept)?;
+			T::Identity::ensure_attested_signer(&caller)?;
+			Self::do_vote_poll(T::Identity::get_identity_id(&caller), poll, vote, justification)?;
 		}
 	}
 }
 
 impl<T: Trait> Module<T> {
-	fn do_vote_council_member(_voter: IdentityId<T>, _candidate: IdentityId<T>)
-		-> Result<(), DispatchError>
-	{
-		// TODO implement
+	fn do_submit_candidacy(account: T::AccountId, platform: DocumentCID) -> Result<(), DispatchError> {
+		let candidate: IdentityId<T> = T::Identity::get_identity_id(&account);
+		Self::ensure_election_level(&candidate)?;
+		ensure!(!<Candidates<T>>::contains_key(&candidate), Error::<T>::AlreadyACandidate);
+		let bond: BalanceOf<T> = T::CandidacyBond::get();
+		T::Currency::reserve(&account, bond).map_err(|_| Error::<T>::InsufficientBalance)?;
+		<Candidates<T>>::insert(candidate, (platform, account, bond));
+		Ok(())
+	}
+
+	fn do_withdraw_candidacy(candidate: IdentityId<T>) -> Result<(), DispatchError> {
+		let (_, account, bond) = <Candidates<T>>::take(&candidate).ok_or(Error::<T>::NotACandidate)?;
+		T::Currency::unreserve(&account, bond);
 		Ok(())
 	}
 
-	fn do_vote_reelect_council(_voter: IdentityId<T>) -> Result<(), DispatchError> {
-		// TODO implement
+	/// Close the candidacy after an election. The bond is returned, unless the candidate received
+	/// less than CandidacyApprovalFloor of the votes, in which case CandidacyBondSlash of the bond
+	/// is redirected to SlashedCandidacyBonds.
+	pub fn settle_candidacy(candidate: &IdentityId<T>, approval: Permill) -> Result<(), DispatchError> {
+		let (_, account, bond) = <Candidates<T>>::take(candidate).ok_or(Error::<T>::NotACandidate)?;
+		let mut slash: BalanceOf<T> = BalanceOf::<T>::zero();
+
+		if approval < T::CandidacyApprovalFloor::get() {
+			slash = T::CandidacyBondSlash::get() * bond;
+			let (imbalance, _) = T::Currency::slash_reserved(&account, slash);
+			T::SlashedCandidacyBonds::on_unbalanced(imbalance);
+		}
+
+		T::Currency::unreserve(&account, bond - slash);
 		Ok(())
 	}
 
-	fn do_vote_reelect(_voter: IdentityId<T>, _member: IdentityId<T>) -> Result<(), DispatchError> {
-		// TODO implement
+	fn ensure_election_level(identity: &IdentityId<T>) -> Result<(), DispatchError> {
+		ensure!(T::Identity::get_identity_level(identity) >= T::ElectionIdentityLevel::get(),
+			Error::<T>::IdentityLevelTooLow
+		);
+		Ok(())
+	}
+
+	/// Add identities to the approvals of a voter and count them in the ApprovalTally. A voter can
+	/// approve at most MaxMembers identities, at most MaxVoters identities vote in an election.
+	fn approve(voter: IdentityId<T>, approved: Vec<IdentityId<T>>) -> Result<(), DispatchError> {
+		Self::ensure_election_level(&voter)?;
+		let mut approvals: Vec<IdentityId<T>> = <Approvals<T>>::get(&voter);
+		let new_voter: bool = approvals.is_empty();
+		ensure!(!new_voter || <VoterCount>::get() < T::MaxVoters::get(), Error::<T>::TooManyVoters);
+		let mut added: Vec<IdentityId<T>> = Vec::new();
+
+		for identity in approved {
+			if !approvals.contains(&identity) {
+				approvals.push(identity.clone());
+				added.push(identity);
+			}
+		}
+
+		ensure!(approvals.len() <= T::MaxMembers::get() as usize, Error::<T>::TooManyApprovals);
+
+		for identity in added.iter() {
+			<ApprovalTally<T>>::mutate(identity, |votes| *votes = votes.saturating_add(1));
+		}
+
+		if new_voter && !approvals.is_empty() {
+			<VoterCount>::mutate(|count| *count = count.saturating_add(1));
+		}
+
+		<Approvals<T>>::insert(&voter, approvals);
 		Ok(())
 	}
 
+	fn do_vote_council_member(voter: IdentityId<T>, candidate: IdentityId<T>)
+		-> Result<(), DispatchError>
+	{
+		// The election draws its candidates from the candidacy list
+		ensure!(<Candidates<T>>::contains_key(&candidate), Error::<T>::NotACandidate);
+		Self::approve(voter, sp_std::vec![candidate])
+	}
+
+	fn do_vote_reelect_council(voter: IdentityId<T>) -> Result<(), DispatchError> {
+		Self::approve(voter, <Members<T>>::get())
+	}
+
+	fn do_vote_reelect(voter: IdentityId<T>, member: IdentityId<T>) -> Result<(), DispatchError> {
+		ensure!(<Members<T>>::get().contains(&member), Error::<T>::NotACouncilMember);
+		Self::approve(voter, sp_std::vec![member])
+	}
+
+	/// Elect the council from the candidates and the current members. Every approval counts as
+	/// one vote, the MaxMembers identities with the most votes form the new council. The council
+	/// stays in office if nobody voted. Returns the consumed weight.
+	fn do_elect() -> Weight {
+		let voters: u32 = <VoterCount>::take();
+
+		if voters == 0 {
+			return T::DbWeight::get().reads_writes(1, 1);
+		}
+
+		// The approvals were counted when they were cast, at most MaxVoters approvals are removed
+		let approvals: u64 = <Approvals<T>>::drain().count() as u64;
+		let mut tally: Vec<(IdentityId<T>, u32)> = <Candidates<T>>::iter()
+			.map(|(candidate, _)| candidate)
+			.chain(<Members<T>>::get().into_iter())
+			.map(|identity| (identity, 0))
+			.collect();
+		tally.sort();
+		tally.dedup();
+
+		for entry in tally.iter_mut() {
+			entry.1 = <ApprovalTally<T>>::get(&entry.0);
+		}
+
+		let approved: u64 = <ApprovalTally<T>>::drain().count() as u64;
+		let identities: u64 = tally.len() as u64;
+
+		// Most votes first, the identity breaks ties deterministically
+		tally.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+		let members: Vec<IdentityId<T>> = tally.iter()
+			.filter(|(_, votes)| *votes > 0)
+			.take(T::MaxMembers::get() as usize)
+			.map(|(identity, _)| identity.clone())
+			.collect();
+
+		for (identity, votes) in tally.iter() {
+			if <Candidates<T>>::contains_key(identity) {
+				let _ = Self::settle_candidacy(identity, Permill::from_rational_approximation(*votes, voters));
+			}
+		}
+
+		let installed: u64 = members.len() as u64;
+		Self::install_members(&members, true);
+		Self::deposit_event(RawEvent::CouncilElected(members));
+		// Every identity is read from the tally and, if it is a candidate, settled (2 reads, 3 writes)
+		T::DbWeight::get().reads_writes(
+			2 + approvals + approved + 3 * identities,
+			3 + approvals + approved + 3 * identities + installed,
+		)
+	}
+
+	/// Replace the council members. New members start a term of TermRounds rounds, the terms
+	/// of continuing members are only renewed if `renew` is set.
+	fn install_members(members: &[IdentityId<T>], renew: bool) {
+		let previous: Vec<IdentityId<T>> = <Members<T>>::get();
+		let term_end: u32 = <CurrentRound>::get().saturating_add(T::TermRounds::get());
+
+		for member in previous.iter().filter(|m| !members.contains(m)) {
+			<MemberTermEnd<T>>::remove(member);
+		}
+
+		for member in members.iter().filter(|m| renew || !previous.contains(m)) {
+			<MemberTermEnd<T>>::insert(member, term_end);
+		}
+
+		<Members<T>>::put(members);
+	}
+
+	/// Remove the members whose term ended and return whether any term ended
+	fn expire_terms() -> bool {
+		let current: u32 = <CurrentRound>::get();
+		let (expired, remaining): (Vec<IdentityId<T>>, Vec<IdentityId<T>>) = <Members<T>>::get().into_iter()
+			.partition(|member| <MemberTermEnd<T>>::get(member).map_or(false, |end| end <= current));
+
+		if expired.is_empty() {
+			return false;
+		}
+
+		Self::install_members(&remaining, false);
+
+		for member in expired {
+			Self::deposit_event(RawEvent::TermExpired(member));
+		}
+
+		true
+	}
+
 	fn do_add_poll(_document: Vec<DocumentCID>, _until: BlockNumber<T>) -> Result<Ticket, DispatchError> {
 		let ticket: Ticket = <TicketNumber>::get();
 		TicketNumber::put(ticket + 1);
 		Ok(ticket)
 	}
 
-	fn do_vote_poll(_member: IdentityId<T>, _poll: Ticket, _accept: bool) -> Result<(), DispatchError> {
+	fn do_vote_poll(member: IdentityId<T>, poll: Ticket, vote: CouncilVote, justification: Option<DocumentCID>)
+		-> Result<(), DispatchError>
+	{
+		ensure!(<Members<T>>::get().contains(&member), Error::<T>::NotACouncilMember);
+		ensure!(poll < <TicketNumber>::get(), Error::<T>::PollNotExistant);
+		ensure!(vote != CouncilVote::Nay || justification.is_some(), Error::<T>::JustificationRequired);
+
+		match (vote, justification) {
+			(CouncilVote::Nay, Some(justification)) => <PollJustifications<T>>::insert(poll, &member, justification),
+			_ => <PollJustifications<T>>::remove(poll, &member),
+		}
+
+		<PollPositions<T>>::insert(poll, &member, vote);
+
+		// A council member can change the vote as long as the poll is running
+		<PollVotes<T>>::mutate(poll, |votes| {
+			votes.retain(|(voter, _)| *voter != member);
+
+			match vote {
+				CouncilVote::Aye => votes.push((member, true)),
+				CouncilVote::Nay => votes.push((member, false)),
+				CouncilVote::Abstain => (),
+			}
+		});
+
 		Ok(())
 	}
 
-	fn do_get_result(_poll: &Ticket) -> Option<Vec<(IdentityId<T>, bool)>> {
-		Some(Vec::from([(Default::default(), true), (Default::default(), true), (Default::default(), true),
-			(Default::default(), true), (Default::default(), true), (Default::default(), true)]))
+	fn do_get_result(poll: &Ticket) -> Option<Vec<(IdentityId<T>, bool)>> {
+		if *poll >= <TicketNumber>::get() {
+			return None;
+		}
+
+		Some(<PollVotes<T>>::get(poll))
+	}
+
+	fn do_is_member(identity: &IdentityId<T>) -> bool {
+		<Members<T>>::get().contains(identity)
+	}
+
+	fn do_get_justifications(poll: &Ticket) -> Vec<(IdentityId<T>, DocumentCID)> {
+		<PollJustifications<T>>::iter_prefix(poll).collect()
+	}
+
+	fn do_get_abstentions(poll: &Ticket) -> Vec<IdentityId<T>> {
+		<PollPositions<T>>::iter_prefix(poll)
+			.filter(|(_, vote)| *vote == CouncilVote::Abstain)
+			.map(|(member, _)| member)
+			.collect()
+	}
+
+	fn do_member_count() -> u32 {
+		<Members<T>>::decode_len().unwrap_or(0) as u32
+	}
+
+	fn do_vote_count(poll: &Ticket) -> u32 {
+		<PollPositions<T>>::iter_prefix(poll).count() as u32
 	}
 }
 
+impl<T: Trait> Contains<T::AccountId> for Module<T> {
+	fn sorted_members() -> Vec<T::AccountId> {
+		let mut members: Vec<T::AccountId> = <Members<T>>::get();
+		members.sort();
+		members
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &T::AccountId) {
+		<Members<T>>::mutate(|members| {
+			if !members.contains(who) {
+				members.push(who.clone());
+			}
+		});
+	}
+}
 
 impl<T: Trait> traits::Council for Module<T>
 {
@@ -150,15 +589,55 @@ impl<T: Trait> traits::Council for Module<T>
 		Self::do_add_poll(documents, until)
 	}
 
-	/// As a council member, vote for a poll
-	fn vote_poll(member: Self::IdentityId, poll: Self::Ticket, accept: bool)
+	/// As a council member, vote for a poll. Nay votes must carry a justification.
+	fn vote_poll(member: Self::IdentityId, poll: Self::Ticket, vote: CouncilVote, justification: Option<Self::DocumentCID>)
 		-> Result<(), DispatchError>
 	{
-		Self::do_vote_poll(member, poll, accept)
+		Self::do_vote_poll(member, poll, vote, justification)
 	}
 
-	/// Retrieve result of a poll
+	/// Retrieve the Aye (true) and Nay (false) votes of a poll
 	fn get_result(poll: &Self::Ticket) -> Option<Vec<(Self::IdentityId, bool)>> {
 		Self::do_get_result(poll)
 	}
+
+	/// Retrieve the members that abstained in a poll
+	fn get_abstentions(poll: &Self::Ticket) -> Vec<Self::IdentityId> {
+		Self::do_get_abstentions(poll)
+	}
+
+	/// Retrieve the justifications of the rejecting votes of a poll
+	fn get_justifications(poll: &Self::Ticket) -> Vec<(Self::IdentityId, Self::DocumentCID)> {
+		Self::do_get_justifications(poll)
+	}
+
+	/// Check whether an identity is a council member
+	fn is_member(identity: &Self::IdentityId) -> bool {
+		Self::do_is_member(identity)
+	}
+
+	/// Expire the terms that ended with the round and elect the council every ElectionRounds
+	/// proposal rounds or as soon as a term expired
+	fn on_round_finished(round: u32) -> Weight {
+		let current: u32 = round.saturating_add(1);
+		<CurrentRound>::put(current);
+		let members: u64 = <Members<T>>::decode_len().unwrap_or(0) as u64;
+		let weight: Weight = T::DbWeight::get().reads_writes(2 + members, 2 + members);
+
+		if Self::expire_terms() || current % T::ElectionRounds::get().max(1) == 0 {
+			return weight.saturating_add(Self::do_elect());
+		}
+
+		weight
+	}
+
+	/// Retrieve the number of council members
+	fn member_count() -> u32 {
+		Self::do_member_count()
+	}
+
+	/// Retrieve the number of votes a poll has received, including abstentions
+	fn vote_count(poll: &Self::Ticket) -> u32 {
+		Self::do_vote_count(poll)
+	}
 }
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use frame_support::dispatch::{Codec, DispatchError, EncodeLike, fmt::Debug, Vec};
+use frame_support::{dispatch::{Codec, DispatchError, EncodeLike, fmt::Debug, Vec}, weights::Weight};
 use num_traits::Num;
+use crate::CouncilVote;
 
 /// Beginning of the council trait definition
 /// Members of the council should be elected every <period>, should be able to be voted of
@@ -34,9 +35,22 @@ pub trait Council
 	fn vote_reelect(voter: Self::IdentityId, member: Self::IdentityId) -> Result<(), DispatchError>;
 	/// As root, queue a poll
 	fn add_poll(documents: Vec<Self::DocumentCID>, until: Self::BlockNumber) -> Result<Self::Ticket, DispatchError>;
-	/// As a council member, vote for a poll
-	fn vote_poll(member: Self::IdentityId, poll: Self::Ticket, accept: bool) -> Result<(), DispatchError>;
-	/// Retrieve result of a poll
+	/// As a council member, vote for a poll. Nay votes must carry a justification.
+	fn vote_poll(member: Self::IdentityId, poll: Self::Ticket, vote: CouncilVote, justification: Option<Self::DocumentCID>)
+		-> Result<(), DispatchError>;
+	/// Retrieve the Aye (true) and Nay (false) votes of a poll
 	fn get_result(poll: &Self::Ticket) -> Option<Vec<(Self::IdentityId, bool)>>;
+	/// Retrieve the members that abstained in a poll
+	fn get_abstentions(poll: &Self::Ticket) -> Vec<Self::IdentityId>;
+	/// Retrieve the justifications of the rejecting votes of a poll
+	fn get_justifications(poll: &Self::Ticket) -> Vec<(Self::IdentityId, Self::DocumentCID)>;
+	/// Check whether an identity is a council member
+	fn is_member(identity: &Self::IdentityId) -> bool;
+	/// Notify the council that a proposal round was finished. Returns the consumed weight.
+	fn on_round_finished(round: u32) -> Weight;
+	/// Retrieve the number of council members
+	fn member_count() -> u32;
+	/// Retrieve the number of votes a poll has received, including abstentions
+	fn vote_count(poll: &Self::Ticket) -> u32;
 	// TODO
 }
@@ -18,12 +18,19 @@
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 
-use frame_support::{decl_event, decl_module, decl_storage, dispatch::{DispatchError, Vec},
-					traits::{Currency, ReservableCurrency}};
+use codec::{Decode, Encode};
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, dispatch::{DispatchError, Vec}, ensure,
+					sp_runtime::{ModuleId, traits::{AccountIdConversion, CheckedAdd, Saturating, Zero}},
+					traits::{BalanceStatus, Currency, EnsureOrigin, Get, ReservableCurrency}, weights::Weight};
 use frame_system::{ensure_signed, ensure_root};
 use pallet_community_identity::{IdentityId, IdentityLevel, ProofType, traits::PeerReviewedPhysicalIdentity};
-use pallet_proposal_types::ProposalWinner;
-use crate::{traits::ProjectTrait, types::{DocumentCID, Project, ProjectID}};
+use pallet_proposal_types::{cid_digest, ProposalCID, ProposalWinner};
+use crate::{
+	traits::{MilestoneConfirmation, OnProjectCompleted, ProjectTrait},
+	types::{ApprovalMethod, COMPLETION_MILESTONE, DocumentCID, Milestone, MilestoneIndex, MilestoneState, Project,
+		ProjectID, ProjectState},
+};
+mod migrations;
 pub mod traits;
 pub mod types;
 
@@ -37,24 +44,82 @@ mod mock;
 mod tests;
 
 
+/// Storage layout versions of this pallet
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+enum Releases {
+	/// Project proposals store their CIDs as Vec<u8>
+	V1VecCid,
+	/// Project proposals store their CIDs as fixed length digests
+	V2FixedCid,
+	/// Projects have a budget and a lifecycle state
+	V3ProjectLifecycle,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1VecCid
+	}
+}
+
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
-	/// Type that manages balances
+	/// Currency the project budgets are denominated in. It can be another asset than the
+	/// currency of the bonds and rewards of the proposal pallet (e.g. a stablecoin).
 	type Currency: ReservableCurrency<Self::AccountId>;
 
 	/// Define Identity type. Must implement PeerReviewedPhysicalIdentity trait
 	type Identity: PeerReviewedPhysicalIdentity<ProofType, IdentityId = IdentityId<Self>,
 						IdentityLevel = IdentityLevel, Address = Self::AccountId>;
+
+	/// Origin that confirms the completion of projects and aborts projects (e.g. the council)
+	type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Identifier of the treasury account that funds the projects
+	type TreasuryModuleId: Get<ModuleId>;
+
+	/// How many blocks after the spawn of a project is its deadline?
+	type ProjectDuration: Get<Self::BlockNumber>;
+
+	/// Into how many milestones can the budget of a project be split at most?
+	type MaxMilestones: Get<u32>;
+
+	/// Confirmation rounds for milestones and project completions that the community approves.
+	/// Use () if only the council approves them.
+	type MilestoneConfirmation: MilestoneConfirmation;
+
+	/// Receives the completed projects, e.g. to reward the proposer
+	type OnCompleted: OnProjectCompleted<IdentityId<Self>, BalanceOf<Self>>;
 }
 
 decl_event! {
-	pub enum Event<T> where PRJ = Project<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>> {
+	pub enum Event<T> where Balance = BalanceOf<T>,
+							PRJ = Project<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>> {
 		/// A new project has been spawned \[Project\]
 		ProjectSpawned(PRJ),
+		/// The proposer started to work on a project \[Project\]
+		ProjectStarted(ProjectID),
+		/// The proposer published a progress report \[Project, Report\]
+		ProjectReported(ProjectID, ProposalCID),
+		/// A project was completed and its budget paid to the proposer \[Project, Budget\]
+		ProjectCompleted(ProjectID, Balance),
+		/// A project was aborted and its remaining budget released in the treasury \[Project, Budget\]
+		ProjectAborted(ProjectID, Balance),
+		/// The proposer split the budget of a project into milestones \[Project, Milestones\]
+		MilestonesSet(ProjectID, u32),
+		/// The proposer claims that a milestone was reached \[Project, Milestone, Evidence\]
+		MilestoneClaimed(ProjectID, MilestoneIndex, ProposalCID),
+		/// The amount of a milestone was paid to the proposer \[Project, Milestone, Amount\]
+		MilestonePaid(ProjectID, MilestoneIndex, Balance),
+		/// The claim of a milestone was rejected \[Project, Milestone\]
+		MilestoneRejected(ProjectID, MilestoneIndex),
+		/// The proposer reported the completion of a project \[Project, Evidence\]
+		CompletionReported(ProjectID, ProposalCID),
+		/// The completion of a project was disputed and awaits the decision of the council \[Project\]
+		CompletionEscalated(ProjectID),
 	}
 }
 
@@ -63,18 +128,167 @@ decl_storage! {
 		pub ProjectNumber get(fn project_number): ProjectID = 0;
 		pub ProjectStorage get(fn project): map hasher(identity)
 			ProjectID => Option<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>> = None;
+		/// Project -> (Block, sha2-256 digest of the report CID) of every progress report
+		pub ProjectReports get(fn project_reports): map hasher(identity)
+			ProjectID => Vec<(T::BlockNumber, ProposalCID)> = Vec::new();
+		/// Project -> Milestones the budget of the project is split into
+		pub Milestones get(fn milestones): map hasher(identity)
+			ProjectID => Vec<Milestone<BalanceOf<T>>> = Vec::new();
+		/// Version of the storage layout
+		StorageVersion build(|_| Releases::V3ProjectLifecycle): Releases;
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The project does not exist
+		ProjectNotExistant,
+		/// Only the proposer of the project can do this
+		NotTheProposer,
+		/// The project is not in the required lifecycle state
+		InvalidProjectState,
+		/// The treasury cannot reserve the budget of the project
+		InsufficientTreasuryFunds,
+		/// The bytes are not a CID with a sha2-256 multihash
+		InvalidCid,
+		/// Between one and MaxMilestones milestones must split the whole budget
+		InvalidMilestones,
+		/// The milestone does not exist
+		MilestoneNotExistant,
+		/// The milestone is not in the required payout state
+		InvalidMilestoneState,
+		/// The milestone is approved by another method
+		WrongApprovalMethod,
+		/// The confirmation round of the milestone is not finished yet
+		ConfirmationRunning,
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
 		fn deposit_event() = default;
 
-		/// As root, spawn a project from a proposal
+		/// Identifier of the treasury account that funds the projects
+		const TreasuryModuleId: ModuleId = T::TreasuryModuleId::get();
+
+		/// How many blocks after the spawn of a project is its deadline?
+		const ProjectDuration: T::BlockNumber = T::ProjectDuration::get();
+
+		/// Into how many milestones can the budget of a project be split at most?
+		const MaxMilestones: u32 = T::MaxMilestones::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		/// As root, spawn a project from a proposal and reserve its budget in the treasury
 		#[weight = 10_000]
-		fn spawn_project(origin, proposal: ProposalWinner<IdentityId<T>>) {
+		fn spawn_project(origin, proposal: ProposalWinner<IdentityId<T>>, budget: BalanceOf<T>) {
 			ensure_root(origin)?;
-			Self::do_spawn_project(proposal)?;
+			Self::do_spawn_project(proposal, budget)?;
+		}
+
+		/// As the proposer, start to work on a spawned project
+		#[weight = 10_000]
+		fn start(origin, project: ProjectID) {
+			let caller = ensure_signed(origin)?;
+			Self::do_start(T::Identity::get_identity_id(&caller), project)?;
+		}
+
+		/// As the proposer, publish the binary CID of a progress report of a running project
+		#[weight = 10_000]
+		fn report(origin, project: ProjectID, report: Vec<u8>) {
+			let caller = ensure_signed(origin)?;
+			let report: ProposalCID = cid_digest(&report).ok_or(Error::<T>::InvalidCid)?;
+			Self::do_report(T::Identity::get_identity_id(&caller), project, report)?;
+		}
+
+		/// As the proposer, split the budget of a spawned project into milestones. The amounts
+		/// must add up to the budget, every milestone is either approved by the council or
+		/// confirmed by the community.
+		#[weight = 10_000]
+		fn set_milestones(origin, project: ProjectID, milestones: Vec<(BalanceOf<T>, ApprovalMethod)>) {
+			let caller = ensure_signed(origin)?;
+			Self::do_set_milestones(T::Identity::get_identity_id(&caller), project, milestones)?;
+		}
+
+		/// As the proposer, claim that a milestone of a running project was reached and publish
+		/// the binary CID of the evidence
+		#[weight = 10_000]
+		fn claim_milestone(origin, project: ProjectID, milestone: MilestoneIndex, evidence: Vec<u8>) {
+			let caller = ensure_signed(origin)?;
+			let evidence: ProposalCID = cid_digest(&evidence).ok_or(Error::<T>::InvalidCid)?;
+			Self::do_claim_milestone(T::Identity::get_identity_id(&caller), project, milestone, evidence)?;
+		}
+
+		/// As the council, approve or reject a claimed milestone. Approved milestones are paid
+		/// to the proposer.
+		#[weight = 10_000]
+		fn approve_milestone(origin, project: ProjectID, milestone: MilestoneIndex, approve: bool) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			Self::ensure_claimed(project, milestone, ApprovalMethod::Council)?;
+			Self::settle_milestone(project, milestone, approve)?;
+		}
+
+		/// Conclude the finished confirmation round of a claimed milestone. Confirmed milestones
+		/// are paid to the proposer.
+		#[weight = 10_000]
+		fn conclude_milestone(origin, project: ProjectID, milestone: MilestoneIndex) {
+			ensure_signed(origin)?;
+			Self::ensure_claimed(project, milestone, ApprovalMethod::Community)?;
+			let confirmed: bool = T::MilestoneConfirmation::conclude_confirmation(project, milestone)
+				.ok_or(Error::<T>::ConfirmationRunning)?;
+			Self::settle_milestone(project, milestone, confirmed)?;
+		}
+
+		/// As the proposer, report the completion of a running project and publish the binary CID
+		/// of the evidence. The identified users verify the completion in a confirmation round,
+		/// without community confirmation the council decides.
+		#[weight = 10_000]
+		fn report_completion(origin, project: ProjectID, evidence: Vec<u8>) {
+			let caller = ensure_signed(origin)?;
+			let evidence: ProposalCID = cid_digest(&evidence).ok_or(Error::<T>::InvalidCid)?;
+			Self::do_report_completion(T::Identity::get_identity_id(&caller), project, evidence)?;
+		}
+
+		/// Conclude the finished confirmation round of a reported completion. Confirmed projects are
+		/// completed, disputed projects are escalated to the council.
+		#[weight = 10_000]
+		fn conclude_completion(origin, project: ProjectID) {
+			ensure_signed(origin)?;
+			let mut document = Self::project_in_state(project, &[ProjectState::CompletionReported])?;
+			let confirmed: bool = T::MilestoneConfirmation::conclude_confirmation(project, COMPLETION_MILESTONE)
+				.ok_or(Error::<T>::ConfirmationRunning)?;
+
+			if confirmed {
+				Self::do_complete(project, &[ProjectState::CompletionReported])?;
+			} else {
+				document.state = ProjectState::Escalated;
+				<ProjectStorage<T>>::insert(project, document);
+				Self::deposit_event(Event::<T>::CompletionEscalated(project));
+			}
+		}
+
+		/// As the council, confirm the completion of a running or escalated project. The budget is
+		/// paid to the proposer, projects with milestones are only completed once every milestone
+		/// was paid.
+		#[weight = 10_000]
+		fn complete(origin, project: ProjectID) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			Self::do_complete(project, &[ProjectState::Running, ProjectState::Escalated])?;
+		}
+
+		/// As the council or the proposer, abort a project that is not finished yet. The budget
+		/// is released in the treasury.
+		#[weight = 10_000]
+		fn abort(origin, project: ProjectID) {
+			let proposer: Option<IdentityId<T>> = match T::CouncilOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(T::Identity::get_identity_id(&ensure_signed(origin)?)),
+			};
+			Self::do_abort(proposer, project)?;
 		}
 
 		/// As an identified user, apply as project leader
@@ -132,18 +346,198 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
+	/// Account of the treasury that funds the projects
+	pub fn treasury_account() -> T::AccountId {
+		T::TreasuryModuleId::get().into_account()
+	}
+
 	/// As root, spawn a project from a proposal
-	fn do_spawn_project(proposal: ProposalWinner<IdentityId<T>>) 
+	fn do_spawn_project(proposal: ProposalWinner<IdentityId<T>>, budget: BalanceOf<T>)
 		-> Result<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>, DispatchError>
 	{
+		T::Currency::reserve(&Self::treasury_account(), budget).map_err(|_| Error::<T>::InsufficientTreasuryFunds)?;
 		let pn: ProjectID = <ProjectNumber>::get();
-		let project = Project::new(pn, proposal);
+		let deadline: T::BlockNumber = <frame_system::Module<T>>::block_number() + T::ProjectDuration::get();
+		let project = Project::new(pn, proposal, budget, deadline);
 		ProjectStorage::<T>::insert(pn, &project);
 		ProjectNumber::put(pn+1);
 		Self::deposit_event(Event::<T>::ProjectSpawned(project.clone()));
 		Ok(project)
 	}
 
+	/// Get a project and ensure that it is in the required state
+	fn project_in_state(project: ProjectID, states: &[ProjectState])
+		-> Result<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>, DispatchError>
+	{
+		let project = <ProjectStorage<T>>::get(project).ok_or(Error::<T>::ProjectNotExistant)?;
+		ensure!(states.contains(&project.state), Error::<T>::InvalidProjectState);
+		Ok(project)
+	}
+
+	/// As the proposer, start to work on a spawned project
+	fn do_start(proposer: IdentityId<T>, project: ProjectID) -> Result<(), DispatchError> {
+		let mut document = Self::project_in_state(project, &[ProjectState::Spawned])?;
+		ensure!(document.proposal.proposer == proposer, Error::<T>::NotTheProposer);
+		document.state = ProjectState::Running;
+		<ProjectStorage<T>>::insert(project, document);
+		Self::deposit_event(Event::<T>::ProjectStarted(project));
+		Ok(())
+	}
+
+	/// As the proposer, publish a progress report of a running project
+	fn do_report(proposer: IdentityId<T>, project: ProjectID, report: ProposalCID) -> Result<(), DispatchError> {
+		let document = Self::project_in_state(project, &[ProjectState::Running])?;
+		ensure!(document.proposal.proposer == proposer, Error::<T>::NotTheProposer);
+		let now: T::BlockNumber = <frame_system::Module<T>>::block_number();
+		<ProjectReports<T>>::append(project, (now, report));
+		Self::deposit_event(Event::<T>::ProjectReported(project, report));
+		Ok(())
+	}
+
+	/// Split the budget of a spawned project into milestones
+	fn do_set_milestones(proposer: IdentityId<T>, project: ProjectID, milestones: Vec<(BalanceOf<T>, ApprovalMethod)>)
+		-> Result<(), DispatchError>
+	{
+		let document = Self::project_in_state(project, &[ProjectState::Spawned])?;
+		ensure!(document.proposal.proposer == proposer, Error::<T>::NotTheProposer);
+		ensure!(!milestones.is_empty() && milestones.len() <= T::MaxMilestones::get() as usize,
+			Error::<T>::InvalidMilestones
+		);
+		let total: BalanceOf<T> = milestones.iter()
+			.try_fold(BalanceOf::<T>::zero(), |total, (amount, _)| total.checked_add(amount))
+			.ok_or(Error::<T>::InvalidMilestones)?;
+		ensure!(total == document.budget, Error::<T>::InvalidMilestones);
+
+		let count: u32 = milestones.len() as u32;
+		<Milestones<T>>::insert(project, milestones.into_iter()
+			.map(|(amount, approval)| Milestone::new(amount, approval))
+			.collect::<Vec<_>>()
+		);
+		Self::deposit_event(Event::<T>::MilestonesSet(project, count));
+		Ok(())
+	}
+
+	/// Claim that a milestone of a running project was reached
+	fn do_claim_milestone(proposer: IdentityId<T>, project: ProjectID, milestone: MilestoneIndex, evidence: ProposalCID)
+		-> Result<(), DispatchError>
+	{
+		let document = Self::project_in_state(project, &[ProjectState::Running])?;
+		ensure!(document.proposal.proposer == proposer, Error::<T>::NotTheProposer);
+		let mut milestones: Vec<Milestone<BalanceOf<T>>> = <Milestones<T>>::get(project);
+		let entry = milestones.get_mut(milestone as usize).ok_or(Error::<T>::MilestoneNotExistant)?;
+		ensure!(entry.state == MilestoneState::Pending, Error::<T>::InvalidMilestoneState);
+
+		if entry.approval == ApprovalMethod::Community {
+			T::MilestoneConfirmation::open_confirmation(project, milestone, evidence)?;
+		}
+
+		entry.state = MilestoneState::Claimed(evidence);
+		<Milestones<T>>::insert(project, milestones);
+		Self::deposit_event(Event::<T>::MilestoneClaimed(project, milestone, evidence));
+		Ok(())
+	}
+
+	/// Ensure that the milestone of a running project is claimed and approved by `method`
+	fn ensure_claimed(project: ProjectID, milestone: MilestoneIndex, method: ApprovalMethod)
+		-> Result<(), DispatchError>
+	{
+		Self::project_in_state(project, &[ProjectState::Running])?;
+		let entry: Milestone<BalanceOf<T>> = <Milestones<T>>::get(project).get(milestone as usize).cloned()
+			.ok_or(Error::<T>::MilestoneNotExistant)?;
+		ensure!(entry.approval == method, Error::<T>::WrongApprovalMethod);
+
+		match entry.state {
+			MilestoneState::Claimed(_) => Ok(()),
+			_ => Err(Error::<T>::InvalidMilestoneState.into()),
+		}
+	}
+
+	/// Pay an approved milestone to the proposer or return a rejected milestone to Pending
+	fn settle_milestone(project: ProjectID, milestone: MilestoneIndex, approved: bool) -> Result<(), DispatchError> {
+		let document = Self::project_in_state(project, &[ProjectState::Running])?;
+		let mut milestones: Vec<Milestone<BalanceOf<T>>> = <Milestones<T>>::get(project);
+		let entry = milestones.get_mut(milestone as usize).ok_or(Error::<T>::MilestoneNotExistant)?;
+
+		if approved {
+			let proposer: T::AccountId = T::Identity::get_address(&document.proposal.proposer);
+			T::Currency::repatriate_reserved(&Self::treasury_account(), &proposer, entry.amount, BalanceStatus::Free)?;
+			entry.state = MilestoneState::Paid;
+			Self::deposit_event(Event::<T>::MilestonePaid(project, milestone, entry.amount));
+		} else {
+			entry.state = MilestoneState::Pending;
+			Self::deposit_event(Event::<T>::MilestoneRejected(project, milestone));
+		}
+
+		<Milestones<T>>::insert(project, milestones);
+		Ok(())
+	}
+
+	/// Sum of the milestones of a project that were paid already
+	fn paid_milestones(project: ProjectID) -> BalanceOf<T> {
+		<Milestones<T>>::get(project).iter()
+			.filter(|milestone| milestone.state == MilestoneState::Paid)
+			.fold(BalanceOf::<T>::zero(), |total, milestone| total.saturating_add(milestone.amount))
+	}
+
+	/// Ensure that every milestone of a project was paid
+	fn ensure_milestones_paid(project: ProjectID) -> Result<(), DispatchError> {
+		ensure!(<Milestones<T>>::get(project).iter().all(|milestone| milestone.state == MilestoneState::Paid),
+			Error::<T>::InvalidMilestoneState
+		);
+		Ok(())
+	}
+
+	/// Report the completion of a running project and open its confirmation round. If the
+	/// community cannot confirm completions, the project is escalated to the council right away.
+	fn do_report_completion(proposer: IdentityId<T>, project: ProjectID, evidence: ProposalCID)
+		-> Result<(), DispatchError>
+	{
+		let mut document = Self::project_in_state(project, &[ProjectState::Running])?;
+		ensure!(document.proposal.proposer == proposer, Error::<T>::NotTheProposer);
+		Self::ensure_milestones_paid(project)?;
+		Self::deposit_event(Event::<T>::CompletionReported(project, evidence));
+
+		if T::MilestoneConfirmation::open_confirmation(project, COMPLETION_MILESTONE, evidence).is_ok() {
+			document.state = ProjectState::CompletionReported;
+		} else {
+			document.state = ProjectState::Escalated;
+			Self::deposit_event(Event::<T>::CompletionEscalated(project));
+		}
+
+		<ProjectStorage<T>>::insert(project, document);
+		Ok(())
+	}
+
+	/// Complete a project in one of the states, pay the remaining budget to the proposer and
+	/// notify OnCompleted
+	fn do_complete(project: ProjectID, states: &[ProjectState]) -> Result<(), DispatchError> {
+		let mut document = Self::project_in_state(project, states)?;
+		Self::ensure_milestones_paid(project)?;
+		let remaining: BalanceOf<T> = document.budget.saturating_sub(Self::paid_milestones(project));
+		let proposer: T::AccountId = T::Identity::get_address(&document.proposal.proposer);
+		T::Currency::repatriate_reserved(&Self::treasury_account(), &proposer, remaining, BalanceStatus::Free)?;
+		document.state = ProjectState::Completed;
+		<ProjectStorage<T>>::insert(project, &document);
+		T::OnCompleted::on_project_completed(&document.proposal.proposer, document.budget);
+		Self::deposit_event(Event::<T>::ProjectCompleted(project, remaining));
+		Ok(())
+	}
+
+	/// Abort a project that is not finished yet and release the budget in the treasury.
+	/// If `proposer` is set, it must be the proposer of the project.
+	fn do_abort(proposer: Option<IdentityId<T>>, project: ProjectID) -> Result<(), DispatchError> {
+		let mut document = Self::project_in_state(project,
+			&[ProjectState::Spawned, ProjectState::Running, ProjectState::Escalated]
+		)?;
+		ensure!(proposer.map_or(true, |p| p == document.proposal.proposer), Error::<T>::NotTheProposer);
+		let remaining: BalanceOf<T> = document.budget.saturating_sub(Self::paid_milestones(project));
+		T::Currency::unreserve(&Self::treasury_account(), remaining);
+		document.state = ProjectState::Aborted;
+		<ProjectStorage<T>>::insert(project, &document);
+		Self::deposit_event(Event::<T>::ProjectAborted(project, remaining));
+		Ok(())
+	}
+
 	/// As an identified user, apply as project leader
 	fn do_application_project_leader(_who: IdentityId<T>, _project: ProjectID, _application: DocumentCID)
 		-> Result<(), DispatchError>
@@ -218,9 +612,9 @@ impl<T: Trait> ProjectTrait for Module<T> {
 	type ProposalWinner = ProposalWinner<IdentityId<T>>;
 	type Project = Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>;
 
-	/// As root, spawn a project from a proposal
-	fn spawn_project(proposal: Self::ProposalWinner) -> Result<Self::Project, DispatchError> {
-		Self::do_spawn_project(proposal)
+	/// Spawn a project from an approved proposal and reserve its budget in the treasury
+	fn spawn_project(proposal: Self::ProposalWinner, budget: Self::Balance) -> Result<Self::Project, DispatchError> {
+		Self::do_spawn_project(proposal, budget)
 	}
 
 	/// As an identified user, apply as project leader
@@ -281,4 +675,9 @@ impl<T: Trait> ProjectTrait for Module<T> {
 	fn get_projects() -> Result<Vec<Self::Project>, DispatchError> {
 		Self::do_get_projects()
 	}
+
+	/// Free balance of the treasury, the existential deposit is kept
+	fn available_budget() -> Self::Balance {
+		T::Currency::free_balance(&Self::treasury_account()).saturating_sub(T::Currency::minimum_balance())
+	}
 }
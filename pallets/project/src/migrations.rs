@@ -0,0 +1,105 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the project pallet
+
+use codec::{Codec, Decode, Encode, EncodeLike};
+use frame_support::{
+	dispatch::{fmt::Debug, Vec},
+	sp_std::cell::Cell,
+	traits::Get,
+	weights::Weight,
+	IterableStorageMap, StorageValue,
+};
+use pallet_proposal_types::deprecated;
+use crate::*;
+use crate::types::Worker;
+
+/// Project as it was stored before CIDs had a fixed length
+#[derive(Decode, Encode)]
+struct LegacyProject<Balance, BlockNumber, IdentityId> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
+{
+	id: ProjectID,
+	proposal: deprecated::ProposalWinner<IdentityId>,
+	project_leader: Option<Worker<Balance, BlockNumber, IdentityId>>,
+	open_positions: Vec<DocumentCID>,
+	workers: Vec<Worker<Balance, BlockNumber, IdentityId>>,
+	deadline: BlockNumber,
+}
+
+/// Project as it was stored before projects had a budget and a lifecycle state
+#[derive(Decode, Encode)]
+struct ProjectWithoutBudget<Balance, BlockNumber, IdentityId> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
+{
+	id: ProjectID,
+	proposal: ProposalWinner<IdentityId>,
+	project_leader: Option<Worker<Balance, BlockNumber, IdentityId>>,
+	open_positions: Vec<DocumentCID>,
+	workers: Vec<Worker<Balance, BlockNumber, IdentityId>>,
+	deadline: BlockNumber,
+}
+
+/// Apply all migrations that are required to reach the current storage layout.
+/// Projects spawned before the lifecycle existed have no budget and remain Spawned.
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+	let version: Releases = StorageVersion::get();
+
+	if version == Releases::V3ProjectLifecycle {
+		return weight;
+	}
+
+	// translate requires a Fn closure, hence the Cell
+	let translated: Cell<u64> = Cell::new(0);
+
+	if version == Releases::V1VecCid {
+		<ProjectStorage<T>>::translate::<LegacyProject<BalanceOf<T>, T::BlockNumber, IdentityId<T>>, _>(|_, old| {
+			translated.set(translated.get() + 1);
+			Some(Project {
+				id: old.id,
+				proposal: old.proposal.into(),
+				project_leader: old.project_leader,
+				open_positions: old.open_positions,
+				workers: old.workers,
+				deadline: old.deadline,
+				budget: Default::default(),
+				state: ProjectState::Spawned,
+			})
+		});
+	} else {
+		<ProjectStorage<T>>::translate::<ProjectWithoutBudget<BalanceOf<T>, T::BlockNumber, IdentityId<T>>, _>(|_, old| {
+			translated.set(translated.get() + 1);
+			Some(Project {
+				id: old.id,
+				proposal: old.proposal,
+				project_leader: old.project_leader,
+				open_positions: old.open_positions,
+				workers: old.workers,
+				deadline: old.deadline,
+				budget: Default::default(),
+				state: ProjectState::Spawned,
+			})
+		});
+	}
+
+	StorageVersion::put(Releases::V3ProjectLifecycle);
+	let entries: u64 = translated.get();
+	weight.saturating_add(T::DbWeight::get().reads_writes(entries, entries + 1))
+}
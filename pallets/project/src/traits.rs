@@ -12,8 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use frame_support::dispatch::{Codec, DispatchError, EncodeLike, fmt::Debug, Vec};
-use crate::types::{DocumentCID, ProjectID};
+use frame_support::{dispatch::{Codec, DispatchError, EncodeLike, fmt::Debug, Vec}, sp_runtime::traits::AtLeast32BitUnsigned};
+use crate::types::{DocumentCID, MilestoneIndex, ProjectID, ProposalCID};
+
+/// Notified when a project was completed and its final payout released
+pub trait OnProjectCompleted<IdentityId, Balance> {
+	/// The project of the proposer with the budget was completed
+	fn on_project_completed(proposer: &IdentityId, budget: Balance);
+}
+
+impl<IdentityId, Balance> OnProjectCompleted<IdentityId, Balance> for () {
+	fn on_project_completed(_proposer: &IdentityId, _budget: Balance) {}
+}
 
 /// Beginning of the project trait definition.
 /// A project is spawned from a proposal and the concerns.
@@ -29,13 +39,14 @@ use crate::types::{DocumentCID, ProjectID};
 /// for a new project leader is opened again.
 pub trait ProjectTrait
 {
-	type Balance: Codec + Clone + Debug + Eq + PartialEq;
+	/// Balance of the asset the project budgets are denominated in
+	type Balance: AtLeast32BitUnsigned + Codec + Copy + Debug;
 	type IdentityId: Codec + Clone + Eq + EncodeLike + Debug;
 	type ProposalWinner: Codec + Clone + Eq + Debug + PartialEq;
 	type Project: Codec + Clone + Debug + Eq + PartialEq;
 
-	/// As root, spawn a project from a proposal
-	fn spawn_project(proposal: Self::ProposalWinner) -> Result<Self::Project, DispatchError>;
+	/// Spawn a project from an approved proposal and reserve its budget in the treasury
+	fn spawn_project(proposal: Self::ProposalWinner, budget: Self::Balance) -> Result<Self::Project, DispatchError>;
 	/// As an identified user, apply as project leader
 	fn application_project_leader(who: Self::IdentityId, project: ProjectID, application: DocumentCID)
 		-> Result<(), DispatchError>;
@@ -61,4 +72,29 @@ pub trait ProjectTrait
 	fn get_project(project: ProjectID) -> Result<Option<Self::Project>, DispatchError>;
 	/// Get all projects
 	fn get_projects() -> Result<Vec<Self::Project>, DispatchError>;
+	/// Funds of the treasury that are available for new project budgets
+	fn available_budget() -> Self::Balance;
+}
+
+/// Lets the identified users confirm the milestones of projects in a confirmation round
+pub trait MilestoneConfirmation {
+	/// Open a confirmation round for a claimed milestone
+	fn open_confirmation(project: ProjectID, milestone: MilestoneIndex, evidence: ProposalCID)
+		-> Result<(), DispatchError>;
+	/// Close the confirmation round of a milestone if it is finished and return whether the
+	/// milestone was confirmed. Returns None while the round is running or if it does not exist.
+	fn conclude_confirmation(project: ProjectID, milestone: MilestoneIndex) -> Option<bool>;
+}
+
+/// Runtimes without community confirmation only support milestones that the council approves
+impl MilestoneConfirmation for () {
+	fn open_confirmation(_project: ProjectID, _milestone: MilestoneIndex, _evidence: ProposalCID)
+		-> Result<(), DispatchError>
+	{
+		Err(DispatchError::Other("Milestones cannot be confirmed by the community"))
+	}
+
+	fn conclude_confirmation(_project: ProjectID, _milestone: MilestoneIndex) -> Option<bool> {
+		None
+	}
 }
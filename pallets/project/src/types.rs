@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use frame_support::dispatch::{Codec, Decode, Encode, EncodeLike, fmt::Debug, Vec};
+pub use pallet_proposal_types::{ConcernCID, ProposalCID};
 use pallet_proposal_types::ProposalWinner;
 #[cfg(feature = "std")]
 use frame_support::serde::{Deserialize, Serialize};
@@ -20,9 +21,11 @@ use frame_support::serde::{Deserialize, Serialize};
 
 // Important: Change Vec<u8> to a fixed length type (otherwise attackable)
 pub type DocumentCID = Vec<u8>;
-pub type ProposalCID = Vec<u8>;
-pub type ConcernCID = ProposalCID;
 pub type ProjectID = u64;
+/// Index of a milestone within its project
+pub type MilestoneIndex = u32;
+/// Milestone index of the confirmation round that verifies the completion of a project
+pub const COMPLETION_MILESTONE: MilestoneIndex = MilestoneIndex::max_value();
 
 /// Contains all relevant information regarding a worker
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
@@ -61,6 +64,71 @@ impl<Balance, BlockNumber, IdentityId> Default for Worker<Balance, BlockNumber,
 }
 
 
+/// Lifecycle of a project
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProjectState {
+	/// The council approved the proposal and the budget is reserved in the treasury
+	Spawned,
+	/// The proposer started to work on the project
+	Running,
+	/// The council confirmed the completion, the budget was paid to the proposer
+	Completed,
+	/// The project was aborted, the budget was released in the treasury
+	Aborted,
+	/// The proposer reported the completion, the community verifies it
+	CompletionReported,
+	/// The community disputed the completion, the council decides
+	Escalated,
+}
+
+impl Default for ProjectState {
+	fn default() -> Self {
+		ProjectState::Spawned
+	}
+}
+
+/// Who has to approve the payout of a milestone
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ApprovalMethod {
+	/// The council approves the payout
+	Council,
+	/// The identified users confirm the milestone in a confirmation round
+	Community,
+}
+
+/// Payout state of a milestone
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MilestoneState {
+	/// The milestone was not reached yet or its claim was rejected
+	Pending,
+	/// The proposer claims that the milestone was reached, contains the digest of the evidence CID
+	Claimed(ProposalCID),
+	/// The amount of the milestone was paid to the proposer
+	Paid,
+}
+
+/// Share of the budget of a project that is paid once the milestone is approved
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Milestone<Balance> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub amount: Balance,
+	pub approval: ApprovalMethod,
+	pub state: MilestoneState,
+}
+
+impl<Balance> Milestone<Balance> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub fn new(amount: Balance, approval: ApprovalMethod) -> Self {
+		Milestone{amount, approval, state: MilestoneState::Pending}
+	}
+}
+
 /// Contains all relevant information for a project
 #[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -75,6 +143,9 @@ pub struct Project<Balance, BlockNumber, IdentityId> where
 	pub open_positions: Vec<DocumentCID>,
 	pub workers: Vec<Worker<Balance, BlockNumber, IdentityId>>, // Maybe HashMap?
 	pub deadline: BlockNumber,
+	/// Budget that is reserved in the treasury and paid to the proposer on completion
+	pub budget: Balance,
+	pub state: ProjectState,
 }
 
 impl<Balance, BlockNumber, IdentityId> Project<Balance, BlockNumber, IdentityId> where
@@ -82,9 +153,9 @@ impl<Balance, BlockNumber, IdentityId> Project<Balance, BlockNumber, IdentityId>
 	BlockNumber: Codec + Clone + Debug + Default + Eq + PartialEq,
 	IdentityId: Codec + Clone + Debug + Default + Eq + EncodeLike,
 {
-	pub fn new(id: ProjectID, proposal: ProposalWinner<IdentityId>) -> Self {
+	pub fn new(id: ProjectID, proposal: ProposalWinner<IdentityId>, budget: Balance, deadline: BlockNumber) -> Self {
 		Project{id, proposal, project_leader: None, workers: Default::default(),
-				open_positions: Default::default(), deadline: Default::default()}
+				open_positions: Default::default(), deadline, budget, state: ProjectState::Spawned}
 	}
 }
 
@@ -94,6 +165,6 @@ impl<Balance, BlockNumber, IdentityId> Default for Project<Balance, BlockNumber,
 	IdentityId: Codec + Clone + Debug + Default + Eq + EncodeLike,
 {
 	fn default() -> Self {
-		Project::new(Default::default(), Default::default())
+		Project::new(Default::default(), Default::default(), Default::default(), Default::default())
 	}
 }
@@ -0,0 +1,85 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # pallet-proposal-rpc-runtime-api
+//! Runtime API definition to query governance data from the proposal pallet
+
+use codec::{Codec, EncodeLike};
+use sp_std::{fmt::Debug, vec::Vec};
+pub use pallet_proposal_types::{Concern, Cursor, Eligibility, ErrorExplanation, GovernanceHistory, LanguageCode,
+	Page, Proposal, ProposalCID, ProposalMetadata, ProposalWinner, ProposerInfo, RoundIndex, RoundResult, RoundSnapshot, States, VoteReceipt};
+
+sp_api::decl_runtime_apis! {
+	/// Query governance data from the proposal pallet
+	pub trait ProposalApi<IdentityId, BlockNumber, AccountId> where
+		IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+		BlockNumber: Codec + Clone + Eq + Debug,
+		AccountId: Codec,
+	{
+		/// Get the current state of the state machine
+		fn current_state() -> States;
+
+		/// Get the index of the running round
+		fn current_round() -> RoundIndex;
+
+		/// Get the block in which the next state transit is scheduled
+		fn next_transit() -> BlockNumber;
+
+		/// Get one page of the proposals of the current round, ordered by submission.
+		/// Pass the cursor of the previous page to fetch the next page.
+		fn active_proposals(start: Option<Cursor>, limit: u32) -> Page<(IdentityId, Proposal)>;
+
+		/// Get one page of the concerns of the current round, ordered by submission.
+		/// Pass the cursor of the previous page to fetch the next page.
+		fn active_concerns(start: Option<Cursor>, limit: u32) -> Page<(IdentityId, Concern)>;
+
+		/// Get the winning proposals of a round (only the last HistoryDepth rounds are available)
+		fn winners(round: RoundIndex) -> Vec<ProposalWinner<IdentityId>>;
+
+		/// Determine the actions the identity of an account can perform in the current phase
+		fn voter_eligibility(account: AccountId) -> Eligibility;
+
+		/// Get the aggregated governance participation of an identity
+		fn history_of(identity: IdentityId) -> GovernanceHistory;
+
+		/// Get the identity level, display handle hash and contact document of a proposer
+		fn proposer_info(identity: IdentityId) -> ProposerInfo;
+
+		/// Get one page of the concerns that were filed against the proposals of an identity
+		/// during this round. Pass the cursor of the previous page to fetch the next page.
+		fn open_concerns_of(identity: IdentityId, start: Option<Cursor>, limit: u32) -> Page<Concern>;
+
+		/// Get the outcome of a finished round (only the last HistoryDepth rounds are available)
+		fn round_result(round: RoundIndex) -> RoundResult<IdentityId>;
+
+		/// Resolve a proposal digest to its proposer, round and submission index
+		fn proposal_by_cid(proposal: ProposalCID) -> Option<(IdentityId, Cursor)>;
+
+		/// Get the explanation of a runtime error (DispatchError::Module index and error) in a language
+		fn error_explanation(module: u8, error: u8, language: LanguageCode) -> Option<ErrorExplanation>;
+
+		/// Get the votes an identity cast during a round (only the last HistoryDepth rounds are available)
+		fn vote_receipt(identity: IdentityId, round: RoundIndex) -> Option<VoteReceipt>;
+
+		/// Was the vote of an identity for a proposal counted in a round?
+		/// (only the last HistoryDepth rounds are available)
+		fn verify_vote(identity: IdentityId, round: RoundIndex, proposal: ProposalCID) -> bool;
+
+		/// Get an overview of the current round. If an identity is supplied, the view contains
+		/// the actions the identity can perform in the current phase.
+		fn round_snapshot(identity: Option<IdentityId>) -> RoundSnapshot<IdentityId, BlockNumber>;
+	}
+}
@@ -0,0 +1,237 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # pallet-proposal-rpc
+//! JSON-RPC interface to query proposals and round summaries without decoding SCALE encoded storage
+
+use std::{fmt::Debug, sync::Arc};
+
+use codec::{Codec, EncodeLike};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_arithmetic::Permill;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+pub use pallet_proposal_rpc_runtime_api::ProposalApi as ProposalRuntimeApi;
+use pallet_proposal_rpc_runtime_api::{Cursor, Proposal, ProposalMetadata, ProposalWinner, RoundIndex, RoundResult, States};
+
+/// Error code of failed runtime API calls
+const RUNTIME_ERROR: i64 = 1;
+/// Error code of queries for data that is not available (anymore)
+const NOT_AVAILABLE: i64 = 2;
+
+/// A proposal with its proposer
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalInfo<IdentityId> {
+	/// Identity that submitted the proposal
+	pub proposer: IdentityId,
+	/// sha2-256 digest of the proposal CID
+	pub proposal: Bytes,
+	/// Number of votes the proposal received
+	pub votes: u32,
+	/// Submission index of the proposal within its round
+	pub index: u32,
+	/// Title hash, category, requested budget and discussion thread of the proposal
+	pub metadata: ProposalMetadata,
+}
+
+impl<IdentityId> From<(IdentityId, Proposal)> for ProposalInfo<IdentityId> {
+	fn from((proposer, proposal): (IdentityId, Proposal)) -> Self {
+		ProposalInfo {
+			proposer,
+			proposal: proposal.proposal.to_vec().into(),
+			votes: proposal.votes,
+			index: proposal.index,
+			metadata: proposal.metadata,
+		}
+	}
+}
+
+/// A winning proposal including the concerns that were accepted against it
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WinnerInfo<IdentityId> {
+	/// Identity that submitted the proposal
+	pub proposer: IdentityId,
+	/// sha2-256 digest of the proposal CID
+	pub proposal: Bytes,
+	/// Share of the votes the proposal received
+	pub vote_ratio: Permill,
+	/// sha2-256 digests of the accepted concern CIDs
+	pub concerns: Vec<Bytes>,
+}
+
+impl<IdentityId> From<ProposalWinner<IdentityId>> for WinnerInfo<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+{
+	fn from(winner: ProposalWinner<IdentityId>) -> Self {
+		WinnerInfo {
+			proposer: winner.proposer,
+			proposal: winner.proposal.to_vec().into(),
+			vote_ratio: winner.vote_ratio,
+			concerns: winner.concerns.iter().map(|concern| concern.to_vec().into()).collect(),
+		}
+	}
+}
+
+/// Summary of a round
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundSummary<IdentityId> {
+	/// Index of the round
+	pub round: RoundIndex,
+	/// Current state if the round is still running, None for finished rounds
+	pub state: Option<States>,
+	/// Number of submitted proposals
+	pub proposal_count: u32,
+	/// Number of votes for proposals
+	pub proposal_votes: u32,
+	/// Number of submitted concerns
+	pub concern_count: u32,
+	/// Number of votes for concerns
+	pub concern_votes: u32,
+	/// Winning proposals including the accepted concerns
+	pub winners: Vec<WinnerInfo<IdentityId>>,
+	/// Council decision for every winning proposal (true = project spawned)
+	pub council_decisions: Vec<(Bytes, bool)>,
+	/// Justification documents of the rejecting council members for every blocked proposal
+	pub council_justifications: Vec<(Bytes, Vec<(IdentityId, Bytes)>)>,
+}
+
+/// Query proposals and round summaries of the proposal pallet
+#[rpc]
+pub trait ProposalApi<BlockHash, IdentityId> {
+	/// Get up to `limit` proposals of the current round whose submission index is at
+	/// least `offset`, ordered by submission. Proposals of finished rounds are pruned.
+	#[rpc(name = "superorganism_proposals")]
+	fn proposals(
+		&self,
+		round: RoundIndex,
+		offset: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ProposalInfo<IdentityId>>>;
+
+	/// Get the summary of the current round or of a finished round
+	/// (only the last HistoryDepth rounds are available)
+	#[rpc(name = "superorganism_roundSummary")]
+	fn round_summary(&self, round: RoundIndex, at: Option<BlockHash>) -> Result<RoundSummary<IdentityId>>;
+}
+
+/// Implementation of ProposalApi that calls into the runtime.
+/// `M` is the tuple (Block, BlockNumber, AccountId) the runtime API is implemented for.
+pub struct Proposals<C, M> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> Proposals<C, M> {
+	/// Create new `Proposals` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Proposals { client, _marker: Default::default() }
+	}
+}
+
+/// Convert a failed runtime API call into an RPC error
+fn runtime_error(error: impl Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query the proposal pallet.".into(),
+		data: Some(format!("{:?}", error).into()),
+	}
+}
+
+impl<C, Block, IdentityId, BlockNumber, AccountId> ProposalApi<<Block as BlockT>::Hash, IdentityId>
+	for Proposals<C, (Block, BlockNumber, AccountId)>
+where
+	Block: BlockT,
+	C: 'static + Send + Sync + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ProposalRuntimeApi<Block, IdentityId, BlockNumber, AccountId>,
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug + Send + Sync + 'static,
+	BlockNumber: Codec + Clone + Eq + Debug + Send + Sync + 'static,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn proposals(
+		&self,
+		round: RoundIndex,
+		offset: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ProposalInfo<IdentityId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let current: RoundIndex = api.current_round(&at).map_err(runtime_error)?;
+
+		if round != current {
+			return Err(RpcError {
+				code: ErrorCode::ServerError(NOT_AVAILABLE),
+				message: "Only the proposals of the current round are available.".into(),
+				data: Some(format!("Current round: {}", current).into()),
+			});
+		}
+
+		// The page starts after the cursor, therefore the cursor points to the previous index
+		let start: Option<Cursor> = offset.checked_sub(1).map(|index| Cursor::new(round, index));
+		let page = api.active_proposals(&at, start, limit).map_err(runtime_error)?;
+		Ok(page.items.into_iter().map(Into::into).collect())
+	}
+
+	fn round_summary(
+		&self,
+		round: RoundIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<RoundSummary<IdentityId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let current: RoundIndex = api.current_round(&at).map_err(runtime_error)?;
+
+		if round > current {
+			return Err(RpcError {
+				code: ErrorCode::ServerError(NOT_AVAILABLE),
+				message: "The round did not start yet.".into(),
+				data: Some(format!("Current round: {}", current).into()),
+			});
+		}
+
+		let result: RoundResult<IdentityId> = api.round_result(&at, round).map_err(runtime_error)?;
+		let state: Option<States> = if round == current {
+			Some(api.current_state(&at).map_err(runtime_error)?)
+		} else {
+			None
+		};
+
+		Ok(RoundSummary {
+			round,
+			state,
+			proposal_count: result.proposal_count,
+			proposal_votes: result.proposal_votes,
+			concern_count: result.concern_count,
+			concern_votes: result.concern_votes,
+			winners: api.winners(&at, round).map_err(runtime_error)?.into_iter().map(Into::into).collect(),
+			council_decisions: result.council_decisions.into_iter()
+				.map(|(proposal, accepted)| (proposal.to_vec().into(), accepted))
+				.collect(),
+			council_justifications: result.council_justifications.into_iter()
+				.map(|(proposal, reasons)| (
+					proposal.to_vec().into(),
+					reasons.into_iter().map(|(member, document)| (member, document.to_vec().into())).collect(),
+				))
+				.collect(),
+		})
+	}
+}
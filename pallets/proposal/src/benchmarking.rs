@@ -0,0 +1,518 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the proposal pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{sp_runtime::traits::Bounded, sp_std::vec};
+use frame_system::RawOrigin;
+use crate::*;
+
+const SEED: u32 = 0;
+
+/// Binary CIDv0 (sha2-256 multihash) derived from a seed
+fn cid(kind: &[u8], seed: u32) -> Vec<u8> {
+	let mut cid: Vec<u8> = vec![0x12, 0x20];
+	cid.extend_from_slice(&sp_io::hashing::blake2_256(&(kind, seed).encode()));
+	cid
+}
+
+/// Digest of the binary CIDv0 derived from a seed
+fn digest(kind: &[u8], seed: u32) -> ProposalCID {
+	cid_digest(&cid(kind, seed)).expect("cid() creates valid CIDs; qed")
+}
+
+/// Unsigned attestation of a document. The signature is checked in validate_unsigned, which
+/// is not part of the dispatch, hence placeholder keys suffice.
+fn attestation<T: Trait>(document: DocumentCID) -> (DocumentPayload<T::Public>, T::Signature) {
+	let zeros: &[u8] = &[0u8; 128];
+	let public = T::Public::decode(&mut &zeros[..]).expect("Keys decode from zeros; qed");
+	let signature = T::Signature::decode(&mut &zeros[..]).expect("Signatures decode from zeros; qed");
+	(DocumentPayload { round: <Round>::get(), document, public }, signature)
+}
+
+/// Create an account that can afford every bond and deposit
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// Caller with the highest identity level, identities are not granted by default
+fn identified_caller<T: Trait>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Identity::set_identity_level(&T::Identity::get_identity_id(&caller), IdentityLevel::max_value());
+	caller
+}
+
+/// Submit `count` proposals from distinct proposers
+fn create_proposals<T: Trait>(count: u32) -> Vec<(IdentityId<T>, ProposalCID)> {
+	(0..count).map(|i| {
+		let proposer: T::AccountId = funded_account::<T>("proposer", i);
+		let id: IdentityId<T> = T::Identity::get_identity_id(&proposer);
+		let proposal: ProposalCID = digest(b"proposal", i);
+		Module::<T>::add_proposal(id.clone(), proposal, ProposalMetadata::default());
+		(id, proposal)
+	}).collect()
+}
+
+/// Submit a proposal from the caller and return its identity
+fn propose_as<T: Trait>(caller: &T::AccountId, proposal: ProposalCID) -> IdentityId<T> {
+	let id: IdentityId<T> = T::Identity::get_identity_id(caller);
+	let bond: BalanceOf<T> = T::ProposeBond::get();
+	let _ = T::Currency::reserve(caller, bond);
+	ProposalBonds::<T>::insert(&proposal, (caller.clone(), bond));
+	Module::<T>::add_proposal(id.clone(), proposal, ProposalMetadata::default());
+	id
+}
+
+/// Let the proposal win the proposal voting round
+fn make_winner<T: Trait>(proposer: IdentityId<T>, proposal: ProposalCID) {
+	let winner = ProposalWinner::<IdentityId<T>>::new(Vec::new(), proposer.clone(), proposal, Permill::from_percent(100));
+	ProposalWinners::<T>::insert(<Round>::get(), VecDeque::from(vec![winner]));
+	<ConcernNotificationsEnabled<T>>::insert(&proposer, true);
+}
+
+/// Submit a concern from the caller and return its identity
+fn concern_as<T: Trait>(caller: &T::AccountId, concern: ConcernCID, proposal: ProposalCID) -> IdentityId<T> {
+	let id: IdentityId<T> = T::Identity::get_identity_id(caller);
+	let deposit: BalanceOf<T> = T::UserConcernFee::get();
+	let _ = T::Currency::reserve(caller, deposit);
+	UserConcernDeposits::<T>::insert((&concern, &proposal), (caller.clone(), deposit));
+	Module::<T>::add_concern(id.clone(), concern, proposal);
+	id
+}
+
+benchmarks! {
+	_ { }
+
+	// Worst case: the proposal limit is reached and the state machine transits
+	propose {
+		create_proposals::<T>(T::ProposeCap::get().saturating_sub(1));
+		let caller: T::AccountId = identified_caller::<T>();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		<State>::put(States::Propose);
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"caller", 0))
+	verify {
+		assert_eq!(<ProposalToIdentity<T>>::get(&digest(b"caller", 0)), T::Identity::get_identity_id(&caller));
+	}
+
+	// Worst case: the proposal limit is reached and every metadata field is set
+	propose_with_metadata {
+		create_proposals::<T>(T::ProposeCap::get().saturating_sub(1));
+		let caller: T::AccountId = identified_caller::<T>();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let category: Option<CategoryId> = T::ProposalCategories::get().checked_sub(1);
+		<State>::put(States::Propose);
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"caller", 0), Some([1u8; 32]), category,
+		Some(T::MaxRequestedBudget::get()), Some(cid(b"discussion", 0)))
+	verify {
+		assert_eq!(<ProposalToIdentity<T>>::get(&digest(b"caller", 0)), T::Identity::get_identity_id(&caller));
+	}
+
+	withdraw_proposal {
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let proposal: ProposalCID = digest(b"caller", 0);
+		propose_as::<T>(&caller, proposal);
+		<State>::put(States::Propose);
+	}: _(RawOrigin::Signed(caller), proposal)
+	verify {
+		assert!(<ProposalByCid<T>>::get(&proposal).is_none());
+	}
+
+	amend_proposal {
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let old_proposal: ProposalCID = digest(b"caller", 0);
+		propose_as::<T>(&caller, old_proposal);
+		<State>::put(States::Propose);
+	}: _(RawOrigin::Signed(caller.clone()), old_proposal, cid(b"caller", 1))
+	verify {
+		assert_eq!(<ProposalToIdentity<T>>::get(&digest(b"caller", 1)), T::Identity::get_identity_id(&caller));
+	}
+
+	remove_proposal {
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let proposal: ProposalCID = digest(b"caller", 0);
+		propose_as::<T>(&caller, proposal);
+		<State>::put(States::VotePropose);
+	}: _(T::CouncilOrigin::successful_origin(), proposal)
+	verify {
+		assert!(<ProposalByCid<T>>::get(&proposal).is_none());
+	}
+
+	punish_proposer {
+		let p in 0 .. T::ProposeIdentifiedUserCap::get() as u32 * T::ReputationCapMultiplier::get() as u32;
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		for i in 0 .. p {
+			propose_as::<T>(&caller, digest(b"caller", i));
+		}
+		<State>::put(States::VotePropose);
+		let identity: IdentityId<T> = T::Identity::get_identity_id(&caller);
+	}: _(T::CouncilOrigin::successful_origin(), identity.clone(), digest(b"reason", 0))
+	verify {
+		assert!(<PenalizedUntil<T>>::get(&identity).is_some());
+	}
+
+	impose_penalty {
+		let identity: IdentityId<T> = T::Identity::get_identity_id(&account("penalized", 0, SEED));
+	}: _(T::CouncilOrigin::successful_origin(), identity.clone())
+	verify {
+		assert!(<PenalizedUntil<T>>::get(&identity).is_some());
+	}
+
+	lift_penalty {
+		let identity: IdentityId<T> = T::Identity::get_identity_id(&account("penalized", 0, SEED));
+		<PenalizedUntil<T>>::insert(&identity, T::BlockNumber::max_value());
+	}: _(T::CouncilOrigin::successful_origin(), identity.clone())
+	verify {
+		assert!(<PenalizedUntil<T>>::get(&identity).is_none());
+	}
+
+	// Worst case: the proposer of the concerned proposal is notified
+	concern {
+		let proposer: T::AccountId = funded_account::<T>("proposer", 0);
+		let proposal: ProposalCID = digest(b"proposal", 0);
+		make_winner::<T>(propose_as::<T>(&proposer, proposal), proposal);
+		let caller: T::AccountId = identified_caller::<T>();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		<State>::put(States::Concern);
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"caller", 0), proposal)
+	verify {
+		assert_eq!(<ConcernToIdentity<T>>::get((&digest(b"caller", 0), &proposal)),
+			T::Identity::get_identity_id(&caller));
+	}
+
+	withdraw_concern {
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let (concern, proposal) = (digest(b"caller", 0), digest(b"proposal", 0));
+		concern_as::<T>(&caller, concern, proposal);
+		<State>::put(States::Concern);
+	}: _(RawOrigin::Signed(caller), concern, proposal)
+	verify {
+		assert_eq!(<ConcernToIdentity<T>>::get((&concern, &proposal)), IdentityId::<T>::default());
+	}
+
+	remove_concern {
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let (concern, proposal) = (digest(b"caller", 0), digest(b"proposal", 0));
+		concern_as::<T>(&caller, concern, proposal);
+		<State>::put(States::VoteConcern);
+	}: _(T::CouncilOrigin::successful_origin(), concern, proposal)
+	verify {
+		assert_eq!(<ConcernToIdentity<T>>::get((&concern, &proposal)), IdentityId::<T>::default());
+	}
+
+	set_concern_notifications {
+		let caller: T::AccountId = identified_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), true)
+	verify {
+		assert!(<ConcernNotificationsEnabled<T>>::get(&T::Identity::get_identity_id(&caller)));
+	}
+
+	// Worst case: the caller already cast every vote but the last one
+	vote_proposal {
+		let proposals = create_proposals::<T>(T::ProposeVoteMaxPerIdentifiedUser::get().max(1) as u32);
+		let proposal: ProposalCID = proposals[0].1;
+		let caller: T::AccountId = identified_caller::<T>();
+		let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+
+		for (proposer, other) in proposals.iter().skip(1) {
+			Module::<T>::add_vote_proposal(id.clone(), *other, proposer.clone());
+		}
+
+		<VoiceCredits<T>>::insert(&id, (<CreditEpoch>::get(), u32::max_value()));
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Signed(caller), proposal)
+	verify {
+		assert_eq!(<ProposalVotes<T>>::get(<Round>::get(), &id).len(),
+			T::ProposeVoteMaxPerIdentifiedUser::get().max(1) as usize);
+	}
+
+	// Worst case: the caller already cast every vote but the last one
+	vote_concern {
+		let concerner: T::AccountId = funded_account::<T>("concerner", 0);
+		let proposal: ProposalCID = digest(b"proposal", 0);
+		let concerns: Vec<(IdentityId<T>, ConcernCID)> = (0..T::ConcernVoteMaxPerIdentifiedUser::get().max(1) as u32)
+			.map(|i| (concern_as::<T>(&concerner, digest(b"concern", i), proposal), digest(b"concern", i)))
+			.collect();
+		let concern: ConcernCID = concerns[0].1;
+		let caller: T::AccountId = identified_caller::<T>();
+		let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+
+		for (submitter, other) in concerns.iter().skip(1) {
+			Module::<T>::add_vote_concern(id.clone(), *other, proposal, submitter.clone());
+		}
+
+		<VoiceCredits<T>>::insert(&id, (<CreditEpoch>::get(), u32::max_value()));
+		<State>::put(States::VoteConcern);
+	}: _(RawOrigin::Signed(caller), concern, proposal)
+	verify {
+		assert_eq!(<ConcernVotes<T>>::get(<Round>::get(), &id).len(),
+			T::ConcernVoteMaxPerIdentifiedUser::get().max(1) as usize);
+	}
+
+	pause {
+		<State>::put(States::Propose);
+		Module::<T>::schedule_state_transit(T::ProposeRoundDuration::get(), States::Propose)?;
+	}: _(T::CouncilOrigin::successful_origin())
+	verify {
+		assert!(<Paused>::get());
+	}
+
+	resume {
+		<State>::put(States::Propose);
+		Paused::put(true);
+	}: _(T::CouncilOrigin::successful_origin())
+	verify {
+		assert!(!<Paused>::get());
+	}
+
+	// The evaluation of the proposal voting round is the most expensive state transit
+	state_transit {
+		let p in 1 .. T::ProposeCap::get().max(1);
+
+		for (proposer, proposal) in create_proposals::<T>(p) {
+			let voter: T::AccountId = account("voter", p, SEED);
+			Module::<T>::add_vote_proposal(T::Identity::get_identity_id(&voter), proposal, proposer);
+		}
+
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Root)
+	verify {
+		assert_ne!(<State>::get(), States::VotePropose);
+	}
+
+	distribute_rewards {
+		let r in 1 .. T::RewardBatchSize::get().max(1);
+
+		for i in 0..r {
+			let account: T::AccountId = funded_account::<T>("rewarded", i);
+			Module::<T>::accrue_reward(&account, T::ProposeReward::get());
+		}
+	}: _(RawOrigin::Root)
+	verify {
+		assert!(<PendingRewards<T>>::iter().next().is_none());
+	}
+
+	set_error_explanation {
+		let m in 0 .. T::MaxErrorExplanationLength::get();
+		let message: Vec<u8> = vec![b'x'; m as usize];
+		<ErrorExplanations>::insert((0u8, 0u8, *b"en"), ErrorExplanation::new(1, Vec::new()));
+	}: _(T::CouncilOrigin::successful_origin(), 0, 0, *b"en", message)
+	verify {
+		assert_eq!(<ErrorExplanations>::get((0u8, 0u8, *b"en")).map(|e| e.version), Some(2));
+	}
+
+	remove_error_explanation {
+		let message: Vec<u8> = vec![b'x'; T::MaxErrorExplanationLength::get() as usize];
+		<ErrorExplanations>::insert((0u8, 0u8, *b"en"), ErrorExplanation::new(1, message));
+	}: _(T::CouncilOrigin::successful_origin(), 0, 0, *b"en")
+	verify {
+		assert!(<ErrorExplanations>::get((0u8, 0u8, *b"en")).is_none());
+	}
+
+	attest_availability {
+		let proposal: ProposalCID = create_proposals::<T>(1)[0].1;
+		<State>::put(States::Propose);
+		let (payload, signature) = attestation::<T>(proposal);
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert!(<ProposalAvailability<T>>::contains_key(&proposal));
+	}
+
+	// Worst case: the document is the last accepted concern of the last winner
+	confirm_pin {
+		let proposal: ProposalCID = digest(b"proposal", 0);
+		let concerns: Vec<ConcernCID> = (0..T::ConcernVoteMaxPerIdentifiedUser::get().max(1) as u32)
+			.map(|i| digest(b"concern", i))
+			.collect();
+		let document: DocumentCID = *concerns.last().expect("At least one concern is created; qed");
+		let winner = ProposalWinner::<IdentityId<T>>::new(concerns, IdentityId::<T>::default(),
+			proposal, Permill::from_percent(100));
+		ProposalWinners::<T>::insert(<Round>::get(), VecDeque::from(vec![winner]));
+		let (payload, signature) = attestation::<T>(document);
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert!(<PinnedDocuments<T>>::contains_key(&document));
+	}
+
+	set_offchain_authorities {
+		let a in 1 .. 100;
+		let authorities: Vec<T::AccountId> = (0..a).map(|i| account("authority", i, SEED)).collect();
+	}: _(T::CouncilOrigin::successful_origin(), authorities.clone())
+	verify {
+		assert_eq!(Module::<T>::offchain_authorities(), authorities);
+	}
+
+	set_phase_duration {
+		let duration: T::BlockNumber = T::BlockNumber::from(100u32);
+	}: _(T::CouncilOrigin::successful_origin(), States::Propose, Some(duration))
+	verify {
+		assert_eq!(Module::<T>::phase_duration(States::Propose), duration);
+	}
+
+	set_submission_cap {
+		let cap: u32 = T::ProposeCap::get().max(1);
+	}: _(T::CouncilOrigin::successful_origin(), States::Propose, Some(cap))
+	verify {
+		assert_eq!(<SubmissionCaps>::get(States::Propose), Some(cap));
+	}
+
+	set_category_cap {
+		let cap: u32 = T::ProposeCap::get().max(1);
+	}: {
+		if T::ProposalCategories::get() > 0 {
+			Module::<T>::set_category_cap(T::CouncilOrigin::successful_origin(), 0, Some(cap))?;
+		}
+	}
+
+	set_acceptance_threshold {
+		let threshold: Permill = Permill::from_percent(50);
+	}: _(T::CouncilOrigin::successful_origin(), States::VotePropose, Some(threshold))
+	verify {
+		assert_eq!(Module::<T>::acceptance_threshold(States::VotePropose), threshold);
+	}
+
+	vote_with_conviction {
+		let proposal: ProposalCID = create_proposals::<T>(1)[0].1;
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let stake: BalanceOf<T> = T::ConvictionVoteUnit::get();
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Signed(caller.clone()), proposal, stake, Conviction::Locked6x)
+	verify {
+		assert_eq!(<ConvictionLocks<T>>::get(&caller).len(), 1);
+	}
+
+	// Worst case: a lock of every lock period, half of them expired
+	unlock {
+		let l in 1 .. Conviction::Locked6x.lock_rounds();
+		let caller: T::AccountId = funded_account::<T>("caller", 0);
+		let stake: BalanceOf<T> = T::ConvictionVoteUnit::get();
+		<ConvictionLocks<T>>::insert(&caller, (0..l).map(|r| (r, stake)).collect::<Vec<_>>());
+		<Round>::put(l / 2);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+
+	// Worst case: the delegate starts a chain of MaxDelegationDepth - 1 delegations
+	delegate {
+		let d in 1 .. T::MaxDelegationDepth::get().max(1);
+		let chain: Vec<IdentityId<T>> = (0..d).map(|i| account("delegate", i, SEED)).collect();
+
+		for pair in chain.windows(2) {
+			<Delegations<T>>::insert(&pair[0], &pair[1]);
+			<Delegators<T>>::mutate(&pair[1], |delegators| delegators.push(pair[0].clone()));
+		}
+
+		let caller: T::AccountId = identified_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), chain[0].clone())
+	verify {
+		assert_eq!(<Delegations<T>>::get(&T::Identity::get_identity_id(&caller)), Some(chain[0].clone()));
+	}
+
+	undelegate {
+		let caller: T::AccountId = identified_caller::<T>();
+		let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+		let delegate: IdentityId<T> = account("delegate", 0, SEED);
+		<Delegations<T>>::insert(&id, &delegate);
+		<Delegators<T>>::insert(&delegate, vec![id.clone()]);
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert!(<Delegations<T>>::get(&id).is_none());
+	}
+
+	vote_batch {
+		let v in 1 .. T::ProposeVoteMaxPerIdentifiedUser::get().max(1) as u32;
+		let approvals: Vec<ProposalCID> = create_proposals::<T>(v).into_iter().map(|(_, proposal)| proposal).collect();
+		let caller: T::AccountId = identified_caller::<T>();
+		let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+		<VoiceCredits<T>>::insert(&id, (<CreditEpoch>::get(), u32::max_value()));
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Signed(caller), approvals)
+	verify {
+		assert_eq!(<ProposalVotes<T>>::get(<Round>::get(), &id).len(), v as usize);
+	}
+
+	// Only RankedChoice runtimes accept ranked ballots
+	vote_ranked {
+		let c in 1 .. T::MaxRankedChoices::get().max(1);
+		let ranking: Vec<ProposalCID> = create_proposals::<T>(c).into_iter().map(|(_, proposal)| proposal).collect();
+		let caller: T::AccountId = identified_caller::<T>();
+		<State>::put(States::VotePropose);
+	}: {
+		if T::TallyAlgorithm::scheme() == VotingScheme::RankedChoice {
+			Module::<T>::vote_ranked(RawOrigin::Signed(caller.clone()).into(), ranking)?;
+		}
+	}
+
+	// Only runtimes with a RevealPeriod accept commitments
+	commit_votes {
+		let caller: T::AccountId = identified_caller::<T>();
+		let commitment: T::Hash = T::Hashing::hash_of(&(Vec::<ProposalCID>::new(), [0u8; 32]));
+		<State>::put(States::VotePropose);
+		<NextTransit<T>>::put(T::BlockNumber::max_value());
+	}: {
+		if T::RevealPeriod::get() > T::BlockNumber::from(0u32) {
+			Module::<T>::commit_votes(RawOrigin::Signed(caller.clone()).into(), commitment)?;
+		}
+	}
+
+	reveal_votes {
+		let v in 1 .. T::ProposeVoteMaxPerIdentifiedUser::get().max(1) as u32;
+		let votes: Vec<ProposalCID> = create_proposals::<T>(v).into_iter().map(|(_, proposal)| proposal).collect();
+		let salt: [u8; 32] = [1u8; 32];
+		let caller: T::AccountId = identified_caller::<T>();
+		let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+		<VoiceCredits<T>>::insert(&id, (<CreditEpoch>::get(), u32::max_value()));
+		<VoteCommitments<T>>::insert(<Round>::get(), &id, T::Hashing::hash_of(&(&votes, &salt)));
+		<State>::put(States::VotePropose);
+		<NextTransit<T>>::put(frame_system::Module::<T>::block_number());
+	}: {
+		if T::RevealPeriod::get() > T::BlockNumber::from(0u32) {
+			Module::<T>::reveal_votes(RawOrigin::Signed(caller.clone()).into(), votes, salt)?;
+		}
+	}
+
+	// Only runtimes with an AnonymityProvider accept anonymous ballots
+	vote_anonymous {
+		let v in 1 .. T::ProposeVoteMaxPerIdentifiedUser::get().max(1) as u32;
+		let votes: Vec<ProposalCID> = create_proposals::<T>(v).into_iter().map(|(_, proposal)| proposal).collect();
+		let nullifier: Nullifier = [1u8; 32];
+		let proof = T::AnonymityProvider::successful_proof(<Round>::get(), &nullifier, &votes);
+		<State>::put(States::VotePropose);
+	}: {
+		if T::AnonymityProvider::verify(<Round>::get(), &nullifier, &votes, &proof) {
+			Module::<T>::vote_anonymous(RawOrigin::None.into(), votes.clone(), nullifier, proof.clone())?;
+		}
+	}
+
+	vote_milestone {
+		let caller: T::AccountId = identified_caller::<T>();
+		let end: T::BlockNumber = T::BlockNumber::max_value();
+		<MilestoneConfirmations<T>>::insert(0, 0, (end, 0, 0));
+	}: _(RawOrigin::Signed(caller.clone()), 0, 0, true)
+	verify {
+		assert!(<MilestoneVoters<T>>::get((0, 0), &T::Identity::get_identity_id(&caller)));
+	}
+
+	// Worst case: a previously scheduled transit is cancelled
+	set_next_transit {
+		<State>::put(States::Propose);
+		Module::<T>::schedule_state_transit(T::ProposeRoundDuration::get(), States::Propose)?;
+		let when: T::BlockNumber = T::ProposeRoundDuration::get() + T::BlockNumber::from(1u32);
+	}: _(T::CouncilOrigin::successful_origin(), when)
+	verify {
+		assert_eq!(<NextTransit<T>>::get(), when);
+	}
+}
@@ -0,0 +1,233 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Default weights of the proposal pallet. Regenerate them with the benchmarks in
+//! benchmarking.rs on the reference hardware when the extrinsics change.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+impl crate::WeightInfo for () {
+	fn propose() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(10 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn propose_with_metadata() -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(12 as Weight))
+			.saturating_add(DbWeight::get().writes(10 as Weight))
+	}
+	fn withdraw_proposal() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn amend_proposal() -> Weight {
+		(53_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn remove_proposal() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn concern() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn withdraw_concern() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn remove_concern() -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn set_concern_notifications() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn vote_proposal() -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn vote_concern() -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn pause() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn resume() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn state_transit(p: u32) -> Weight {
+		(93_000_000 as Weight)
+			.saturating_add((2_400_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	fn distribute_rewards(r: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((41_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(r as Weight)))
+	}
+	fn set_error_explanation(m: u32) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_error_explanation() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn attest_availability() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn confirm_pin() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_offchain_authorities(a: u32) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((50_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_phase_duration() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_submission_cap() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_acceptance_threshold() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_next_transit() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn vote_ranked(c: u32) -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn vote_batch(v: u32) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((31_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn vote_with_conviction() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(11 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn unlock(l: u32) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((400_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn delegate(d: u32) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn commit_votes() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_votes(v: u32) -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add((31_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn vote_anonymous(v: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((18_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	fn set_category_cap() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn vote_milestone() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn punish_proposer(p: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((45_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((6 as Weight).saturating_mul(p as Weight)))
+	}
+	fn impose_penalty() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn lift_penalty() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -18,25 +18,43 @@
 //! Manages proposal and concern rounds as well as the correspondant voting rounds
 
 
-use frame_support::{decl_error, decl_module, decl_storage, decl_event, Parameter, ensure, /*print, debug,*/
-	dispatch::{Vec, DispatchResult, Dispatchable, DispatchError},
-	traits::{Get, Currency, ReservableCurrency,
-		schedule::{Anon, DispatchTime, LOWEST_PRIORITY},
+use frame_support::{decl_error, decl_module, decl_storage, decl_event, Parameter, RuntimeDebug, ensure, transactional, /*print, debug,*/
+	dispatch::{Vec, DispatchResult, Dispatchable},
+	traits::{EnsureOrigin, ExistenceRequirement, Get, Currency, LockIdentifier, LockableCurrency, ReservableCurrency,
+		WithdrawReasons,
+		schedule::{DispatchTime, Named, LOWEST_PRIORITY},
+	},
+	sp_runtime::{KeyTypeId, ModuleId,
+		traits::{AccountIdConversion, Hash, IdentifyAccount, SaturatedConversion, Saturating, ValidateUnsigned},
+		transaction_validity::{InvalidTransaction, TransactionPriority, TransactionSource,
+			TransactionValidity, ValidTransaction},
 	},
 	sp_std::collections::vec_deque::VecDeque,
-	//weights::Weight,
+	weights::{DispatchClass, Weight},
+};
+use frame_system::{ensure_none, ensure_root, ensure_signed, RawOrigin::Root,
+	offchain::{AppCrypto, SendTransactionTypes, SignedPayload, SigningTypes},
 };
-use frame_system::{ensure_root, ensure_signed, RawOrigin::Root};
 // use frame_system;
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 // Fixed point arithmetic
 use sp_arithmetic::Permill;
 // Identity pallet
 use pallet_community_identity::{ProofType, IdentityId, IdentityLevel, traits::PeerReviewedPhysicalIdentity};
 use pallet_council::{BlockNumber, DocumentCID, Ticket, traits::Council};
-use pallet_project::{types::{Project as ProjectType}, traits::ProjectTrait};
+use pallet_project::{types::{MilestoneIndex, ProjectID}, traits::{MilestoneConfirmation, OnProjectCompleted, ProjectTrait}};
+use pallet_reputation::{ReputationAction, Score, traits::Reputation};
 // Custom types
-use pallet_proposal_types::{Concern, ConcernCID, Proposal, ProposalCID, ProposalWinner, States};
+use pallet_proposal_types::{cid_digest, CategoryId, Concern, ConcernCID, Conviction, Cursor, Eligibility, ErrorExplanation, GovernanceHistory, LanguageCode,
+	Nullifier, Page, Proposal, ProposalCID, ProposalMetadata, ProposalWinner, ProposerInfo, RoundDigest, RoundIndex, RoundResult,
+	RoundSnapshot, States, VoteReceipt, VotingScheme};
+use crate::traits::{AnonymityProvider, TallyAlgorithm};
+mod benchmarking;
+mod default_weights;
+mod migrations;
+pub mod offchain;
+pub mod tally;
+pub mod traits;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -44,16 +62,175 @@ mod tests;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+/// Key type of the off-chain workers that attest the availability of proposals and pins
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ipfs");
+
+/// Keys the off-chain workers sign their attestations with. The node operator inserts an
+/// sr25519 key of the type "ipfs" into the keystore, the account of the key must be an
+/// off-chain authority.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use frame_support::sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Off-chain authority key of the runtime
+	pub struct AuthorityId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthorityId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sr25519::Signature;
+		type GenericPublic = sr25519::Public;
+	}
+}
+
+/// Attestation of an off-chain worker about a document, signed with its authority key
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub struct DocumentPayload<Public> {
+	/// Round the document belongs to
+	pub round: RoundIndex,
+	/// Attested proposal or concern
+	pub document: DocumentCID,
+	/// Authority key that signed the attestation
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for DocumentPayload<T::Public> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// Balance of the asset the project budgets are denominated in
+type BudgetOf<T> = <<T as Trait>::Project as ProjectTrait>::Balance;
+
+/// Result of paying out a batch of accrued rewards
+#[derive(Debug, Eq, PartialEq)]
+struct RewardPayout<AccountId, Balance> {
+	/// Rewards that were transferred
+	paid: Vec<(AccountId, Balance)>,
+	/// Rewards that could not be transferred
+	failed: Vec<(AccountId, Balance)>,
+	/// Available pot balance, if the pot could not cover the next reward of the batch
+	exhausted: Option<Balance>,
+}
+
+/// Transfer the rewards of a batch in order until the available pot balance cannot cover a reward
+fn pay_rewards<AccountId, Balance: Copy + PartialOrd>(
+	batch: Vec<(AccountId, Balance)>,
+	available: impl Fn() -> Balance,
+	mut transfer: impl FnMut(&AccountId, Balance) -> bool,
+) -> RewardPayout<AccountId, Balance> {
+	let mut payout = RewardPayout { paid: Vec::new(), failed: Vec::new(), exhausted: None };
+
+	for (account, reward) in batch {
+		let available: Balance = available();
+
+		if reward > available {
+			payout.exhausted = Some(available);
+			break;
+		}
+
+		if transfer(&account, reward) {
+			payout.paid.push((account, reward));
+		} else {
+			payout.failed.push((account, reward));
+		}
+	}
+
+	payout
+}
+
+/// Storage layout versions of this pallet. The declaration order defines the order of the
+/// versions, the explicit indices are the encoding that is stored on chain and must never change.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Releases {
+	/// The round is a u8 and the documents are keyed by the identity only. It predates the
+	/// storage version, therefore it is only used as default and encoded after the later versions.
+	#[codec(index = "255")]
+	V0U8Round,
+	/// CIDs are stored as Vec<u8>
+	#[codec(index = "0")]
+	V1VecCid,
+	/// CIDs are stored as fixed length digests
+	#[codec(index = "1")]
+	V2FixedCid,
+	/// Proposals carry optional metadata
+	#[codec(index = "2")]
+	V3ProposalMetadata,
+	/// Round results contain the justifications of rejecting council votes
+	#[codec(index = "3")]
+	V4CouncilJustifications,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0U8Round
+	}
+}
+
+/// Storage layout version of this runtime
+pub const CURRENT_RELEASE: Releases = Releases::V4CouncilJustifications;
+
+/// Weight functions of the extrinsics of this pallet
+pub trait WeightInfo {
+	fn propose() -> Weight;
+	fn propose_with_metadata() -> Weight;
+	fn withdraw_proposal() -> Weight;
+	fn amend_proposal() -> Weight;
+	fn remove_proposal() -> Weight;
+	fn concern() -> Weight;
+	fn withdraw_concern() -> Weight;
+	fn remove_concern() -> Weight;
+	fn set_concern_notifications() -> Weight;
+	fn vote_proposal() -> Weight;
+	fn vote_concern() -> Weight;
+	fn pause() -> Weight;
+	fn resume() -> Weight;
+	fn state_transit(p: u32) -> Weight;
+	fn distribute_rewards(r: u32) -> Weight;
+	fn set_error_explanation(m: u32) -> Weight;
+	fn remove_error_explanation() -> Weight;
+	fn attest_availability() -> Weight;
+	fn confirm_pin() -> Weight;
+	fn set_offchain_authorities(a: u32) -> Weight;
+	fn set_phase_duration() -> Weight;
+	fn set_submission_cap() -> Weight;
+	fn set_acceptance_threshold() -> Weight;
+	fn set_next_transit() -> Weight;
+	fn vote_ranked(c: u32) -> Weight;
+	fn vote_batch(v: u32) -> Weight;
+	fn vote_with_conviction() -> Weight;
+	fn unlock(l: u32) -> Weight;
+	fn delegate(d: u32) -> Weight;
+	fn undelegate() -> Weight;
+	fn commit_votes() -> Weight;
+	fn reveal_votes(v: u32) -> Weight;
+	fn vote_anonymous(v: u32) -> Weight;
+	fn set_category_cap() -> Weight;
+	fn vote_milestone() -> Weight;
+	fn punish_proposer(p: u32) -> Weight;
+	fn impose_penalty() -> Weight;
+	fn lift_penalty() -> Weight;
+}
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
-pub trait Trait: frame_system::Trait {
+pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> + SigningTypes {
 	// Type trait constraints
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
-	/// Type that manages balances
-	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Type that manages balances. Locks back conviction votes.
+	type Currency: ReservableCurrency<Self::AccountId>
+		+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 
-	/// Define the Scheduler type. Just implement (unamed) scheduling trait Anon
-	type Scheduler: Anon<Self::BlockNumber, Self::Proposal, Self::PalletsOrigin>;
+	/// Identifier of the governance pot. It pays the rewards and receives the slashed bonds
+	/// and deposits. Use the account that funds the projects to share one pot.
+	type TreasuryModuleId: Get<ModuleId>;
+
+	/// Define the Scheduler type. Must implement the named scheduling trait Named
+	type Scheduler: Named<Self::BlockNumber, Self::Proposal, Self::PalletsOrigin>;
 	type Proposal: Parameter + Dispatchable<Origin=Self::Origin> + From<Call<Self>>;
 	type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>> + Codec + Clone + Eq;
 
@@ -65,19 +242,58 @@ pub trait Trait: frame_system::Trait {
 	type Council: Council<IdentityId = IdentityId<Self>, DocumentCID=DocumentCID,
 							BlockNumber=BlockNumber<Self>, Ticket=Ticket>;
 
-	// Define Project type. Must implement ProjectTrait trait
-	type Project: ProjectTrait<Balance = BalanceOf<Self>, IdentityId = IdentityId<Self>,
-					ProposalWinner=ProposalWinner<IdentityId<Self>>,
-					Project=ProjectType<BalanceOf<Self>, Self::BlockNumber, IdentityId<Self>>>;
+	/// Registry that credits identities with reputation for voting and for accepted
+	/// proposals and concerns. () disables the reputation tracking
+	type Reputation: Reputation<IdentityId<Self>>;
+
+	// Define Project type. Must implement ProjectTrait trait. The budgets can be denominated
+	// in another asset than the bonds and rewards.
+	type Project: ProjectTrait<IdentityId = IdentityId<Self>, ProposalWinner=ProposalWinner<IdentityId<Self>>>;
+
+	/// Origin that is allowed to act on behalf of the council, e.g. to remove spam
+	type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Define the voting scheme that determines the winning proposals and concerns.
+	/// Must implement TallyAlgorithm trait
+	type TallyAlgorithm: TallyAlgorithm;
+
+	/// Define the verifier of anonymous ballots. Must implement AnonymityProvider trait,
+	/// () disables anonymous ballots
+	type AnonymityProvider: AnonymityProvider;
+
+	/// Weight information for the extrinsics of this pallet
+	type WeightInfo: WeightInfo;
 
 	// Parameters
 	/// How long is an identified user locked out from submitting proposals / concerns
-	/// for bad behaviour. Value in seconds.
-	type IdentifiedUserPenality: Get<u32>;
+	/// for bad behaviour. Value in blocks.
+	type IdentifiedUserPenality: Get<Self::BlockNumber>;
+
+	/// How long are the submission caps of an identity halved after its lockout expired?
+	/// Value in blocks.
+	type RecentPenaltyPeriod: Get<Self::BlockNumber>;
+
+	/// How much reputation raises the submission caps of an identity by another multiple of
+	/// ProposeIdentifiedUserCap / ConcernIdentifiedUserCap? 0 disables the reputation bonus
+	type ReputationCapStep: Get<Score>;
+
+	/// Up to which multiple of the submission caps can reputation raise them?
+	type ReputationCapMultiplier: Get<u8>;
 
 	/// Part 1.1: Proposal state configuration
-	// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
-	// type UserProposeFee: Get<BalanceOf<Self>>;
+	/// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
+	type UserProposeFee: Get<BalanceOf<Self>>;
+
+	/// How many funds must be reserved as a bond when submitting a proposal?
+	/// The bond is returned after the proposal voting round and slashed if the proposal is spam.
+	type ProposeBond: Get<BalanceOf<Self>>;
+
+	/// How much budget can the metadata of a proposal request at most?
+	type MaxRequestedBudget: Get<BudgetOf<Self>>;
+
+	/// How much budget can the winning proposals of a round request in total? The free balance
+	/// of the governance pot caps the budget as well.
+	type RoundBudget: Get<BudgetOf<Self>>;
 
 	/// How many proposals can be submitted per proposal round? (required for weight calculation)
 	type ProposeCap: Get<u32>;
@@ -88,6 +304,9 @@ pub trait Trait: frame_system::Trait {
 	/// Which identity level is required to create a proposal?
 	type ProposeIdentityLevel: Get<u8>;
 
+	/// How many proposal categories exist? Categories are numbered from 0.
+	type ProposalCategories: Get<CategoryId>;
+
 	/// How high is the reward (%) for the proposer if the proposal is converted into a project?
 	type ProposeReward: Get<Permill>;
 
@@ -107,6 +326,27 @@ pub trait Trait: frame_system::Trait {
 	/// How many votes can each identified user (with an appropriate identity level) submit?
 	type ProposeVoteMaxPerIdentifiedUser: Get<u16>;
 
+	/// How many proposals can a ranked ballot contain? Only used by the RankedChoice scheme.
+	type MaxRankedChoices: Get<u32>;
+
+	/// How many delegations are followed at most to find the delegate that votes for an identity?
+	type MaxDelegationDepth: Get<u32>;
+
+	/// How many identities can a delegate represent at most, directly or through other delegates?
+	type MaxDelegators: Get<u32>;
+
+	/// During how many blocks before the end of the VotePropose phase are committed votes revealed?
+	/// 0 disables commit-reveal voting, votes are cast directly.
+	type RevealPeriod: Get<Self::BlockNumber>;
+
+	/// During how many blocks before the end of a voting phase must proposers not vote for their
+	/// own proposals? 0 disables the quiet period.
+	type ProposerQuietPeriod: Get<Self::BlockNumber>;
+
+	/// How much stake must be locked for one additional vote (before the conviction multiplier)?
+	/// 0 disables conviction voting.
+	type ConvictionVoteUnit: Get<BalanceOf<Self>>;
+
 	/// How high is the reward if a proposal that the user voted for passes into next round?
 	type ProposeVoteCorrectReward: Get<BalanceOf<Self>>;
 
@@ -126,8 +366,8 @@ pub trait Trait: frame_system::Trait {
 	/// How long can concerns be submitted? Value in seconds.
 	type ConcernRoundDuration: Get<Self::BlockNumber>;
 
-	// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
-	// type UserConcernFee: Get<BalanceOf<Self>>;
+	/// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
+	type UserConcernFee: Get<BalanceOf<Self>>;
 
 	/// Part 2.2: Concern voting state configuration
 	/// How many votes (%) does a concern require to be accepted for the next round?
@@ -145,6 +385,32 @@ pub trait Trait: frame_system::Trait {
 	/// How high is the reward if a concern that the user voted for passes into next round?
 	type ConcernVoteCorrectReward: Get<BalanceOf<Self>>;
 
+	/// How many accrued rewards are paid out per block after a voting round?
+	type RewardBatchSize: Get<u32>;
+
+	/// How many voters are evaluated per block after a voting round?
+	type TallyBatchSize: Get<u32>;
+
+	/// How many results of finished rounds are kept in the round history?
+	type HistoryDepth: Get<u32>;
+
+	/// How many bytes may an error explanation contain?
+	type MaxErrorExplanationLength: Get<u32>;
+
+	/// Priority of the availability attestations submitted by the off-chain worker
+	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// Key the off-chain workers sign their attestations with
+	type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+	/// Must the off-chain worker attest that a proposal is retrievable on IPFS before
+	/// it can receive votes?
+	type ExcludeUnavailableProposals: Get<bool>;
+
+	/// How many non-transferable voice credits does every identity receive per voting round?
+	/// Votes consume voice credits, the cost is defined by the TallyAlgorithm.
+	type VoiceCreditsPerRound: Get<u32>;
+
 	/// Part 3: Final evaluation of the winning proposals and associated concern by the council
 	/// How much time is reserved for the council to vote? Value in seconds
 	type CouncilVoteRoundDuration: Get<Self::BlockNumber>;
@@ -152,6 +418,14 @@ pub trait Trait: frame_system::Trait {
 	/// How many percent of the council must agree that a concern is too serious to launch a
 	/// project from the associated proposal?
 	type CouncilAcceptConcernMinVotes: Get<Permill>;
+
+	/// How many percent of the council must have voted on every poll to end the council vote
+	/// early, given that a supermajority was achieved?
+	type CouncilEarlyTransitQuorum: Get<Permill>;
+
+	/// How many percent of the votes must agree on the outcome of every poll to end the
+	/// council vote early, given that the quorum was achieved?
+	type CouncilEarlyTransitSupermajority: Get<Permill>;
 }
 
 // TODO: Remove pub storage and write getters
@@ -162,98 +436,451 @@ decl_storage! {
 		// the state machine cannot be initialized during genesis, because
 		// add_extra_genesis won't be called at all (1. Nov 2020)
 		pub State get(fn state) config(): States = States::Uninitialized;
+		/// Storage layout version, used to determine which migrations have to be applied
+		StorageVersion build(|_| CURRENT_RELEASE): Releases;
 		/// BlockNumber for which the next state transit is scheduled
 		pub NextTransit get(fn next_transit): T::BlockNumber = T::BlockNumber::from(0);
-		/// Current round
-		// decided for u8 because after 256 proposal rounds the old proposals should be converted
-		// into projects already. In addition, the blockchain state can be inspected at any block.
-		// Last, There is no gurantee that the proposals still exist in decentralized storage.
-		pub Round get(fn round): u8 = 0;
-
-		/// Identity -> Proposals
-		pub Proposals get(fn proposals): map hasher(identity)
-			IdentityId<T> => Vec<Proposal> = Vec::new();
+		/// Named scheduler id of the next scheduled state transit
+		pub ScheduledTransit get(fn scheduled_transit): Option<Vec<u8>> = None;
+		/// Is the state machine frozen?
+		pub Paused get(fn paused): bool = false;
+		/// Phase -> Duration set by governance, overrides the configured duration of the phase
+		pub PhaseDurations get(fn phase_duration_override): map hasher(identity)
+			States => Option<T::BlockNumber> = None;
+		/// Submission phase -> Submission cap set by governance, bounded by the configured cap
+		pub SubmissionCaps get(fn submission_cap_override): map hasher(identity)
+			States => Option<u32> = None;
+		/// Category -> Maximum number of proposals of the category per round, set by governance
+		pub CategoryCaps get(fn category_cap): map hasher(identity) CategoryId => Option<u32> = None;
+		/// (Round, Category) -> Number of submitted proposals of the category
+		pub CategoryProposalCount get(fn category_proposal_count): double_map hasher(identity) RoundIndex,
+			hasher(identity) CategoryId => u32 = 0;
+		/// Voting phase -> Acceptance threshold set by governance, overrides the configured threshold
+		pub AcceptanceThresholds get(fn acceptance_threshold_override): map hasher(identity)
+			States => Option<Permill> = None;
+		/// Remaining blocks until the next state transit at the time the state machine was paused
+		pub PausedTransitRemaining get(fn paused_transit_remaining): T::BlockNumber = T::BlockNumber::from(0);
+		/// Current round. Incremented after every full cycle, never wraps around.
+		pub Round get(fn round) config(): RoundIndex = 0;
+		/// Must the state machine be started in the next block? Set by on_runtime_upgrade if the
+		/// pallet was added during a runtime upgrade, cleared by on_initialize after the first transit
+		pub PendingInit get(fn pending_init): bool = false;
+		/// Is a state transit currently being executed?
+		pub TransitInProgress get(fn transit_in_progress): bool = false;
+		/// Was a state transit refused because a tally was running? on_initialize executes it
+		/// as soon as the tally finished.
+		pub DeferredTransit get(fn deferred_transit): bool = false;
+		/// (Round, State, Block) of the last completed state transit. Prevents that duplicate
+		/// triggers within the same block advance the state machine twice.
+		pub LastTransit get(fn last_transit): Option<(RoundIndex, States, T::BlockNumber)> = None;
+
+		// The round keyed double maps can be pruned with remove_prefix(round). They use the identity
+		// hasher, therefore their keys equal the keys of the former (Round, Identity) maps.
+		/// (Round, Identity) -> Proposals
+		pub Proposals get(fn proposals): double_map hasher(identity) RoundIndex,
+			hasher(identity) IdentityId<T> => Vec<Proposal> = Vec::new();
 		/// Proposal -> Identity
 		pub ProposalToIdentity get(fn proposal_to_identity): map hasher(identity)
 			ProposalCID => IdentityId<T> = IdentityId::<T>::default();
-		/// Identity -> Votes (we have to keep track of the CIDs to reward the user)
-		pub ProposalVotes get(fn votes): map hasher(identity)
-			IdentityId<T> => Vec<ProposalCID> = Vec::new();
+		/// Proposal -> (Proposer, Round and submission index) of the latest submission of the CID
+		pub ProposalByCid get(fn proposal_by_cid): map hasher(identity)
+			ProposalCID => Option<(IdentityId<T>, Cursor)> = None;
+		/// Proposal -> Block in which the off-chain worker attested that the proposal is retrievable on IPFS
+		pub ProposalAvailability get(fn proposal_availability): map hasher(identity)
+			ProposalCID => Option<T::BlockNumber> = None;
+		/// Accounts whose off-chain worker keys may attest availabilities and pins
+		pub OffchainAuthorities get(fn offchain_authorities): Vec<T::AccountId>;
+		/// (Round, Identity) -> Votes (we have to keep track of the CIDs to reward the user)
+		pub ProposalVotes get(fn votes): double_map hasher(identity) RoundIndex,
+			hasher(identity) IdentityId<T> => Vec<ProposalCID> = Vec::new();
+		/// (Round, Identity) -> Hash of the committed (votes, salt), removed when revealed
+		pub VoteCommitments get(fn vote_commitments): double_map hasher(identity) RoundIndex,
+			hasher(identity) IdentityId<T> => Option<T::Hash> = None;
+		/// (Round, Identity) -> Votes of the identity (only the last HistoryDepth rounds are kept)
+		pub VoteReceipts get(fn vote_receipt): double_map hasher(identity) RoundIndex,
+			hasher(identity) IdentityId<T> => Option<VoteReceipt> = None;
+		/// (Round, Nullifier) -> Did the anonymous voter cast a ballot?
+		pub AnonymousNullifiers get(fn anonymous_nullifiers): double_map hasher(identity) RoundIndex,
+			hasher(identity) Nullifier => bool = false;
+		/// (Project, Milestone) -> (End, Confirmations, Rejections) of the confirmation round of a milestone
+		pub MilestoneConfirmations get(fn milestone_confirmation): double_map hasher(identity) ProjectID,
+			hasher(identity) MilestoneIndex => Option<(T::BlockNumber, u32, u32)> = None;
+		/// ((Project, Milestone), Identity) -> Did the identity vote in the confirmation round?
+		pub MilestoneVoters get(fn milestone_voters): double_map hasher(identity) (ProjectID, MilestoneIndex),
+			hasher(identity) IdentityId<T> => bool = false;
 		/// Total votes
 		pub ProposalVoteCount get(fn vote_count): u32 = 0;
 		/// Total proposals
 		pub ProposalCount get(fn proposal_count): u32 = 0;
+		/// Submission index of the next proposal in this round
+		pub NextProposalIndex get(fn next_proposal_index): u32 = 0;
+		/// Proposal -> (Account, reserved bond)
+		pub ProposalBonds get(fn proposal_bonds): map hasher(identity)
+			ProposalCID => Option<(T::AccountId, BalanceOf<T>)> = None;
+		/// Proposal -> (Account, reserved deposit) for proposals of users without sufficient identity level
+		pub UserProposeDeposits get(fn user_propose_deposits): map hasher(identity)
+			ProposalCID => Option<(T::AccountId, BalanceOf<T>)> = None;
+		/// Identity -> Block until which the identity must not submit proposals or concerns
+		/// and must not vote
+		pub PenalizedUntil get(fn penalized_until): map hasher(identity)
+			IdentityId<T> => Option<T::BlockNumber> = None;
 		/// Proposal winner for specific round
 		pub ProposalWinners get(fn proposal_winners): map hasher(identity)
-			u8 => VecDeque<ProposalWinner<IdentityId<T>>> = VecDeque::new();
+			RoundIndex => VecDeque<ProposalWinner<IdentityId<T>>> = VecDeque::new();
 
-		/// Identity -> Concerns
-		pub Concerns get(fn concerns): map hasher(identity)
-			IdentityId<T> => Vec<Concern> = Vec::new();
+		/// (Round, Identity) -> Concerns
+		pub Concerns get(fn concerns): double_map hasher(identity) RoundIndex,
+			hasher(identity) IdentityId<T> => Vec<Concern> = Vec::new();
 		/// ConcernCID -> Identity
 		pub ConcernToIdentity get(fn concern_to_identity): map hasher(identity)
 			(ConcernCID, ProposalCID) => IdentityId<T> = IdentityId::<T>::default();
 		/// Total Concerns
 		pub ConcernCount get(fn concern_count): u32 = 0;
-
-		/// Identity -> Votes for concerns (we have to keep track of the CIDs to reward the user)
-		pub ConcernVotes get(fn votes_concern): map hasher(identity)
-			IdentityId<T> => Vec<ConcernCID> = Vec::new();
+		/// Submission index of the next concern in this round
+		pub NextConcernIndex get(fn next_concern_index): u32 = 0;
+		/// (ConcernCID, ProposalCID) -> (Account, reserved deposit) for concerns of users without
+		/// sufficient identity level
+		pub UserConcernDeposits get(fn user_concern_deposits): map hasher(identity)
+			(ConcernCID, ProposalCID) => Option<(T::AccountId, BalanceOf<T>)> = None;
+
+		/// Identity -> Whether the identity wants to be notified about concerns against its proposals
+		pub ConcernNotificationsEnabled get(fn concern_notifications_enabled): map hasher(identity)
+			IdentityId<T> => bool = false;
+		/// (Proposer, (Concern, Proposal)) -> Was the concern filed against the proposal of the
+		/// proposer during this round?
+		pub PendingConcernNotifications get(fn pending_concern_notifications): double_map hasher(identity)
+			IdentityId<T>, hasher(identity) (ConcernCID, ProposalCID) => bool = false;
+
+		/// (Round, Identity) -> Votes for concerns (we have to keep track of the CIDs to reward the user)
+		pub ConcernVotes get(fn votes_concern): double_map hasher(identity) RoundIndex,
+			hasher(identity) IdentityId<T> => Vec<ConcernCID> = Vec::new();
 		/// Total votes for concerns
 		pub ConcernVoteCount get(fn vote_count_concern): u32 = 0;
 
+		/// Voting round counter, voice credits of past voting rounds are void
+		pub CreditEpoch get(fn credit_epoch): u32 = 0;
+		/// Identity -> (Voting round, remaining voice credits)
+		// Voice credits are minted lazily on the first vote of an identity within a voting round
+		pub VoiceCredits get(fn voice_credits): map hasher(identity)
+			IdentityId<T> => (u32, u32) = (0, 0);
+
 		/// Tickets used as reference for council polls targeting proposals
 		pub CouncilVoteTickets get(fn council_vote_tickets): Vec<Ticket> = Vec::new();
+
+		/// Account -> (Last round of the lock, locked stake) of every conviction vote
+		pub ConvictionLocks get(fn conviction_locks): map hasher(identity)
+			T::AccountId => Vec<(RoundIndex, BalanceOf<T>)> = Vec::new();
+
+		/// Account -> Accrued rewards that were not paid out yet
+		pub PendingRewards get(fn pending_rewards): map hasher(identity)
+			T::AccountId => BalanceOf<T> = BalanceOf::<T>::from(0);
+		/// Account -> Accrued rewards whose transfer failed, retried by the next reward distribution
+		pub FailedRewards get(fn failed_rewards): map hasher(identity)
+			T::AccountId => BalanceOf<T> = BalanceOf::<T>::from(0);
+		/// Sum of all PendingRewards and FailedRewards
+		pub PendingRewardTotal get(fn pending_reward_total): BalanceOf<T> = BalanceOf::<T>::from(0);
+		/// Number of the next scheduled reward distribution batch (None = no distribution running)
+		pub RewardBatch get(fn reward_batch): Option<u32> = None;
+		/// (Round, voting phase) whose voters are still being evaluated (None = no tally running)
+		pub TallyCursor get(fn tally_cursor): Option<(RoundIndex, States)> = None;
+		/// Rewards issued by the running tally so far
+		pub TallyRewardTotal get(fn tally_reward_total): BalanceOf<T> = BalanceOf::<T>::from(0);
+
+		/// State -> How often that phase ended without any submissions or votes
+		pub IdlePhaseCount get(fn idle_phase_count): map hasher(identity) States => u32 = 0;
+
+		/// Round -> Outcome of the round (only the last HistoryDepth rounds are kept)
+		pub RoundHistory get(fn round_history): map hasher(identity)
+			RoundIndex => RoundResult<IdentityId<T>> = RoundResult::default();
+
+		/// Winning proposal or accepted concern -> Block in which the off-chain worker confirmed
+		/// that an IPFS node pinned the document
+		pub PinnedDocuments get(fn pinned_documents): map hasher(identity)
+			DocumentCID => Option<T::BlockNumber> = None;
+
+		/// Delegator -> Delegate that votes on behalf of the delegator if the delegator does not vote
+		pub Delegations get(fn delegations): map hasher(identity)
+			IdentityId<T> => Option<IdentityId<T>> = None;
+		/// Delegate -> Identities that delegated to the delegate directly
+		pub Delegators get(fn delegators): map hasher(identity)
+			IdentityId<T> => Vec<IdentityId<T>> = Vec::new();
+
+		/// Identity -> Aggregated governance participation over all rounds
+		pub History get(fn history): map hasher(identity)
+			IdentityId<T> => GovernanceHistory = GovernanceHistory::default();
+
+		/// (Module index, Error index, Language) -> Human readable explanation of the error,
+		/// maintained by the council
+		pub ErrorExplanations get(fn error_explanation): map hasher(identity)
+			(u8, u8, LanguageCode) => Option<ErrorExplanation> = None;
 	}
 	add_extra_genesis {
-		build(|_| {
-			let _ = <Module<T>>::do_state_transit();
-		}); 
+		/// (Proposer, Proposal) pairs that are submitted in the initial round. The initial state
+		/// must be Propose or VotePropose if proposals are seeded.
+		config(proposals): Vec<(IdentityId<T>, ProposalCID)>;
+		build(|config: &GenesisConfig<T>| {
+			assert!(config.proposals.len() <= T::ProposeCap::get() as usize,
+				"The number of genesis proposals must not exceed ProposeCap");
+
+			for (id, proposal) in config.proposals.iter() {
+				assert!(!<ProposalToIdentity<T>>::contains_key(proposal), "Genesis proposals must be unique");
+				<Module<T>>::add_proposal(id.clone(), *proposal, ProposalMetadata::default());
+			}
+
+			// Start the state machine in the configured state, the default starts a proposal round
+			match config.state {
+				States::Uninitialized => {
+					assert!(config.proposals.is_empty(), "Genesis proposals require the Propose or VotePropose state");
+					let _ = <Module<T>>::do_state_transit();
+				},
+				States::Propose => {
+					<Module<T>>::schedule_state_transit(T::ProposeRoundDuration::get(), States::Propose)
+						.expect("Scheduling the first state transit must succeed");
+				},
+				States::VotePropose => {
+					assert!(!config.proposals.is_empty(), "The VotePropose state requires genesis proposals");
+					<Module<T>>::schedule_state_transit(T::ProposeVoteDuration::get(), States::VotePropose)
+						.expect("Scheduling the first state transit must succeed");
+				},
+				_ => panic!("The initial state must be Uninitialized, Propose or VotePropose"),
+			}
+		});
 	}
 }
 
 decl_event! {
-	pub enum Event<T> where Balance = BalanceOf<T>,
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId,
+							Balance = BalanceOf<T>,
+							Budget = BudgetOf<T>,
+							BlockNumber = <T as frame_system::Trait>::BlockNumber,
 							ID = IdentityId<T>,
 							PW = ProposalWinner<IdentityId<T>> {
 		/// Rotated to the next state. \[NewState\]
 		StateRotated(States),
+		/// The state machine was frozen
+		StateMachinePaused,
+		/// The state machine was resumed
+		StateMachineResumed,
 		/// Total reward for correct votes after VoteProposal round \[Balance\]
 		TotalProposalReward(Balance),
 		/// Total reward for winning concerns and votes after VoteConcern round \[Balance\]
 		TotalConcernReward(Balance),
+		/// A batch of accrued rewards was paid out \[Count, Balance\]
+		RewardsDistributed(u32, Balance),
+		/// An accrued reward was paid out \[Account, Balance\]
+		RewardPaid(AccountId, Balance),
+		/// The governance pot cannot cover the pending rewards. They are paid by the next reward
+		/// distribution after the pot was funded. \[Pending, Available\]
+		RewardPotExhausted(Balance, Balance),
+		/// The governance pot cannot cover the budget of an approved proposal, no project was
+		/// spawned \[Round, Proposal, Budget\]
+		ProjectNotFunded(RoundIndex, ProposalCID, Budget),
+		/// A proposal reached the acceptance threshold, but its budget exceeds the remaining
+		/// budget of the round \[Round, Proposal, Budget\]
+		ProposalExceedsRoundBudget(RoundIndex, ProposalCID, Budget),
+		/// A proposal was submitted \[Round, Proposer, Proposal\]
+		ProposalSubmitted(RoundIndex, ID, ProposalCID),
+		/// A proposal was submitted with metadata \[Proposal, Metadata\]
+		ProposalMetadataSet(ProposalCID, ProposalMetadata),
+		/// A vote for a proposal was cast \[Round, Voter, Proposal\]
+		ProposalVoted(RoundIndex, ID, ProposalCID),
+		/// Stake was locked to back a vote with conviction \[Account, Balance, LastLockedRound\]
+		VoteStakeLocked(AccountId, Balance, RoundIndex),
+		/// The expired conviction locks of an account were removed \[Account, RemainingLock\]
+		VoteStakeUnlocked(AccountId, Balance),
+		/// An anonymous ballot was cast \[Round, Proposals\]
+		AnonymousBallotCast(RoundIndex, Vec<ProposalCID>),
+		/// An identity committed to its proposal votes \[Round, Voter\]
+		VotesCommitted(RoundIndex, ID),
+		/// An identity revealed and cast its committed votes \[Round, Voter\]
+		VotesRevealed(RoundIndex, ID),
+		/// An identity delegated its proposal votes \[Delegator, Delegate\]
+		Delegated(ID, ID),
+		/// An identity revoked its delegation \[Delegator\]
+		Undelegated(ID),
+		/// A ranked ballot was cast \[Round, Voter, Ranking\]
+		RankingSubmitted(RoundIndex, ID, Vec<ProposalCID>),
+		/// A concern was submitted \[Round, Submitter, Concern, Proposal\]
+		ConcernSubmitted(RoundIndex, ID, ConcernCID, ProposalCID),
+		/// A vote for a concern was cast \[Round, Voter, Concern, Proposal\]
+		ConcernVoted(RoundIndex, ID, ConcernCID, ProposalCID),
+		/// The council decided about a winning proposal \[Round, Proposal, Accepted\]
+		CouncilVoted(RoundIndex, ProposalCID, bool),
+		/// A round was completed and its results were archived \[Round\]
+		RoundFinalized(RoundIndex),
+		/// A phase ended without any submissions or votes. The configuration (durations, fees,
+		/// identity requirements) might need adjustments. \[State, TotalIdleCountOfState\]
+		PhaseHadNoActivity(States, u32),
 		/// If the council decides to deny a proposal, announce the proposal
 		/// and the votes \[ProposalWinner, Vec(id, vote)\]
 		CouncilDeniedProposal(PW, Vec<(ID, bool)>),
+		/// The council blocked a proposal, every rejecting vote carries a justification document
+		/// \[Round, Proposal, Justifications (Council member, Document)\]
+		ProposalBlocked(RoundIndex, ProposalCID, Vec<(ID, DocumentCID)>),
+		/// The community can confirm a milestone until the block \[Project, Milestone, Evidence, End\]
+		MilestoneConfirmationOpened(ProjectID, MilestoneIndex, ProposalCID, BlockNumber),
+		/// An identity voted in the confirmation round of a milestone \[Project, Milestone, Voter, Confirm\]
+		MilestoneVoted(ProjectID, MilestoneIndex, ID, bool),
+		/// The confirmation round of a milestone was concluded \[Project, Milestone, Confirmed\]
+		MilestoneConfirmationConcluded(ProjectID, MilestoneIndex, bool),
+		/// A proposal was removed as spam and the bond was slashed \[Proposer, Proposal, Balance\]
+		ProposalBondSlashed(ID, ProposalCID, Balance),
+		/// A proposer was penalized for abusive content, the reserved bonds were slashed and the
+		/// identity is locked out until the block \[Proposer, Reason, Balance, LockedUntil\]
+		ProposerPenalized(ID, DocumentCID, Balance, BlockNumber),
+		/// An identity was locked out until the block \[Identity, LockedUntil\]
+		PenaltyImposed(ID, BlockNumber),
+		/// The penalty of an identity was lifted \[Identity\]
+		PenaltyLifted(ID),
+		/// A proposal was withdrawn by the proposer and the bond was returned
+		/// \[Proposer, Proposal, Balance\]
+		ProposalWithdrawn(ID, ProposalCID, Balance),
+		/// A proposal was replaced by the proposer \[Proposer, OldProposal, NewProposal\]
+		ProposalAmended(ID, ProposalCID, ProposalCID),
+		/// The off-chain worker retrieved a proposal from IPFS \[Round, Proposal\]
+		ProposalAvailable(RoundIndex, ProposalCID),
+		/// An IPFS node pinned a winning proposal or an accepted concern \[Round, Document\]
+		DocumentPinned(RoundIndex, DocumentCID),
+		/// Governance replaced the accounts of the off-chain authorities \[Authorities\]
+		OffchainAuthoritiesSet(Vec<AccountId>),
+		/// A concern was removed as spam and the deposit was slashed
+		/// \[Submitter, Concern, Proposal, Balance\]
+		ConcernDepositSlashed(ID, ConcernCID, ProposalCID, Balance),
+		/// A concern was withdrawn by the submitter and the deposit was returned
+		/// \[Submitter, Concern, Proposal, Balance\]
+		ConcernWithdrawn(ID, ConcernCID, ProposalCID, Balance),
+		/// A concern was filed against a proposal. The event is indexed by the hash of the proposer.
+		/// \[Proposer, Concern, Proposal\]
+		ConcernFiled(ID, ConcernCID, ProposalCID),
+		/// The scheduled state transit was moved to another block \[Block\]
+		NextTransitSet(BlockNumber),
+		/// Governance changed the duration of a phase, None restores the configured duration
+		/// \[Phase, Duration\]
+		PhaseDurationSet(States, Option<BlockNumber>),
+		/// Governance changed the submission cap of a phase, None restores the configured cap
+		/// \[Phase, Cap\]
+		SubmissionCapSet(States, Option<u32>),
+		/// Governance changed the submission cap of a proposal category, None removes the cap
+		/// \[Category, Cap\]
+		CategoryCapSet(CategoryId, Option<u32>),
+		/// Governance changed the acceptance threshold of a voting phase, None restores the
+		/// configured threshold \[Phase, Threshold\]
+		AcceptanceThresholdSet(States, Option<Permill>),
+		/// The explanation of an error was changed or removed
+		/// \[ModuleIndex, ErrorIndex, Language, Version\]
+		ErrorExplanationUpdated(u8, u8, LanguageCode, u32),
 	}
 }
 
 decl_error! {
 	pub enum Error for Module<T: Trait> {
+		/// The identity already voted for the document and the voting scheme does not accept
+		/// repeated votes.
+		AlreadyVoted,
+		/// Votes must be committed and revealed, because RevealPeriod is not 0.
+		CommitRevealRequired,
+		/// Commit-reveal voting is disabled, because RevealPeriod is 0.
+		CommitRevealDisabled,
+		/// The revealed votes and salt do not match the commitment of the identity.
+		CommitmentMismatch,
+		/// The anonymous ballot is not accompanied by a valid proof of eligibility.
+		InvalidAnonymityProof,
 		/// Concern was already submitted by another person
 		ConcernAlreadySubmitted,
 		/// Unable to add proposal because the concern limit is reached.
 		ConcernLimitReached,
 		/// Concern does not exist
 		ConcernNotExistant,
+		/// The delegation would create a cycle or a chain longer than MaxDelegationDepth.
+		InvalidDelegation,
+		/// The delegate already represents MaxDelegators identities.
+		TooManyDelegators,
+		/// The document is neither a winning proposal nor an accepted concern of the round.
+		DocumentNotPinnable,
+		/// The document was pinned already.
+		DocumentAlreadyPinned,
+		/// The error explanation exceeds MaxErrorExplanationLength.
+		ExplanationTooLong,
 		/// Identity level too low.
 		IdentityLevelTooLow,
-		/// Proposal was already submitted by another person
-		ProposalAlreadySubmitted,
-		/// Proposal does not exist
-		ProposalNotExistant,
+		/// The ranking is empty, exceeds MaxRankedChoices or contains a proposal twice.
+		InvalidRanking,
+		/// The parameter does not exist for the phase.
+		InvalidPhase,
+		/// The submitted bytes are not a CIDv0 or CIDv1 with a sha2-256 multihash.
+		InvalidCid,
+		/// The category or the requested budget of the proposal metadata is out of bounds.
+		InvalidMetadata,
+		/// The maximum number of proposals of the category was reached for this round.
+		CategoryLimitReached,
+		/// The account has no identity (or the identity was revoked).
+		NoIdentity,
+		/// The state transit must be scheduled in a future block.
+		TransitNotInFuture,
+		/// The identity does not delegate its votes.
+		NotDelegating,
+		/// The state machine is not paused.
+		NotPaused,
+		/// The proposal was submitted by another identity.
+		NotProposalOwner,
+		/// The concern was submitted by another identity.
+		NotTheSubmitter,
+		/// The state machine is paused.
+		Paused,
+		/// The account cannot reserve enough funds.
+		InsufficientBalance,
+		/// The identity does not have enough voice credits left to vote.
+		InsufficientVoiceCredits,
+		/// Proposal was already submitted in this round
+		DuplicateProposal,
+		/// Proposal does not exist in this round.
+		UnknownProposal,
+		/// Proposers cannot act on their own proposals shortly before the voting phase ends.
+		ProposerQuietPeriod,
+		/// Round simulations are only available on test networks.
+		SimulationDisabled,
+		/// Conviction voting is disabled or the stake is below ConvictionVoteUnit.
+		StakeTooLow,
 		/// Unable to add proposal because the proposal limit is reached.
 		ProposalLimitReached,
+		/// The parameter is zero or exceeds the bound of the runtime configuration.
+		ParameterOutOfBounds,
+		/// The availability of the proposal on IPFS was attested already.
+		ProposalAlreadyAvailable,
+		/// The proposal was not retrieved from IPFS yet and cannot receive votes.
+		ProposalUnavailable,
+		/// The scheduler rejected a state transit or a reward distribution batch.
+		SchedulingFailed,
 		/// User submitted too many concerns.
 		UserConcernLimitReached,
-		/// User voted too many times on concerns.
-		UserConcernVoteLimitReached,
 		/// User submitted too many proposals.
 		UserProposalLimitReached,
-		/// User voted too many times.
-		UserProposalVoteLimitReached,
+		/// The identity cast the maximum number of votes in this voting phase.
+		VoteLimitReached,
+		/// The state transit of the current phase was already executed in this block.
+		TransitAlreadyCompleted,
+		/// A state transit is currently being executed.
+		TransitInProgress,
+		/// The voters of the previous voting phase are still being evaluated.
+		TallyRunning,
 		/// The operation requested cannot be executed because the pallet is in the wrong state.
 		WrongState,
+		/// The vote does not match the voting scheme of the runtime (ranked or individual votes).
+		WrongVotingScheme,
+		/// Votes are committed before and revealed during the reveal period.
+		WrongVotingPeriod,
+		/// No confirmation round for the milestone is running.
+		NoMilestoneConfirmation,
+		/// The identity was penalized and is locked out from submitting proposals and concerns
+		/// and from voting.
+		IdentityPenalized,
+		/// The identity has no penalty that could be lifted.
+		NotPenalized,
+		/// The revocation of the identity is disputed, it can't submit proposals and concerns
+		/// or vote until the dispute is resolved.
+		IdentityDisputed,
+		/// The council banned the identity, it can't submit proposals and concerns.
+		IdentityBanned,
 	}
 }
 
@@ -263,15 +890,41 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		/// Identifier of the governance pot. It pays the rewards and receives the slashed bonds
+		/// and deposits.
+		const TreasuryModuleId: ModuleId = T::TreasuryModuleId::get();
+
 		// TODO: Think about how to handle arbitrarily huge number of votes
 		// Fetch configuration
 		/// How long is an identified user locked out from submitting proposals / concerns
-		/// for bad behaviour. Value in seconds.
-		const IdentifiedUserPenality: u32 = T::IdentifiedUserPenality::get() as u32;
+		/// for bad behaviour. Value in blocks.
+		const IdentifiedUserPenality: T::BlockNumber = T::IdentifiedUserPenality::get();
+
+		/// How long are the submission caps of an identity halved after its lockout expired?
+		/// Value in blocks.
+		const RecentPenaltyPeriod: T::BlockNumber = T::RecentPenaltyPeriod::get();
+
+		/// How much reputation raises the submission caps of an identity by another multiple of
+		/// ProposeIdentifiedUserCap / ConcernIdentifiedUserCap? 0 disables the reputation bonus
+		const ReputationCapStep: Score = T::ReputationCapStep::get();
+
+		/// Up to which multiple of the submission caps can reputation raise them?
+		const ReputationCapMultiplier: u8 = T::ReputationCapMultiplier::get();
 
 		// Part 1.1: Proposal state configuration
-		// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
-		// const UserProposeFee: BalanceOf<T> = T::UserProposeFee::get();
+		/// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
+		const UserProposeFee: BalanceOf<T> = T::UserProposeFee::get();
+
+		/// How many funds must be reserved as a bond when submitting a proposal?
+		/// The bond is returned after the proposal voting round and slashed if the proposal is spam.
+		const ProposeBond: BalanceOf<T> = T::ProposeBond::get();
+
+		/// How much budget can the metadata of a proposal request at most?
+		const MaxRequestedBudget: BudgetOf<T> = T::MaxRequestedBudget::get();
+
+		/// How much budget can the winning proposals of a round request in total? The free balance
+		/// of the governance pot caps the budget as well.
+		const RoundBudget: BudgetOf<T> = T::RoundBudget::get();
 
 		/// How many proposals can be submitted per proposal round? (required for weight calculation)
 		const ProposeCap: u32 = T::ProposeCap::get() as u32;
@@ -282,6 +935,9 @@ decl_module! {
 		/// Which identity level is required to create a proposal?
 		const ProposeIdentityLevel: u8 = T::ProposeIdentifiedUserCap::get() as u8;
 
+		/// How many proposal categories exist? Categories are numbered from 0.
+		const ProposalCategories: CategoryId = T::ProposalCategories::get();
+
 		/// How high is the reward (%) for the proposer if the proposal is converted into a project?
 		const ProposeReward: Permill = T::ProposeReward::get();
 
@@ -301,6 +957,27 @@ decl_module! {
 		/// How many votes can each identified user (with an appropriate identity level) submit?
 		const ProposeVoteMaxPerIdentifiedUser: u16 = T::ProposeVoteMaxPerIdentifiedUser::get() as u16;
 
+		/// How many proposals can a ranked ballot contain? Only used by the RankedChoice scheme.
+		const MaxRankedChoices: u32 = T::MaxRankedChoices::get() as u32;
+
+		/// How many delegations are followed at most to find the delegate that votes for an identity?
+		const MaxDelegationDepth: u32 = T::MaxDelegationDepth::get() as u32;
+
+		/// How many identities can a delegate represent at most, directly or through other delegates?
+		const MaxDelegators: u32 = T::MaxDelegators::get() as u32;
+
+		/// During how many blocks before the end of the VotePropose phase are committed votes revealed?
+		/// 0 disables commit-reveal voting, votes are cast directly.
+		const RevealPeriod: T::BlockNumber = T::RevealPeriod::get();
+
+		/// During how many blocks before the end of a voting phase must proposers not vote for their
+		/// own proposals? 0 disables the quiet period.
+		const ProposerQuietPeriod: T::BlockNumber = T::ProposerQuietPeriod::get();
+
+		/// How much stake must be locked for one additional vote (before the conviction multiplier)?
+		/// 0 disables conviction voting.
+		const ConvictionVoteUnit: BalanceOf<T> = T::ConvictionVoteUnit::get();
+
 		/// How high is the reward if a proposal that the user voted for passes into next round?
 		const ProposeVoteCorrectReward: BalanceOf<T> = T::ProposeVoteCorrectReward::get();
 
@@ -320,8 +997,8 @@ decl_module! {
 		/// How long can concerns be submitted? Value in seconds.
 		const ConcernRoundDuration: T::BlockNumber = T::ConcernRoundDuration::get();
 
-		// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
-		// const UserConcernFee: BalanceOf<T> = T::UserConcernFee::get();
+		/// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
+		const UserConcernFee: BalanceOf<T> = T::UserConcernFee::get();
 
 		// Part 2.2: Concern voting state configuration
 		/// How many votes (%) does a concern require to be accepted for the next round?
@@ -339,6 +1016,22 @@ decl_module! {
 		/// How high is the reward if a concern that the user voted for passes into next round?
 		const ConcernVoteCorrectReward: BalanceOf<T> = T::ConcernVoteCorrectReward::get();
 
+		/// How many accrued rewards are paid out per block after a voting round?
+		const RewardBatchSize: u32 = T::RewardBatchSize::get() as u32;
+
+		/// How many voters are evaluated per block after a voting round?
+		const TallyBatchSize: u32 = T::TallyBatchSize::get() as u32;
+
+		/// How many results of finished rounds are kept in the round history?
+		const HistoryDepth: u32 = T::HistoryDepth::get() as u32;
+
+		/// How many non-transferable voice credits does every identity receive per voting round?
+		/// Votes consume voice credits, the cost is defined by the TallyAlgorithm.
+		const VoiceCreditsPerRound: u32 = T::VoiceCreditsPerRound::get() as u32;
+
+		/// Voting scheme of the TallyAlgorithm, determines the cost of repeated votes
+		const VotingModel: VotingScheme = T::TallyAlgorithm::scheme();
+
 		/// Part 3: Final evaluation of the winning proposals and associated concern by the council
 		/// How much time is reserved for the council to vote? Value in seconds
 		const CouncilVoteRoundDuration: T::BlockNumber = T::CouncilVoteRoundDuration::get();
@@ -346,87 +1039,426 @@ decl_module! {
 		/// How many percent of the council must agree that a concern is too serious to launch a
 		/// project from the associated proposal?
 		const CouncilAcceptConcernMinVotes: Permill = T::CouncilAcceptConcernMinVotes::get() as Permill;
+
+		/// How many percent of the council must have voted on every poll to end the council vote
+		/// early, given that a supermajority was achieved?
+		const CouncilEarlyTransitQuorum: Permill = T::CouncilEarlyTransitQuorum::get() as Permill;
+
+		/// How many percent of the votes must agree on the outcome of every poll to end the
+		/// council vote early, given that the quorum was achieved?
+		const CouncilEarlyTransitSupermajority: Permill = T::CouncilEarlyTransitSupermajority::get() as Permill;
+
+		/// How many bytes may an error explanation contain?
+		const MaxErrorExplanationLength: u32 = T::MaxErrorExplanationLength::get() as u32;
+
+		/// Priority of the availability attestations submitted by the off-chain worker
+		const UnsignedPriority: TransactionPriority = T::UnsignedPriority::get();
+
+		/// Must the off-chain worker attest that a proposal is retrievable on IPFS before
+		/// it can receive votes?
+		const ExcludeUnavailableProposals: bool = T::ExcludeUnavailableProposals::get();
 		
 
-		/// If this module was added during a runtime upgrade, start the state machine
-		// If you want to implement this feature, consider:
-		// 1. This function is called before the runtime state is initialized, therefore
-		// 	  we don't have access to the current block number. This means that we we cannot
-		//    figure out when the scheduler should transit into the next state in do_state_transit() (31. Oct 2020)
-		/*
+		/// Migrate the storage to the current layout. If this module was added during a runtime
+		/// upgrade, start the state machine in the next block.
+		// The upgrade only sets PendingInit. on_initialize runs afterwards with the block number
+		// of the block, performs the first state transit and schedules the next one from there.
 		fn on_runtime_upgrade() -> Weight {
-			if let States::Uninitialized = <State>::get() {
+			#[cfg(feature = "try-runtime")]
+			migrations::pre_upgrade::<T>().expect("pre_upgrade checks of the proposal pallet failed");
+
+			let mut weight: Weight = migrations::migrate::<T>();
+
+			#[cfg(feature = "try-runtime")]
+			migrations::post_upgrade::<T>().expect("post_upgrade checks of the proposal pallet failed");
+
+			// Repeated upgrades only set the flag again, the state machine is started once
+			if <State>::get() == States::Uninitialized {
+				PendingInit::put(true);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+
+			weight.saturating_add(T::DbWeight::get().reads(1))
+		}
+
+		/// Start the state machine if it is pending, continue a running tally and leave the
+		/// council vote early if every poll is decided
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let mut weight: Weight = T::DbWeight::get().reads(2);
+
+			if <PendingInit>::get() {
+				// The transit is scheduled with a named id that contains the round, the state and
+				// the block, therefore a state machine that is already running is never scheduled twice.
+				// If the first transit fails, it is retried in the next block.
+				if <State>::get() != States::Uninitialized || Self::do_state_transit().is_ok() {
+					PendingInit::kill();
+				}
+
+				weight = weight.saturating_add(T::WeightInfo::state_transit(0));
+			}
+
+			if <TallyCursor>::exists() {
+				let evaluated: u64 = Self::tally_voter_rewards() as u64;
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(4 + 2 * evaluated, 2 + 2 * evaluated));
+
+				// Execute the state transit that waited for the tally
+				if !<TallyCursor>::exists() && DeferredTransit::take() {
+					let _ = Self::do_state_transit();
+					weight = weight.saturating_add(T::WeightInfo::state_transit(T::ProposeCap::get()));
+				}
+			}
+
+			if <Paused>::get() || <State>::get() != States::VoteCouncil {
+				return weight.saturating_add(T::DbWeight::get().reads(2));
+			}
+
+			let tickets: Vec<Ticket> = <CouncilVoteTickets>::get();
+
+			if !tickets.is_empty() && tickets.iter().all(|ticket| Self::council_poll_decided(ticket)) {
+				// TODO: Appropriate Error or handling.
 				let _ = Self::do_state_transit();
+				return weight.saturating_add(T::WeightInfo::state_transit(T::ProposeCap::get()));
 			}
 
-			0
-		}*/
+			weight.saturating_add(T::DbWeight::get().reads(2 + 2 * tickets.len() as u64))
+		}
+
+		/// Check whether the proposals of the current round are retrievable on IPFS and pin
+		/// the winning proposals and their accepted concerns
+		fn offchain_worker(now: T::BlockNumber) {
+			if <Paused>::get() {
+				return;
+			}
+
+			if <State>::get() == States::Propose {
+				offchain::attest_availability::<T>(now);
+			}
+
+			offchain::pin_winners::<T>(now);
+		}
 
-		
 		/// Enforce state transit
 		// Only for test purposes. Will be deleted in the future.
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(5000,3)]
+		#[weight = T::WeightInfo::state_transit(T::ProposeCap::get())]
 		fn state_transit(origin) -> DispatchResult {
 			// check and change the current state
 			ensure_root(origin)?;
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
 			Self::do_state_transit()
 		}
 
+		/// As the council or root, freeze the state machine. Cancels the scheduled state transit
+		/// and rejects submissions and votes until the state machine is resumed.
+		#[weight = T::WeightInfo::pause()]
+		fn pause(origin) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+
+			if let Some(id) = <ScheduledTransit>::take() {
+				let _ = T::Scheduler::cancel_named(id);
+			}
+
+			let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+			let next_transit: T::BlockNumber = <NextTransit<T>>::get();
+			let remaining: T::BlockNumber = if next_transit > current_block {
+				next_transit - current_block
+			} else {
+				T::BlockNumber::from(0)
+			};
+			<PausedTransitRemaining<T>>::put(remaining);
+			Paused::put(true);
+			Self::deposit_event(Event::<T>::StateMachinePaused);
+		}
+
+		/// As the council or root, resume the state machine. The state transit is scheduled
+		/// with the time that was remaining when the state machine was paused.
+		#[weight = T::WeightInfo::resume()]
+		fn resume(origin) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(<Paused>::get(), Error::<T>::NotPaused);
+
+			let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+			// Schedule at least one block in the future
+			let remaining: T::BlockNumber = <PausedTransitRemaining<T>>::take().max(T::BlockNumber::from(1u32));
+			Self::schedule_state_transit(current_block + remaining, <State>::get())?;
+			Paused::put(false);
+			Self::deposit_event(Event::<T>::StateMachineResumed);
+		}
+
+		/// As root, fabricate and run through a full synthetic round within one block.
+		/// Only available on test networks (feature "testnet").
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(5000, 3)
+			+ T::DbWeight::get().reads_writes(8, 6).saturating_mul(
+				*proposals as u64 + 2 * *votes as u64 + *concerns as u64
+			)]
+		fn simulate_round(origin, proposals: u32, votes: u32, concerns: u32) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
+			ensure!(<ProposalCount>::get().saturating_add(proposals) <= Self::submission_cap(States::Propose),
+				Error::<T>::ProposalLimitReached
+			);
+			ensure!(concerns <= Self::submission_cap(States::Concern), Error::<T>::ConcernLimitReached);
+			Self::do_simulate_round(proposals, votes, concerns)
+		}
+
+		/// Pay out the next batch of accrued rewards
+		#[weight = T::WeightInfo::distribute_rewards(T::RewardBatchSize::get())]
+		fn distribute_rewards(origin) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::do_distribute_rewards()
+		}
+
 
-		/// As an identified user, submit a concern
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
-		fn concern(origin, concern: ConcernCID, proposal: ProposalCID) {
+		/// As an identified user, submit a concern. Users without a sufficient identity level
+		/// can submit a concern by reserving the UserConcernFee. The concern is a binary CID,
+		/// the proposal is referenced by its digest.
+		#[weight = T::WeightInfo::concern()]
+		fn concern(origin, concern: Vec<u8>, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
-			// Ensure that the pallet is in the appropriate state
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
 			ensure!(<State>::get() == States::Concern, Error::<T>::WrongState);
+			// Ensure that the concern is a valid CID
+			let concern: ConcernCID = cid_digest(&concern).ok_or(Error::<T>::InvalidCid)?;
 			// Ensure that the maximum concern count was not reached yet
-			ensure!(<ConcernCount>::get() < T::ConcernCap::get().into(), Error::<T>::ConcernLimitReached);
-			// Ensure the identity level is high enough to submit a concern.
+			ensure!(<ConcernCount>::get() < Self::submission_cap(States::Concern), Error::<T>::ConcernLimitReached);
+			// Check whether the identity level is high enough to submit a concern without a deposit.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ConcernIdentityLevel::get().into(),
-					Error::<T>::IdentityLevelTooLow
-			);
+			Self::ensure_not_penalized(&id)?;
+			ensure!(!T::Identity::is_banned(&id), Error::<T>::IdentityBanned);
+			let identified: bool = T::Identity::get_identity_level(&id) >= T::ConcernIdentityLevel::get().into();
 			// Ensure the user has not surpassed the concern limit per user
-			ensure!(<Concerns<T>>::get(&id).len() < T::ConcernIdentifiedUserCap::get().into(),
+			ensure!(<Concerns<T>>::get(<Round>::get(), &id).len()
+					< Self::identity_cap(&id, T::ConcernIdentifiedUserCap::get()),
 					Error::<T>::UserConcernLimitReached
 			);
 			// Ensure that the concern was not already submitted
 			ensure!(<ConcernToIdentity<T>>::get((&concern, &proposal)) == IdentityId::<T>::default(),
 					Error::<T>::ConcernAlreadySubmitted
 			);
-			Self::add_concern(id, concern, proposal);
+			// Reserve the deposit, it is returned after the concern voting round
+			if !identified {
+				let deposit: BalanceOf<T> = T::UserConcernFee::get();
+				T::Currency::reserve(&caller, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+				UserConcernDeposits::<T>::insert((&concern, &proposal), (caller, deposit));
+			}
+			Self::add_concern(id.clone(), concern, proposal);
+			Self::deposit_event(Event::<T>::ConcernSubmitted(<Round>::get(), id, concern, proposal));
+		}
+
+		/// Enable or disable notifications about concerns that are filed against own proposals
+		#[weight = T::WeightInfo::set_concern_notifications()]
+		fn set_concern_notifications(origin, enabled: bool) {
+			let caller = ensure_signed(origin)?;
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+
+			if enabled {
+				<ConcernNotificationsEnabled<T>>::insert(&id, true);
+			} else {
+				<ConcernNotificationsEnabled<T>>::remove(&id);
+			}
+		}
+
+		/// As the council or root, remove a spam concern and slash the deposit
+		#[weight = T::WeightInfo::remove_concern()]
+		fn remove_concern(origin, concern: ConcernCID, proposal: ProposalCID) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			// Concerns only exist during the concern and the concern voting round
+			let state: States = <State>::get();
+			ensure!(state == States::Concern || state == States::VoteConcern, Error::<T>::WrongState);
+			// Ensure that the concern exists
+			let submitter: IdentityId<T> = <ConcernToIdentity<T>>::get((&concern, &proposal));
+			ensure!(submitter != IdentityId::<T>::default(),
+				Error::<T>::ConcernNotExistant
+			);
+			Self::remove_concern_entry(&submitter, &concern, &proposal);
+			<History<T>>::mutate(&submitter, |h| h.penalties_received += 1);
+			let mut slashed = BalanceOf::<T>::from(0);
+
+			if let Some((account, deposit)) = UserConcernDeposits::<T>::take((&concern, &proposal)) {
+				slashed += Self::slash_into_pot(&account, deposit);
+			}
+
+			Self::deposit_event(Event::<T>::ConcernDepositSlashed(submitter, concern, proposal, slashed));
+		}
+
+		/// As the submitter, withdraw a concern during the concern round. The deposit is returned
+		/// and the concern no longer counts towards the concern limits.
+		#[weight = T::WeightInfo::withdraw_concern()]
+		fn withdraw_concern(origin, concern: ConcernCID, proposal: ProposalCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::Concern, Error::<T>::WrongState);
+			// Ensure that the concern exists and was submitted by the caller
+			let submitter: IdentityId<T> = <ConcernToIdentity<T>>::get((&concern, &proposal));
+			ensure!(submitter != IdentityId::<T>::default(),
+				Error::<T>::ConcernNotExistant
+			);
+			ensure!(submitter == T::Identity::get_identity_id(&caller), Error::<T>::NotTheSubmitter);
+			Self::remove_concern_entry(&submitter, &concern, &proposal);
+			let mut refunded = BalanceOf::<T>::from(0);
+
+			if let Some((account, deposit)) = UserConcernDeposits::<T>::take((&concern, &proposal)) {
+				refunded += deposit - T::Currency::unreserve(&account, deposit);
+			}
+
+			Self::deposit_event(Event::<T>::ConcernWithdrawn(submitter, concern, proposal, refunded));
+		}
+
+
+		/// As an identified user, submit a proposal. Users without a sufficient identity level
+		/// can submit a proposal by reserving the UserProposeFee. Transits into the proposal
+		/// voting state as soon as the proposal limit is reached. The proposal is a binary CID.
+		#[weight = T::WeightInfo::propose()]
+		fn propose(origin, proposal: Vec<u8>) {
+			let caller = ensure_signed(origin)?;
+			Self::do_propose(caller, proposal, ProposalMetadata::default())?;
+		}
+
+		/// Submit a proposal like propose, including metadata that frontends can display without
+		/// fetching the document: the blake2-256 hash of the title, the category, the requested
+		/// budget and the binary CID of the discussion thread.
+		#[weight = T::WeightInfo::propose_with_metadata()]
+		fn propose_with_metadata(origin, proposal: Vec<u8>, title_hash: Option<[u8; 32]>,
+			category: Option<CategoryId>, budget: Option<BudgetOf<T>>, discussion: Option<Vec<u8>>
+		) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the metadata is within its bounds
+			ensure!(category.map_or(true, |c| c < T::ProposalCategories::get()), Error::<T>::InvalidMetadata);
+			// A proposal that requests more than RoundBudget could never win
+			let max_budget: BudgetOf<T> = T::MaxRequestedBudget::get().min(T::RoundBudget::get());
+			ensure!(budget.map_or(true, |b| b <= max_budget), Error::<T>::InvalidMetadata);
+			let discussion: Option<ProposalCID> = match discussion {
+				Some(cid) => Some(cid_digest(&cid).ok_or(Error::<T>::InvalidCid)?),
+				None => None,
+			};
+
+			let metadata = ProposalMetadata {
+				title_hash,
+				category,
+				budget: budget.map(|b| b.saturated_into::<u128>()),
+				discussion,
+			};
+			Self::do_propose(caller, proposal, metadata)?;
+		}
+
+		/// As the council or root, remove a spam proposal and slash the bond
+		#[weight = T::WeightInfo::remove_proposal()]
+		fn remove_proposal(origin, proposal: ProposalCID) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			// Proposals only exist during the proposal and the proposal voting round
+			let state: States = <State>::get();
+			ensure!(state == States::Propose || state == States::VotePropose, Error::<T>::WrongState);
+			// Ensure that the proposal exists
+			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&proposal);
+			ensure!(proposer != IdentityId::<T>::default(),
+				Error::<T>::UnknownProposal
+			);
+			Self::remove_proposal_entry(&proposer, &proposal);
+			<History<T>>::mutate(&proposer, |h| h.penalties_received += 1);
+			let slashed: BalanceOf<T> = Self::slash_proposal_deposits(&proposal);
+			Self::deposit_event(Event::<T>::ProposalBondSlashed(proposer, proposal, slashed));
+		}
+
+		/// As the council or root, penalize a proposer whose content was confirmed as abusive.
+		/// The proposals of the identity in the current round are removed, their reserved bonds
+		/// and deposits are slashed and the identity is locked out from submitting proposals and
+		/// concerns for IdentifiedUserPenality blocks. `reason` references the justification.
+		#[weight = T::WeightInfo::punish_proposer(
+			T::ProposeIdentifiedUserCap::get() as u32 * T::ReputationCapMultiplier::get() as u32
+		)]
+		fn punish_proposer(origin, identity: IdentityId<T>, reason: DocumentCID) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			let mut slashed = BalanceOf::<T>::from(0);
+			// Bonds are only reserved during the proposal and the proposal voting round
+			let state: States = <State>::get();
+
+			if state == States::Propose || state == States::VotePropose {
+				let proposals: Vec<ProposalCID> = <Proposals<T>>::get(<Round>::get(), &identity)
+					.iter().map(|p| p.proposal).collect();
+
+				for proposal in proposals.iter() {
+					Self::remove_proposal_entry(&identity, proposal);
+					slashed += Self::slash_proposal_deposits(proposal);
+				}
+			}
+
+			let until: T::BlockNumber = Self::impose_lockout(&identity);
+			Self::deposit_event(Event::<T>::ProposerPenalized(identity, reason, slashed, until));
 		}
 
+		/// As the council or root, lock an identity out from submitting proposals and concerns
+		/// and from voting for IdentifiedUserPenality blocks
+		#[weight = T::WeightInfo::impose_penalty()]
+		fn impose_penalty(origin, identity: IdentityId<T>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			let until: T::BlockNumber = Self::impose_lockout(&identity);
+			Self::deposit_event(Event::<T>::PenaltyImposed(identity, until));
+		}
+
+		/// As the council or root, lift the penalty of an identity before it expires
+		#[weight = T::WeightInfo::lift_penalty()]
+		fn lift_penalty(origin, identity: IdentityId<T>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(<PenalizedUntil<T>>::contains_key(&identity), Error::<T>::NotPenalized);
+			<PenalizedUntil<T>>::remove(&identity);
+			Self::deposit_event(Event::<T>::PenaltyLifted(identity));
+		}
 
-		/// As an identified user, submit a proposal
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
-		fn propose(origin, proposal: ProposalCID) {
+		/// As the proposer, withdraw a proposal during the proposal round. The bond or the
+		/// deposit is returned and the proposal no longer counts towards the proposal limits.
+		#[weight = T::WeightInfo::withdraw_proposal()]
+		fn withdraw_proposal(origin, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
-			// Ensure that the pallet is in the appropriate state
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
 			ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
-			// Ensure that the maximum proposal count was not reached yet
-			ensure!(<ProposalCount>::get() < T::ProposeCap::get().into(), Error::<T>::ProposalLimitReached);
-			// Ensure the identity level is high enough to propose.
-			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ProposeIdentityLevel::get().into(),
-					Error::<T>::IdentityLevelTooLow
+			// Ensure that the proposal exists and was submitted by the caller
+			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&proposal);
+			ensure!(proposer != IdentityId::<T>::default(),
+				Error::<T>::UnknownProposal
 			);
-			// Ensure the user has not surpassed the proposal limit per user
-			ensure!(<Proposals<T>>::get(&id).len() < T::ProposeIdentifiedUserCap::get().into(),
-					Error::<T>::UserProposalLimitReached
+			ensure!(proposer == T::Identity::get_identity_id(&caller), Error::<T>::NotProposalOwner);
+			Self::remove_proposal_entry(&proposer, &proposal);
+			let refunded: BalanceOf<T> = Self::refund_proposal_deposits(&proposal);
+			Self::deposit_event(Event::<T>::ProposalWithdrawn(proposer, proposal, refunded));
+		}
+
+		/// As the proposer, replace the CID of a proposal during the proposal round. The amended
+		/// proposal keeps its submission index and does not count as an additional proposal.
+		#[weight = T::WeightInfo::amend_proposal()]
+		fn amend_proposal(origin, old_proposal: ProposalCID, new_proposal: Vec<u8>) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
+			// Ensure that proposals are not swapped shortly before the voting phase
+			ensure!(!Self::in_quiet_period(), Error::<T>::ProposerQuietPeriod);
+			// Ensure that the new proposal is a valid CID
+			let new_proposal: ProposalCID = cid_digest(&new_proposal).ok_or(Error::<T>::InvalidCid)?;
+			// Ensure that the proposal exists and was submitted by the caller
+			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&old_proposal);
+			ensure!(proposer != IdentityId::<T>::default(),
+				Error::<T>::UnknownProposal
 			);
-			// Ensure that the proposal was not already submitted
-			ensure!(<ProposalToIdentity<T>>::get(&proposal) == IdentityId::<T>::default(),
-					Error::<T>::ProposalAlreadySubmitted
+			ensure!(proposer == T::Identity::get_identity_id(&caller), Error::<T>::NotProposalOwner);
+			// Ensure that the new proposal was not already submitted
+			let round: RoundIndex = <Round>::get();
+			ensure!(<ProposalByCid<T>>::get(&new_proposal).map_or(true, |(_, cursor)| cursor.round != round),
+					Error::<T>::DuplicateProposal
 			);
-			Self::add_proposal(id, proposal);
+			Self::amend_proposal_entry(&proposer, &old_proposal, new_proposal);
+			Self::deposit_event(Event::<T>::ProposalAmended(proposer, old_proposal, new_proposal));
 		}
 
 		/// As an identified user, vote for a concern
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
+		#[weight = T::WeightInfo::vote_concern()]
 		fn vote_concern(origin, concern: ConcernCID, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
-			// Ensure that the pallet is in the appropriate state
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
 			ensure!(<State>::get() == States::VoteConcern, Error::<T>::WrongState);
 			// Ensure that the concern exists
 			let proposer: IdentityId<T> = <ConcernToIdentity<T>>::get((&concern, &proposal));
@@ -435,109 +1467,1244 @@ decl_module! {
 			);
 			// Ensure the identity level is high enough to vote.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ConcernVoteIdentityLevel::get().into(),
-					Error::<T>::IdentityLevelTooLow
-			);
+			Self::ensure_identity_level(&id, T::ConcernVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
 			// Ensure the user has not surpassed the vote limit per user
-			ensure!(<ConcernVotes<T>>::get(&id).len() < T::ConcernVoteMaxPerIdentifiedUser::get().into(),
-					Error::<T>::UserConcernVoteLimitReached
+			let votes: Vec<ConcernCID> = <ConcernVotes<T>>::get(<Round>::get(), &id);
+			ensure!(votes.len() < T::ConcernVoteMaxPerIdentifiedUser::get().into(),
+					Error::<T>::VoteLimitReached
 			);
+			// Pay the vote with voice credits
+			let prior_votes: u32 = votes.iter().filter(|v| **v == concern).count() as u32;
+			ensure!(prior_votes == 0 || T::TallyAlgorithm::repeated_votes(), Error::<T>::AlreadyVoted);
+			Self::consume_voice_credits(&id, prior_votes)?;
 
-			// Optional: Ensure that the user did not already vote for the concern (design decision)
-			Self::add_vote_concern(id, concern, proposal, proposer);
+			Self::add_vote_concern(id.clone(), concern, proposal, proposer);
+			Self::deposit_event(Event::<T>::ConcernVoted(<Round>::get(), id, concern, proposal));
 		}
 
 		/// As an identified user, vote for a proposal
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
+		#[weight = T::WeightInfo::vote_proposal()]
 		fn vote_proposal(origin, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
-			// Ensure that the pallet is in the appropriate state
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
 			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
-			// Ensure that the proposal exists
-			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&proposal);
-			ensure!(proposer != IdentityId::<T>::default(),
-				Error::<T>::ProposalNotExistant
-			);
+			ensure!(T::TallyAlgorithm::scheme() != VotingScheme::RankedChoice, Error::<T>::WrongVotingScheme);
+			ensure!(T::RevealPeriod::get() == T::BlockNumber::from(0), Error::<T>::CommitRevealRequired);
 			// Ensure the identity level is high enough to vote.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ProposeVoteIdentityLevel::get().into(),
-					Error::<T>::IdentityLevelTooLow
-			);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
 			// Ensure the user has not surpassed the vote limit per user
-			ensure!(<ProposalVotes<T>>::get(&id).len() < T::ProposeVoteMaxPerIdentifiedUser::get().into(),
-					Error::<T>::UserProposalVoteLimitReached
+			let votes: Vec<ProposalCID> = <ProposalVotes<T>>::get(<Round>::get(), &id);
+			ensure!(votes.len() < T::ProposeVoteMaxPerIdentifiedUser::get().into(),
+					Error::<T>::VoteLimitReached
 			);
+			// Ensure that the proposal exists and the identity can vote for it
+			let proposer: IdentityId<T> = Self::ensure_votable(&id, &proposal)?;
+			// Pay the vote with voice credits
+			let prior_votes: u32 = votes.iter().filter(|v| **v == proposal).count() as u32;
+			ensure!(prior_votes == 0 || T::TallyAlgorithm::repeated_votes(), Error::<T>::AlreadyVoted);
+			Self::consume_voice_credits(&id, prior_votes)?;
+
+			Self::add_vote_proposal(id.clone(), proposal, proposer);
+			Self::deposit_event(Event::<T>::ProposalVoted(<Round>::get(), id, proposal));
+		}
 
-			// Optional: Ensure that the user did not already vote for the proposal (design decision)
-			Self::add_vote_proposal(id, proposal, proposer);
+		/// As an identified user, vote for a proposal and lock stake to increase the weight of
+		/// the vote. Every ConvictionVoteUnit of stake adds the multiplier of the conviction to
+		/// the votes, higher convictions lock the stake for more rounds.
+		#[weight = T::WeightInfo::vote_with_conviction()]
+		fn vote_with_conviction(origin, proposal: ProposalCID, stake: BalanceOf<T>, conviction: Conviction) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			ensure!(T::TallyAlgorithm::scheme() != VotingScheme::RankedChoice, Error::<T>::WrongVotingScheme);
+			ensure!(T::RevealPeriod::get() == T::BlockNumber::from(0), Error::<T>::CommitRevealRequired);
+			// Ensure that conviction voting is enabled and the stake adds at least one vote
+			let unit: BalanceOf<T> = T::ConvictionVoteUnit::get();
+			ensure!(unit > BalanceOf::<T>::from(0) && stake >= unit, Error::<T>::StakeTooLow);
+			ensure!(T::Currency::free_balance(&caller) >= stake, Error::<T>::InsufficientBalance);
+			// Ensure the identity level is high enough to vote.
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+			// Ensure the user has not surpassed the vote limit per user
+			let round: RoundIndex = <Round>::get();
+			let votes: Vec<ProposalCID> = <ProposalVotes<T>>::get(round, &id);
+			ensure!(votes.len() < T::ProposeVoteMaxPerIdentifiedUser::get().into(),
+					Error::<T>::VoteLimitReached
+			);
+			// Ensure that the proposal exists and the identity can vote for it
+			let proposer: IdentityId<T> = Self::ensure_votable(&id, &proposal)?;
+			// Pay the vote with voice credits
+			let prior_votes: u32 = votes.iter().filter(|v| **v == proposal).count() as u32;
+			ensure!(prior_votes == 0 || T::TallyAlgorithm::repeated_votes(), Error::<T>::AlreadyVoted);
+			Self::consume_voice_credits(&id, prior_votes)?;
+
+			let last_round: RoundIndex = round.saturating_add(conviction.lock_rounds() - 1);
+			Self::lock_vote_stake(&caller, stake, last_round);
+			let weight: u32 = (stake / unit).saturated_into::<u32>().saturating_mul(conviction.multiplier());
+			Self::add_vote_proposal(id.clone(), proposal, proposer.clone());
+			Self::add_vote_weight(&proposer, proposal, weight);
+			Self::record_receipt(&id, proposal, weight);
+			Self::deposit_event(Event::<T>::VoteStakeLocked(caller, stake, last_round));
+			Self::deposit_event(Event::<T>::ProposalVoted(round, id, proposal));
 		}
 
-		/*
-		#[weight = 10_000]
-		fn test_identity_level(origin) {
+		/// As an identified user, let another identity vote on your behalf. In every round in which
+		/// you do not vote yourself, your vote counts once for the ballot of the delegate.
+		#[weight = T::WeightInfo::delegate(T::MaxDelegationDepth::get())]
+		fn delegate(origin, to: IdentityId<T>) {
 			let caller = ensure_signed(origin)?;
-			let identity: IdentityId<T> = caller;
-			let identity_level : IdentityLevel = 0;
-			let level: IdentityLevel = T::Identity::get_identity_level(identity).unwrap_or(identity_level);
-			debug::info!("IdentityLevel: {:?}", level);
-		}*/
-	}
-}
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+			Self::ensure_identity_level(&to, 1)?;
 
-impl<T: Trait> Module<T> {
-	/// Add concern to storage and update relevant storage values
-	fn add_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID) {
-		// Create proper Concern and add it to the users list of concerns
-		let document = Concern::new(concern.clone(), proposal.clone());
-		<Concerns<T>>::mutate(&id, |user_concerns| {
-			user_concerns.push(document);
-		});
-		// Add mapping from (ConcernCID, ProposalCid) to identity
-		ConcernToIdentity::<T>::insert((&concern, &proposal), &id);
-		// Increment total concern count
-		<ConcernCount>::mutate(|cc| *cc += 1);
-	}
+			// Ensure that the delegation chain starting at the delegate is short and acyclic
+			let mut current: IdentityId<T> = to.clone();
+			let mut depth: u32 = 1;
 
-	fn add_council_poll(mut winners: VecDeque<ProposalWinner<IdentityId<T>>>) {
-		let mut tickets: Vec<Ticket> = Vec::new();
-		let transit_time: T::BlockNumber = T::CouncilVoteRoundDuration::get();
+			loop {
+				ensure!(current != id && depth <= T::MaxDelegationDepth::get(), Error::<T>::InvalidDelegation);
 
-		// Add every proposal and its concerns to a freshly created council poll
-		for winner in winners.iter_mut() {
-			let mut documents: Vec<DocumentCID> = Vec::new();
-			documents.push(winner.proposal.clone());
-			documents.append(&mut winner.concerns);
+				match <Delegations<T>>::get(&current) {
+					Some(next) => current = next,
+					None => break,
+				}
 
-			// TODO: Better error handling
-			if let Ok(ticket) = T::Council::add_poll(documents, transit_time) {
-				tickets.push(ticket);
+				depth += 1;
+			}
+
+			let previous: Option<IdentityId<T>> = <Delegations<T>>::get(&id);
+			ensure!(previous.as_ref() == Some(&to)
+				|| (<Delegators<T>>::decode_len(&to).unwrap_or(0) as u32) < T::MaxDelegators::get(),
+				Error::<T>::TooManyDelegators
+			);
+
+			if let Some(previous) = previous {
+				Self::remove_delegator(&previous, &id);
 			}
+
+			<Delegators<T>>::mutate(&to, |delegators| delegators.push(id.clone()));
+			<Delegations<T>>::insert(&id, &to);
+			Self::deposit_event(Event::<T>::Delegated(id, to));
 		}
 
-		CouncilVoteTickets::put(tickets);
-	}
+		/// As a delegator, revoke the delegation
+		#[weight = T::WeightInfo::undelegate()]
+		fn undelegate(origin) {
+			let caller = ensure_signed(origin)?;
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			let delegate: IdentityId<T> = <Delegations<T>>::take(&id).ok_or(Error::<T>::NotDelegating)?;
+			Self::remove_delegator(&delegate, &id);
+			Self::deposit_event(Event::<T>::Undelegated(id));
+		}
 
-	/// Add proposal to storage and update relevant storage values
-	fn add_proposal(id: IdentityId<T>, proposal: ProposalCID) {
-		// Create proper Proposal and add it to the users list of proposals
-		let document = Proposal::new(proposal.clone());
-		<Proposals<T>>::mutate(&id, |user_proposals| {
-			user_proposals.push(document);
-		});
-		// Add mapping from proposalCID to identity
-		ProposalToIdentity::<T>::insert(&proposal, &id);
-		// Increment total proposal count
-		<ProposalCount>::mutate(|pc| *pc += 1);
-	}
+		/// Remove the expired conviction locks of an account
+		#[weight = T::WeightInfo::unlock(Conviction::Locked6x.lock_rounds())]
+		fn unlock(origin, target: T::AccountId) {
+			ensure_signed(origin)?;
+			let round: RoundIndex = <Round>::get();
+			let mut locks: Vec<(RoundIndex, BalanceOf<T>)> = <ConvictionLocks<T>>::get(&target);
+			locks.retain(|(last_round, _)| *last_round >= round);
+			let remaining: BalanceOf<T> = locks.iter().map(|(_, stake)| *stake).max()
+				.unwrap_or_else(|| BalanceOf::<T>::from(0));
+
+			if locks.is_empty() {
+				T::Currency::remove_lock(CONVICTION_LOCK_ID, &target);
+				<ConvictionLocks<T>>::remove(&target);
+			} else {
+				T::Currency::set_lock(CONVICTION_LOCK_ID, &target, remaining, WithdrawReasons::all());
+				<ConvictionLocks<T>>::insert(&target, locks);
+			}
+
+			Self::deposit_event(Event::<T>::VoteStakeUnlocked(target, remaining));
+		}
+
+		/// As an identified user, vote for several proposals at once. The votes count towards
+		/// ProposeVoteMaxPerIdentifiedUser. Either every vote is cast or none.
+		#[weight = T::WeightInfo::vote_batch(approvals.len() as u32)]
+		fn vote_batch(origin, approvals: Vec<ProposalCID>) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			ensure!(T::TallyAlgorithm::scheme() != VotingScheme::RankedChoice, Error::<T>::WrongVotingScheme);
+			ensure!(T::RevealPeriod::get() == T::BlockNumber::from(0), Error::<T>::CommitRevealRequired);
+			// Ensure the identity level is high enough to vote.
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+			Self::do_vote_batch(id, approvals)?;
+		}
+
+		/// As an identified user, rank up to MaxRankedChoices proposals in the order of preference.
+		/// Only available if the runtime uses the RankedChoice scheme, one ballot per voting round.
+		#[weight = T::WeightInfo::vote_ranked(ranking.len() as u32)]
+		fn vote_ranked(origin, ranking: Vec<ProposalCID>) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			ensure!(T::TallyAlgorithm::scheme() == VotingScheme::RankedChoice, Error::<T>::WrongVotingScheme);
+			ensure!(T::RevealPeriod::get() == T::BlockNumber::from(0), Error::<T>::CommitRevealRequired);
+			// Ensure the identity level is high enough to vote
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+			Self::do_vote_ranked(id, ranking)?;
+		}
+
+		/// As an identified user, commit to your proposal votes before the reveal period of the
+		/// VotePropose phase. The commitment is the hash of the SCALE encoded (votes, salt).
+		/// Only available if RevealPeriod is not 0.
+		#[weight = T::WeightInfo::commit_votes()]
+		fn commit_votes(origin, commitment: T::Hash) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			ensure!(T::RevealPeriod::get() > T::BlockNumber::from(0), Error::<T>::CommitRevealDisabled);
+			ensure!(!Self::in_reveal_period(), Error::<T>::WrongVotingPeriod);
+			// Ensure the identity level is high enough to vote and the identity did not commit yet
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+			let round: RoundIndex = <Round>::get();
+			ensure!(!<VoteCommitments<T>>::contains_key(round, &id), Error::<T>::AlreadyVoted);
+
+			<VoteCommitments<T>>::insert(round, &id, commitment);
+			Self::deposit_event(Event::<T>::VotesCommitted(round, id));
+		}
+
+		/// As an identified user, reveal the committed votes during the reveal period. The votes
+		/// are cast like vote_batch (or vote_ranked for the RankedChoice scheme).
+		#[weight = T::WeightInfo::reveal_votes(votes.len() as u32)]
+		fn reveal_votes(origin, votes: Vec<ProposalCID>, salt: [u8; 32]) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the state machine is running and in the appropriate state
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			ensure!(T::RevealPeriod::get() > T::BlockNumber::from(0), Error::<T>::CommitRevealDisabled);
+			ensure!(Self::in_reveal_period(), Error::<T>::WrongVotingPeriod);
+			// Ensure that the votes match the commitment
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			let round: RoundIndex = <Round>::get();
+			ensure!(<VoteCommitments<T>>::get(round, &id) == Some(T::Hashing::hash_of(&(&votes, &salt))),
+				Error::<T>::CommitmentMismatch
+			);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+
+			if T::TallyAlgorithm::scheme() == VotingScheme::RankedChoice {
+				Self::do_vote_ranked(id.clone(), votes)?;
+			} else {
+				Self::do_vote_batch(id.clone(), votes)?;
+			}
+
+			<VoteCommitments<T>>::remove(round, &id);
+			Self::deposit_event(Event::<T>::VotesRevealed(round, id));
+		}
+
+		/// Cast an anonymous ballot. Eligibility is proven by the AnonymityProvider of the
+		/// runtime, the ballot is not linked to an identity. Anonymous ballots are unsigned,
+		/// carry no voice credits, can't be delegated and are not rewarded.
+		#[weight = T::WeightInfo::vote_anonymous(votes.len() as u32)]
+		fn vote_anonymous(origin, votes: Vec<ProposalCID>, nullifier: Nullifier,
+			proof: <T::AnonymityProvider as AnonymityProvider>::Proof
+		) {
+			ensure_none(origin)?;
+			Self::ensure_anonymous_ballot(&votes, &nullifier, &proof)?;
+			let round: RoundIndex = <Round>::get();
+
+			for proposal in votes.iter() {
+				let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(proposal);
+				Self::add_vote_weight(&proposer, *proposal, 1);
+			}
+
+			<AnonymousNullifiers>::insert(round, &nullifier, true);
+			Self::deposit_event(Event::<T>::AnonymousBallotCast(round, votes));
+		}
+
+		/// As the council or root, set the explanation of an error of any pallet in a language.
+		/// `module` and `error` are the indices of DispatchError::Module. The message is either
+		/// a short UTF-8 text or the CID of a document containing the explanation.
+		#[weight = T::WeightInfo::set_error_explanation(message.len() as u32)]
+		fn set_error_explanation(origin, module: u8, error: u8, language: LanguageCode, message: Vec<u8>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(message.len() <= T::MaxErrorExplanationLength::get() as usize,
+				Error::<T>::ExplanationTooLong
+			);
+
+			let version: u32 = <ErrorExplanations>::get((module, error, language))
+				.map_or(1, |explanation| explanation.version.saturating_add(1));
+			<ErrorExplanations>::insert((module, error, language), ErrorExplanation::new(version, message));
+			Self::deposit_event(Event::<T>::ErrorExplanationUpdated(module, error, language, version));
+		}
+
+		/// As the council or root, remove the explanation of an error in a language
+		#[weight = T::WeightInfo::remove_error_explanation()]
+		fn remove_error_explanation(origin, module: u8, error: u8, language: LanguageCode) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+
+			if let Some(explanation) = <ErrorExplanations>::take((module, error, language)) {
+				Self::deposit_event(
+					Event::<T>::ErrorExplanationUpdated(module, error, language, explanation.version)
+				);
+			}
+		}
+
+		/// As the council or root, move the scheduled state transit of the current phase to another
+		/// block, e.g. to extend or shorten a live phase during incidents
+		#[weight = T::WeightInfo::set_next_transit()]
+		fn set_next_transit(origin, when: T::BlockNumber) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			ensure!(!<TransitInProgress>::get(), Error::<T>::TransitInProgress);
+			ensure!(<State>::get() != States::Uninitialized, Error::<T>::WrongState);
+			ensure!(when > frame_system::Module::<T>::block_number(), Error::<T>::TransitNotInFuture);
+
+			Self::schedule_state_transit(when, <State>::get())?;
+			Self::deposit_event(Event::<T>::NextTransitSet(when));
+		}
+
+		/// As the council or root, set the duration of a phase. Applies when the phase starts
+		/// the next time. None restores the configured duration.
+		#[weight = T::WeightInfo::set_phase_duration()]
+		fn set_phase_duration(origin, phase: States, duration: Option<T::BlockNumber>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(phase != States::Uninitialized, Error::<T>::InvalidPhase);
+			ensure!(duration.map_or(true, |d| d > T::BlockNumber::from(0)), Error::<T>::ParameterOutOfBounds);
+
+			<PhaseDurations<T>>::mutate(phase, |d| *d = duration);
+			Self::deposit_event(Event::<T>::PhaseDurationSet(phase, duration));
+		}
+
+		/// As the council or root, set how many documents can be submitted in the Propose or
+		/// Concern phase. The cap must not exceed the configured cap, which bounds the weights.
+		/// None restores the configured cap.
+		#[weight = T::WeightInfo::set_submission_cap()]
+		fn set_submission_cap(origin, phase: States, cap: Option<u32>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			let max: u32 = match phase {
+				States::Propose => T::ProposeCap::get(),
+				States::Concern => T::ConcernCap::get(),
+				_ => return Err(Error::<T>::InvalidPhase.into()),
+			};
+			ensure!(cap.map_or(true, |c| c > 0 && c <= max), Error::<T>::ParameterOutOfBounds);
+
+			<SubmissionCaps>::mutate(phase, |c| *c = cap);
+			Self::deposit_event(Event::<T>::SubmissionCapSet(phase, cap));
+		}
+
+		/// As the council or root, set how many proposals of a category can be submitted per
+		/// round, so a single topic cannot consume the entire ProposeCap. None removes the cap.
+		#[weight = T::WeightInfo::set_category_cap()]
+		fn set_category_cap(origin, category: CategoryId, cap: Option<u32>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(category < T::ProposalCategories::get(), Error::<T>::InvalidMetadata);
+			ensure!(cap.map_or(true, |c| c > 0 && c <= T::ProposeCap::get()), Error::<T>::ParameterOutOfBounds);
+
+			<CategoryCaps>::mutate(category, |c| *c = cap);
+			Self::deposit_event(Event::<T>::CategoryCapSet(category, cap));
+		}
+
+		/// As an identified user, confirm or reject a milestone of a project in its confirmation
+		/// round. The same identity level as for proposal votes is required.
+		#[weight = T::WeightInfo::vote_milestone()]
+		fn vote_milestone(origin, project: ProjectID, milestone: MilestoneIndex, confirm: bool) {
+			let caller = ensure_signed(origin)?;
+			ensure!(!<Paused>::get(), Error::<T>::Paused);
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_identity_level(&id, T::ProposeVoteIdentityLevel::get())?;
+			Self::ensure_not_penalized(&id)?;
+			let (end, _, _) = <MilestoneConfirmations<T>>::get(project, milestone)
+				.ok_or(Error::<T>::NoMilestoneConfirmation)?;
+			ensure!(frame_system::Module::<T>::block_number() < end, Error::<T>::NoMilestoneConfirmation);
+			ensure!(!<MilestoneVoters<T>>::get((project, milestone), &id), Error::<T>::AlreadyVoted);
+
+			<MilestoneVoters<T>>::insert((project, milestone), &id, true);
+			<MilestoneConfirmations<T>>::mutate(project, milestone, |round| {
+				if let Some((_, confirmations, rejections)) = round {
+					if confirm {
+						*confirmations = confirmations.saturating_add(1);
+					} else {
+						*rejections = rejections.saturating_add(1);
+					}
+				}
+			});
+			Self::deposit_event(Event::<T>::MilestoneVoted(project, milestone, id, confirm));
+		}
+
+		/// As the council or root, set the share of the votes a document requires to be accepted
+		/// in the VotePropose or VoteConcern phase. None restores the configured threshold.
+		#[weight = T::WeightInfo::set_acceptance_threshold()]
+		fn set_acceptance_threshold(origin, phase: States, threshold: Option<Permill>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			ensure!(phase == States::VotePropose || phase == States::VoteConcern, Error::<T>::InvalidPhase);
+
+			<AcceptanceThresholds>::mutate(phase, |t| *t = threshold);
+			Self::deposit_event(Event::<T>::AcceptanceThresholdSet(phase, threshold));
+		}
+
+		/// Record that a proposal is retrievable on IPFS. Submitted by the off-chain worker,
+		/// the payload is signed by an off-chain authority (checked in validate_unsigned).
+		#[weight = T::WeightInfo::attest_availability()]
+		fn attest_availability(origin, payload: DocumentPayload<T::Public>, _signature: T::Signature) {
+			ensure_none(origin)?;
+			Self::ensure_attestable(payload.round, &payload.document)?;
+			<ProposalAvailability<T>>::insert(&payload.document, frame_system::Module::<T>::block_number());
+			Self::deposit_event(Event::<T>::ProposalAvailable(payload.round, payload.document));
+		}
+
+		/// Record that an IPFS node pinned a winning proposal or an accepted concern.
+		/// Submitted by the off-chain worker, the payload is signed by an off-chain authority
+		/// (checked in validate_unsigned).
+		#[weight = T::WeightInfo::confirm_pin()]
+		fn confirm_pin(origin, payload: DocumentPayload<T::Public>, _signature: T::Signature) {
+			ensure_none(origin)?;
+			Self::ensure_pinnable(payload.round, &payload.document)?;
+			<PinnedDocuments<T>>::insert(&payload.document, frame_system::Module::<T>::block_number());
+			Self::deposit_event(Event::<T>::DocumentPinned(payload.round, payload.document));
+		}
+
+		/// As the council or root, replace the accounts whose off-chain worker keys may attest
+		/// availabilities and pins
+		#[weight = T::WeightInfo::set_offchain_authorities(authorities.len() as u32)]
+		fn set_offchain_authorities(origin, authorities: Vec<T::AccountId>) {
+			T::CouncilOrigin::ensure_origin(origin)?;
+			<OffchainAuthorities<T>>::put(&authorities);
+			Self::deposit_event(Event::<T>::OffchainAuthoritiesSet(authorities));
+		}
+
+		/*
+		#[weight = 10_000]
+		fn test_identity_level(origin) {
+			let caller = ensure_signed(origin)?;
+			let identity: IdentityId<T> = caller;
+			let identity_level : IdentityLevel = 0;
+			let level: IdentityLevel = T::Identity::get_identity_level(identity).unwrap_or(identity_level);
+			debug::info!("IdentityLevel: {:?}", level);
+		}*/
+	}
+}
+
+/// Identifier of the lock that backs conviction votes
+const CONVICTION_LOCK_ID: LockIdentifier = *b"superorg";
+/// Prefix of the named scheduler ids of the reward distribution batches
+const REWARD_DISTRIBUTION_ID: &[u8] = b"superorganism/rewards";
+/// Prefix of the named scheduler ids of the state transits
+const STATE_TRANSIT_ID: &[u8] = b"superorganism/transit";
+/// Prefix of the offchain index keys of the round digests. Key: (prefix, round).encode()
+pub const ROUND_DIGEST_KEY: &[u8] = b"superorganism/round";
+
+impl<T: Trait> Module<T> {
+	/// Duration of a phase. The duration set by governance takes precedence.
+	pub fn phase_duration(phase: States) -> T::BlockNumber {
+		<PhaseDurations<T>>::get(phase).unwrap_or_else(|| match phase {
+			States::Uninitialized => T::BlockNumber::from(0),
+			States::Propose => T::ProposeRoundDuration::get(),
+			States::VotePropose => T::ProposeVoteDuration::get(),
+			States::Concern => T::ConcernRoundDuration::get(),
+			States::VoteConcern => T::ConcernVoteDuration::get(),
+			States::VoteCouncil => T::CouncilVoteRoundDuration::get(),
+		})
+	}
+
+	/// How many documents can be submitted in the Propose or Concern phase.
+	/// The cap set by governance takes precedence.
+	pub fn submission_cap(phase: States) -> u32 {
+		<SubmissionCaps>::get(phase).unwrap_or_else(|| match phase {
+			States::Concern => T::ConcernCap::get(),
+			_ => T::ProposeCap::get(),
+		})
+	}
+
+	/// Share of the votes a document requires to be accepted in the VotePropose or VoteConcern
+	/// phase. The threshold set by governance takes precedence.
+	pub fn acceptance_threshold(phase: States) -> Permill {
+		<AcceptanceThresholds>::get(phase).unwrap_or_else(|| match phase {
+			States::VoteConcern => T::ConcernVoteAcceptanceMin::get(),
+			_ => T::ProposeVoteAcceptanceMin::get(),
+		})
+	}
+
+	/// Ensure that the availability of a proposal of the running proposal round can be attested
+	fn ensure_attestable(round: RoundIndex, proposal: &ProposalCID) -> DispatchResult {
+		ensure!(!<Paused>::get(), Error::<T>::Paused);
+		ensure!(round == <Round>::get() && <State>::get() == States::Propose, Error::<T>::WrongState);
+		ensure!(<ProposalToIdentity<T>>::contains_key(proposal), Error::<T>::UnknownProposal);
+		ensure!(!<ProposalAvailability<T>>::contains_key(proposal), Error::<T>::ProposalAlreadyAvailable);
+		Ok(())
+	}
+
+	/// Ensure that a document is a winning proposal or an accepted concern of the current round
+	/// that was not pinned yet
+	fn ensure_pinnable(round: RoundIndex, document: &DocumentCID) -> DispatchResult {
+		ensure!(round == <Round>::get(), Error::<T>::WrongState);
+		ensure!(<ProposalWinners<T>>::get(round).iter()
+			.any(|winner| winner.proposal == *document || winner.concerns.contains(document)),
+			Error::<T>::DocumentNotPinnable
+		);
+		ensure!(!<PinnedDocuments<T>>::contains_key(document), Error::<T>::DocumentAlreadyPinned);
+		Ok(())
+	}
+
+	/// Get the aggregated governance participation of an identity
+	pub fn history_of(identity: IdentityId<T>) -> GovernanceHistory {
+		let mut history: GovernanceHistory = <History<T>>::get(&identity);
+		history.reviews_performed = T::Identity::get_review_count(&identity);
+		history
+	}
+
+	/// Get the identity level and the published metadata of a proposer
+	pub fn proposer_info(identity: IdentityId<T>) -> ProposerInfo {
+		let metadata = T::Identity::get_metadata(&identity).unwrap_or_default();
+
+		ProposerInfo {
+			level: T::Identity::get_identity_level(&identity),
+			display_hash: metadata.display_hash,
+			contact: metadata.contact,
+		}
+	}
+
+	/// Is the current phase in its final ProposerQuietPeriod blocks?
+	fn in_quiet_period() -> bool {
+		let quiet_period: T::BlockNumber = T::ProposerQuietPeriod::get();
+
+		if quiet_period == T::BlockNumber::from(0u32) {
+			return false;
+		}
+
+		let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+		current_block + quiet_period >= <NextTransit<T>>::get()
+	}
+
+	/// Was the vote of an identity for a proposal counted in a round?
+	/// (only the last HistoryDepth rounds are available)
+	pub fn verify_vote(identity: IdentityId<T>, round: RoundIndex, proposal: ProposalCID) -> bool {
+		<VoteReceipts<T>>::get(round, &identity)
+			.map_or(false, |receipt| receipt.votes.iter().any(|(cid, _)| *cid == proposal))
+	}
+
+	/// Get an overview of the current round. If an identity is supplied, the view contains
+	/// the actions the identity can perform in the current phase.
+	pub fn round_snapshot(identity: Option<IdentityId<T>>) -> RoundSnapshot<IdentityId<T>, T::BlockNumber> {
+		let round: RoundIndex = <Round>::get();
+
+		RoundSnapshot {
+			round,
+			state: <State>::get(),
+			paused: <Paused>::get(),
+			next_transit: <NextTransit<T>>::get(),
+			// The counters are reset by the tally, the documents stay until the round ends
+			proposal_count: <Proposals<T>>::iter_prefix_values(round).map(|p| p.len() as u32).sum(),
+			concern_count: <Concerns<T>>::iter_prefix_values(round).map(|c| c.len() as u32).sum(),
+			winners: <ProposalWinners<T>>::get(round).into_iter().collect(),
+			eligibility: identity.map(|id| Self::eligibility_of(&id)),
+		}
+	}
+
+	/// Get one page of the proposals of the current round, ordered by submission.
+	/// Pass the cursor of the previous page to fetch the next page.
+	pub fn active_proposals(start: Option<Cursor>, limit: u32) -> Page<(IdentityId<T>, Proposal)> {
+		let round: RoundIndex = <Round>::get();
+		let proposals: Vec<(Cursor, (IdentityId<T>, Proposal))> = <Proposals<T>>::iter_prefix(round)
+			.flat_map(|(id, proposals)| proposals.into_iter().map(move |p| (id.clone(), p)))
+			.map(|(id, proposal)| (Cursor::new(round, proposal.index), (id, proposal)))
+			.collect();
+
+		Page::paginate(proposals, start, limit)
+	}
+
+	/// Get one page of the concerns of the current round, ordered by submission.
+	/// Pass the cursor of the previous page to fetch the next page.
+	pub fn active_concerns(start: Option<Cursor>, limit: u32) -> Page<(IdentityId<T>, Concern)> {
+		let round: RoundIndex = <Round>::get();
+		let concerns: Vec<(Cursor, (IdentityId<T>, Concern))> = <Concerns<T>>::iter_prefix(round)
+			.flat_map(|(id, concerns)| concerns.into_iter().map(move |c| (id.clone(), c)))
+			.map(|(id, concern)| (Cursor::new(round, concern.index), (id, concern)))
+			.collect();
+
+		Page::paginate(concerns, start, limit)
+	}
+
+	/// Get the winning proposals of the current round or of a finished round
+	/// (only the last HistoryDepth rounds are available)
+	pub fn winners_of(round: RoundIndex) -> Vec<ProposalWinner<IdentityId<T>>> {
+		if round == <Round>::get() {
+			<ProposalWinners<T>>::get(round).into_iter().collect()
+		} else {
+			<RoundHistory<T>>::get(round).winners
+		}
+	}
+
+	/// Determine the actions the identity of an account can perform in the current phase
+	pub fn voter_eligibility(account: &T::AccountId) -> Eligibility {
+		Self::eligibility_of(&T::Identity::get_identity_id(account))
+	}
+
+	/// Determine the actions an identity can perform in the current phase
+	fn eligibility_of(identity: &IdentityId<T>) -> Eligibility {
+		let round: RoundIndex = <Round>::get();
+		let level: IdentityLevel = T::Identity::get_identity_level(identity);
+		let running: bool = !<Paused>::get();
+		let mut eligibility = Eligibility::default();
+
+		match <State>::get() {
+			States::Propose => {
+				eligibility.can_submit = running && <ProposalCount>::get() < Self::submission_cap(States::Propose)
+					&& <Proposals<T>>::decode_len(round, identity).unwrap_or(0)
+						< Self::identity_cap(identity, T::ProposeIdentifiedUserCap::get());
+				eligibility.requires_deposit = level < T::ProposeIdentityLevel::get().into();
+			},
+			States::VotePropose => {
+				eligibility.can_vote = running && level >= T::ProposeVoteIdentityLevel::get().into()
+					&& <ProposalVotes<T>>::decode_len(round, identity).unwrap_or(0)
+						< T::ProposeVoteMaxPerIdentifiedUser::get().into();
+				eligibility.voice_credits = Self::voice_credits_of(identity);
+			},
+			States::Concern => {
+				eligibility.can_submit = running && <ConcernCount>::get() < Self::submission_cap(States::Concern)
+					&& <Concerns<T>>::decode_len(round, identity).unwrap_or(0)
+						< Self::identity_cap(identity, T::ConcernIdentifiedUserCap::get());
+				eligibility.requires_deposit = level < T::ConcernIdentityLevel::get().into();
+			},
+			States::VoteConcern => {
+				eligibility.can_vote = running && level >= T::ConcernVoteIdentityLevel::get().into()
+					&& <ConcernVotes<T>>::decode_len(round, identity).unwrap_or(0)
+						< T::ConcernVoteMaxPerIdentifiedUser::get().into();
+				eligibility.voice_credits = Self::voice_credits_of(identity);
+			},
+			States::Uninitialized | States::VoteCouncil => {},
+		}
+
+		eligibility
+	}
+
+	/// Get the voice credits an identity has left in the current voting round
+	pub fn voice_credits_of(identity: &IdentityId<T>) -> u32 {
+		let (epoch, credits) = <VoiceCredits<T>>::get(identity);
+
+		if epoch == <CreditEpoch>::get() {
+			credits
+		} else {
+			T::VoiceCreditsPerRound::get()
+		}
+	}
+
+	/// Ensure that the identity exists and its level is at least the required level
+	fn ensure_identity_level(identity: &IdentityId<T>, required: u8) -> DispatchResult {
+		let level: IdentityLevel = T::Identity::get_identity_level(identity);
+		ensure!(level > 0, Error::<T>::NoIdentity);
+		ensure!(level >= required, Error::<T>::IdentityLevelTooLow);
+		Ok(())
+	}
+
+	/// Ensure that an anonymous ballot is valid and that its voter is eligible and did not
+	/// vote anonymously yet during the current round
+	fn ensure_anonymous_ballot(votes: &[ProposalCID], nullifier: &Nullifier,
+		proof: &<T::AnonymityProvider as AnonymityProvider>::Proof
+	) -> DispatchResult {
+		ensure!(!<Paused>::get(), Error::<T>::Paused);
+		ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+		ensure!(T::TallyAlgorithm::scheme() != VotingScheme::RankedChoice, Error::<T>::WrongVotingScheme);
+		ensure!(T::RevealPeriod::get() == T::BlockNumber::from(0), Error::<T>::CommitRevealRequired);
+		ensure!(!votes.is_empty() && votes.len() <= T::ProposeVoteMaxPerIdentifiedUser::get().into(),
+			Error::<T>::VoteLimitReached
+		);
+		// Every proposal receives at most one vote per ballot
+		ensure!(votes.iter().enumerate().all(|(idx, cid)| !votes[..idx].contains(cid)), Error::<T>::AlreadyVoted);
+
+		for proposal in votes.iter() {
+			ensure!(<ProposalToIdentity<T>>::contains_key(proposal), Error::<T>::UnknownProposal);
+			ensure!(!T::ExcludeUnavailableProposals::get() || <ProposalAvailability<T>>::contains_key(proposal),
+				Error::<T>::ProposalUnavailable
+			);
+		}
+
+		let round: RoundIndex = <Round>::get();
+		ensure!(!<AnonymousNullifiers>::get(round, nullifier), Error::<T>::AlreadyVoted);
+		ensure!(T::AnonymityProvider::verify(round, nullifier, votes, proof), Error::<T>::InvalidAnonymityProof);
+		Ok(())
+	}
+
+	/// Is the VotePropose phase in its final RevealPeriod blocks?
+	fn in_reveal_period() -> bool {
+		let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+		current_block + T::RevealPeriod::get() >= <NextTransit<T>>::get()
+	}
+
+	/// Cast votes for several proposals. Either every vote is cast or none.
+	fn do_vote_batch(id: IdentityId<T>, approvals: Vec<ProposalCID>) -> DispatchResult {
+		// Ensure the user does not surpass the vote limit per user
+		let round: RoundIndex = <Round>::get();
+		let mut votes: Vec<ProposalCID> = <ProposalVotes<T>>::get(round, &id);
+		ensure!(votes.len().saturating_add(approvals.len()) <= T::ProposeVoteMaxPerIdentifiedUser::get().into(),
+			Error::<T>::VoteLimitReached
+		);
+
+		// Verify every vote and its cost before any vote is written
+		let mut credits: u32 = Self::voice_credits_of(&id);
+		let mut proposers: Vec<IdentityId<T>> = Vec::with_capacity(approvals.len());
+
+		for proposal in approvals.iter() {
+			proposers.push(Self::ensure_votable(&id, proposal)?);
+			let prior_votes: u32 = votes.iter().filter(|v| *v == proposal).count() as u32;
+			ensure!(prior_votes == 0 || T::TallyAlgorithm::repeated_votes(), Error::<T>::AlreadyVoted);
+			credits = credits.checked_sub(T::TallyAlgorithm::vote_cost(prior_votes))
+				.ok_or(Error::<T>::InsufficientVoiceCredits)?;
+			votes.push(*proposal);
+		}
+
+		<VoiceCredits<T>>::insert(&id, (<CreditEpoch>::get(), credits));
+
+		for (proposal, proposer) in approvals.into_iter().zip(proposers) {
+			Self::add_vote_proposal(id.clone(), proposal, proposer);
+			Self::deposit_event(Event::<T>::ProposalVoted(round, id.clone(), proposal));
+		}
+
+		Ok(())
+	}
+
+	/// Cast a ranked ballot
+	fn do_vote_ranked(id: IdentityId<T>, ranking: Vec<ProposalCID>) -> DispatchResult {
+		// Ensure that the ranking is valid
+		ensure!(!ranking.is_empty() && ranking.len() <= T::MaxRankedChoices::get() as usize,
+			Error::<T>::InvalidRanking
+		);
+		ensure!(ranking.iter().enumerate().all(|(idx, cid)| !ranking[..idx].contains(cid)),
+			Error::<T>::InvalidRanking
+		);
+		// Ensure the identity did not vote yet
+		ensure!(!<ProposalVotes<T>>::contains_key(<Round>::get(), &id), Error::<T>::AlreadyVoted);
+		// Ensure that every ranked proposal exists and the identity can vote for it
+		for proposal in ranking.iter() {
+			Self::ensure_votable(&id, proposal)?;
+		}
+		// A ballot costs the same as a single vote
+		Self::consume_voice_credits(&id, 0)?;
+
+		Self::add_ranked_vote(id.clone(), ranking.clone());
+		Self::deposit_event(Event::<T>::RankingSubmitted(<Round>::get(), id, ranking));
+		Ok(())
+	}
+
+	/// Ensure that the proposal exists and can receive a vote from the identity.
+	/// Returns the proposer.
+	fn ensure_votable(identity: &IdentityId<T>, proposal: &ProposalCID) -> Result<IdentityId<T>, Error<T>> {
+		let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(proposal);
+		ensure!(proposer != IdentityId::<T>::default(), Error::<T>::UnknownProposal);
+		ensure!(!T::ExcludeUnavailableProposals::get() || <ProposalAvailability<T>>::contains_key(proposal),
+			Error::<T>::ProposalUnavailable
+		);
+		// Ensure that proposers do not vote for their own proposals during the quiet period
+		ensure!(*identity != proposer || !Self::in_quiet_period(), Error::<T>::ProposerQuietPeriod);
+		Ok(proposer)
+	}
+
+	/// Consume the voice credits for a vote. Mints the voice credits of the current voting round
+	/// if the identity did not vote yet during this voting round.
+	fn consume_voice_credits(identity: &IdentityId<T>, prior_votes: u32) -> DispatchResult {
+		let cost: u32 = T::TallyAlgorithm::vote_cost(prior_votes);
+		let credits: u32 = Self::voice_credits_of(identity).checked_sub(cost)
+			.ok_or(Error::<T>::InsufficientVoiceCredits)?;
+		<VoiceCredits<T>>::insert(identity, (<CreditEpoch>::get(), credits));
+		Ok(())
+	}
+
+	/// Fabricate a round with synthetic identities and run through every state of it
+	#[cfg(feature = "testnet")]
+	fn do_simulate_round(proposals: u32, votes: u32, concerns: u32) -> DispatchResult {
+		use frame_support::{Hashable, sp_runtime::traits::TrailingZeroInput};
+
+		let round: RoundIndex = <Round>::get();
+		let identity = |kind: &[u8], index: u32| -> IdentityId<T> {
+			(b"simulation", kind, index).using_encoded(|seed| {
+				IdentityId::<T>::decode(&mut TrailingZeroInput::new(seed)).unwrap_or_default()
+			})
+		};
+
+		// Propose
+		let mut cids: Vec<ProposalCID> = Vec::new();
+		for i in 0..proposals {
+			let cid: ProposalCID = (b"simulation/proposal", round, i).blake2_256();
+			Self::add_proposal(identity(b"proposer", i), cid, ProposalMetadata::default());
+			cids.push(cid);
+		}
+
+		Self::do_state_transit()?;
+
+		if cids.is_empty() {
+			return Ok(());
+		}
+
+		// VotePropose
+		for i in 0..votes {
+			let idx: usize = i as usize % cids.len();
+			Self::add_vote_proposal(identity(b"voter", i), cids[idx], identity(b"proposer", idx as u32));
+		}
+
+		Self::do_state_transit()?;
+
+		if <State>::get() != States::Concern {
+			return Ok(());
+		}
+
+		// Concern
+		let winners: Vec<ProposalCID> = <ProposalWinners<T>>::get(&round).iter()
+			.map(|winner| winner.proposal)
+			.collect();
+		let mut concern_cids: Vec<(ConcernCID, ProposalCID)> = Vec::new();
+
+		for i in 0..concerns {
+			let proposal: ProposalCID = winners[i as usize % winners.len()];
+			let cid: ConcernCID = (b"simulation/concern", round, i).blake2_256();
+			Self::add_concern(identity(b"concerner", i), cid, proposal);
+			concern_cids.push((cid, proposal));
+		}
+
+		Self::do_state_transit()?;
+
+		// VoteConcern
+		if !concern_cids.is_empty() {
+			for i in 0..votes {
+				let idx: usize = i as usize % concern_cids.len();
+				let (concern, proposal) = concern_cids[idx];
+				Self::add_vote_concern(identity(b"voter", i), concern, proposal, identity(b"concerner", idx as u32));
+			}
+
+			Self::do_state_transit()?;
+		}
+
+		// VoteCouncil
+		Self::do_state_transit()
+	}
+
+	#[cfg(not(feature = "testnet"))]
+	fn do_simulate_round(_proposals: u32, _votes: u32, _concerns: u32) -> DispatchResult {
+		Err(Error::<T>::SimulationDisabled.into())
+	}
+
+	/// Accrue a reward for an account, it is paid out by the next reward distribution batches
+	fn accrue_reward(account: &T::AccountId, reward: BalanceOf<T>) {
+		<PendingRewards<T>>::mutate(account, |pending| *pending += reward);
+		<PendingRewardTotal<T>>::mutate(|total| *total = total.saturating_add(reward));
+	}
+
+	/// Schedule the first reward distribution batch if none is running yet
+	fn start_reward_distribution() {
+		if <RewardBatch>::get().is_some() {
+			return;
+		}
+
+		// Retry up to one batch of rewards whose transfer failed during the last distribution
+		for (account, reward) in <FailedRewards<T>>::drain().take(T::RewardBatchSize::get() as usize) {
+			<PendingRewards<T>>::mutate(&account, |pending| *pending = pending.saturating_add(reward));
+		}
+
+		if <PendingRewards<T>>::iter().next().is_some() {
+			// TODO: Appropriate Error or handling.
+			let _ = Self::schedule_reward_batch(0);
+		}
+	}
+
+	/// Schedule a reward distribution batch for the next block
+	fn schedule_reward_batch(batch: u32) -> DispatchResult {
+		let next_block: T::BlockNumber = frame_system::Module::<T>::block_number() + T::BlockNumber::from(1u32);
+
+		if T::Scheduler::schedule_named(
+			(REWARD_DISTRIBUTION_ID, batch).encode(),
+			DispatchTime::At(next_block),
+			None,
+			LOWEST_PRIORITY,
+			Root.into(),
+			Call::distribute_rewards().into(),
+		).is_err() {
+			return Err(Error::<T>::SchedulingFailed.into());
+		};
+
+		RewardBatch::put(batch);
+		Ok(())
+	}
+
+	/// Pay out up to RewardBatchSize accrued rewards from the governance pot and schedule the
+	/// next batch if required. Rewards that cannot be transferred (e.g. below the existential
+	/// deposit) are moved to FailedRewards and retried by the next distribution. If the pot cannot
+	/// cover a reward, the distribution stops and the remaining rewards stay pending.
+	fn do_distribute_rewards() -> DispatchResult {
+		let mut total_reward_paid = BalanceOf::<T>::from(0);
+		let pot: T::AccountId = Self::treasury_account();
+		let batch: Vec<(T::AccountId, BalanceOf<T>)> = <PendingRewards<T>>::iter()
+			.take(T::RewardBatchSize::get() as usize)
+			.collect();
+
+		let payout = pay_rewards(
+			batch,
+			|| T::Currency::free_balance(&pot).saturating_sub(T::Currency::minimum_balance()),
+			|account, reward| T::Currency::transfer(&pot, account, reward, ExistenceRequirement::KeepAlive).is_ok(),
+		);
+
+		for (account, reward) in payout.paid.iter() {
+			<PendingRewards<T>>::remove(account);
+			<PendingRewardTotal<T>>::mutate(|total| *total = total.saturating_sub(*reward));
+			total_reward_paid += *reward;
+			Self::deposit_event(Event::<T>::RewardPaid(account.clone(), *reward));
+		}
+
+		// Every processed reward leaves the queue, otherwise failing rewards would fill every batch
+		for (account, reward) in payout.failed {
+			<PendingRewards<T>>::remove(&account);
+			<FailedRewards<T>>::mutate(&account, |failed| *failed = failed.saturating_add(reward));
+		}
+
+		Self::deposit_event(Event::<T>::RewardsDistributed(payout.paid.len() as u32, total_reward_paid));
+
+		if let Some(available) = payout.exhausted {
+			Self::deposit_event(Event::<T>::RewardPotExhausted(<PendingRewardTotal<T>>::get(), available));
+			RewardBatch::kill();
+			return Ok(());
+		}
+
+		if <PendingRewards<T>>::iter().next().is_some() {
+			let batch: u32 = <RewardBatch>::get().unwrap_or(0).saturating_add(1);
+			return Self::schedule_reward_batch(batch);
+		}
+
+		RewardBatch::kill();
+		Ok(())
+	}
+
+	/// Add concern to storage and update relevant storage values
+	fn add_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID) {
+		// Create proper Concern and add it to the users list of concerns
+		let index: u32 = <NextConcernIndex>::mutate(|ci| { *ci += 1; *ci - 1 });
+		let document = Concern::new(concern, proposal, index);
+		<Concerns<T>>::mutate(<Round>::get(), &id, |user_concerns| {
+			user_concerns.push(document);
+		});
+		// Add mapping from (ConcernCID, ProposalCid) to identity
+		ConcernToIdentity::<T>::insert((&concern, &proposal), &id);
+		// Increment total concern count
+		<ConcernCount>::mutate(|cc| *cc += 1);
+		<History<T>>::mutate(&id, |h| h.concerns_raised += 1);
+		Self::notify_proposer(concern, proposal);
+	}
+
+	/// Get the proposer of a winning proposal of the current round
+	fn winner_proposer(proposal: &ProposalCID) -> Option<IdentityId<T>> {
+		<ProposalWinners<T>>::get(<Round>::get()).into_iter()
+			.find(|winner| winner.proposal == *proposal)
+			.map(|winner| winner.proposer)
+	}
+
+	/// Inform the proposer about a concern against its proposal, if the proposer opted in
+	fn notify_proposer(concern: ConcernCID, proposal: ProposalCID) {
+		let proposer: IdentityId<T> = match Self::winner_proposer(&proposal) {
+			Some(proposer) => proposer,
+			None => return,
+		};
+
+		if !<ConcernNotificationsEnabled<T>>::get(&proposer) {
+			return;
+		}
+
+		<PendingConcernNotifications<T>>::insert(&proposer, (concern, proposal), true);
+		// Index the event by the proposer, so clients can subscribe to it
+		let topic: T::Hash = T::Hashing::hash_of(&proposer);
+		let event = <T as Trait>::Event::from(Event::<T>::ConcernFiled(proposer, concern, proposal));
+		frame_system::Module::<T>::deposit_event_indexed(&[topic], event.into());
+	}
+
+	/// Get one page of the concerns that were filed against the proposals of an identity
+	/// during this round, ordered by submission
+	pub fn open_concerns_of(identity: IdentityId<T>, start: Option<Cursor>, limit: u32) -> Page<Concern> {
+		let round: RoundIndex = <Round>::get();
+		let proposals: Vec<ProposalCID> = <ProposalWinners<T>>::get(&round).iter()
+			.filter(|winner| winner.proposer == identity)
+			.map(|winner| winner.proposal)
+			.collect();
+
+		if proposals.is_empty() {
+			return Page::paginate(Vec::new(), start, limit);
+		}
+
+		let concerns: Vec<(Cursor, Concern)> = <Concerns<T>>::iter_prefix(round)
+			.flat_map(|(_, concerns)| concerns.into_iter())
+			.filter(|concern| proposals.contains(&concern.associated_proposal))
+			.map(|concern| (Cursor::new(round, concern.index), concern))
+			.collect();
+
+		Page::paginate(concerns, start, limit)
+	}
+
+	/// Remove concern from storage and update relevant storage values
+	fn remove_concern_entry(id: &IdentityId<T>, concern: &ConcernCID, proposal: &ProposalCID) {
+		let mut votes: u32 = 0;
+		<Concerns<T>>::mutate(<Round>::get(), id, |user_concerns| {
+			if let Some(pos) = user_concerns.iter().position(|el| {
+				el.concern == *concern && el.associated_proposal == *proposal
+			}) {
+				votes = user_concerns.remove(pos).votes;
+			}
+		});
+		ConcernToIdentity::<T>::remove((concern, proposal));
+		// Remove the notification of the proposer
+		if let Some(proposer) = Self::winner_proposer(proposal) {
+			<PendingConcernNotifications<T>>::remove(&proposer, (concern, proposal));
+		}
+		// Votes for removed concerns must not dilute the vote ratio of the remaining concerns
+		<ConcernVoteCount>::mutate(|vc| *vc = vc.saturating_sub(votes));
+		<ConcernCount>::mutate(|cc| *cc = cc.saturating_sub(1));
+	}
+
+	fn add_council_poll(mut winners: VecDeque<ProposalWinner<IdentityId<T>>>) {
+		let mut tickets: Vec<Ticket> = Vec::new();
+		let transit_time: T::BlockNumber = Self::phase_duration(States::VoteCouncil);
+
+		// Add every proposal and its concerns to a freshly created council poll
+		for winner in winners.iter_mut() {
+			let mut documents: Vec<DocumentCID> = Vec::new();
+			documents.push(winner.proposal);
+			documents.append(&mut winner.concerns);
+
+			// TODO: Better error handling
+			if let Ok(ticket) = T::Council::add_poll(documents, transit_time) {
+				tickets.push(ticket);
+			}
+		}
+
+		CouncilVoteTickets::put(tickets);
+	}
+
+	/// Submit a proposal of an account. Transits into the proposal voting state as soon as the
+	/// proposal limit is reached. A failing transit reverts the submission.
+	#[transactional]
+	fn do_propose(caller: T::AccountId, proposal: Vec<u8>, metadata: ProposalMetadata) -> DispatchResult {
+		// Ensure that the state machine is running and in the appropriate state
+		ensure!(!<Paused>::get(), Error::<T>::Paused);
+		ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
+		// Ensure that the proposal is a valid CID
+		let proposal: ProposalCID = cid_digest(&proposal).ok_or(Error::<T>::InvalidCid)?;
+		// Ensure that the maximum proposal count was not reached yet
+		ensure!(<ProposalCount>::get() < Self::submission_cap(States::Propose), Error::<T>::ProposalLimitReached);
+		// Ensure that the maximum proposal count of the category was not reached yet
+		if let Some(category) = metadata.category {
+			ensure!(<CategoryCaps>::get(category)
+				.map_or(true, |cap| <CategoryProposalCount>::get(<Round>::get(), category) < cap),
+				Error::<T>::CategoryLimitReached
+			);
+		}
+		// Check whether the identity level is high enough to propose without a deposit.
+		let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+		Self::ensure_not_penalized(&id)?;
+		ensure!(!T::Identity::is_banned(&id), Error::<T>::IdentityBanned);
+		let identified: bool = T::Identity::get_identity_level(&id) >= T::ProposeIdentityLevel::get().into();
+		// Ensure the user has not surpassed the proposal limit per user
+		ensure!(<Proposals<T>>::get(<Round>::get(), &id).len()
+				< Self::identity_cap(&id, T::ProposeIdentifiedUserCap::get()),
+				Error::<T>::UserProposalLimitReached
+		);
+		// Ensure that the proposal was not already submitted
+		let round: RoundIndex = <Round>::get();
+		ensure!(<ProposalByCid<T>>::get(&proposal).map_or(true, |(_, cursor)| cursor.round != round),
+				Error::<T>::DuplicateProposal
+		);
+		// Reserve the bond or the deposit, they are returned after the proposal voting round
+		if identified {
+			let bond: BalanceOf<T> = T::ProposeBond::get();
+			T::Currency::reserve(&caller, bond).map_err(|_| Error::<T>::InsufficientBalance)?;
+			ProposalBonds::<T>::insert(&proposal, (caller, bond));
+		} else {
+			let deposit: BalanceOf<T> = T::UserProposeFee::get();
+			T::Currency::reserve(&caller, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+			UserProposeDeposits::<T>::insert(&proposal, (caller, deposit));
+		}
+		let has_metadata: bool = metadata != ProposalMetadata::default();
+		Self::add_proposal(id.clone(), proposal, metadata.clone());
+		Self::deposit_event(Event::<T>::ProposalSubmitted(round, id, proposal));
+
+		if has_metadata {
+			Self::deposit_event(Event::<T>::ProposalMetadataSet(proposal, metadata));
+		}
+
+		// Early state transit when the proposal limit was reached
+		if <ProposalCount>::get() >= Self::submission_cap(States::Propose) {
+			Self::do_state_transit()?;
+		}
+
+		Ok(())
+	}
+
+	/// Add proposal to storage and update relevant storage values
+	fn add_proposal(id: IdentityId<T>, proposal: ProposalCID, metadata: ProposalMetadata) {
+		// Create proper Proposal and add it to the users list of proposals
+		let index: u32 = <NextProposalIndex>::mutate(|pi| { *pi += 1; *pi - 1 });
+		let round: RoundIndex = <Round>::get();
+		let mut document = Proposal::new(proposal, index);
+
+		if let Some(category) = metadata.category {
+			<CategoryProposalCount>::mutate(round, category, |count| *count += 1);
+		}
+
+		document.metadata = metadata;
+		<Proposals<T>>::mutate(round, &id, |user_proposals| {
+			user_proposals.push(document);
+		});
+		// Add mapping from proposalCID to identity
+		ProposalToIdentity::<T>::insert(&proposal, &id);
+		ProposalByCid::<T>::insert(&proposal, (&id, Cursor::new(round, index)));
+		// Increment total proposal count
+		<ProposalCount>::mutate(|pc| *pc += 1);
+		<History<T>>::mutate(&id, |h| h.proposals_submitted += 1);
+	}
+
+	/// Remove proposal from storage and update relevant storage values
+	fn remove_proposal_entry(id: &IdentityId<T>, proposal: &ProposalCID) {
+		let round: RoundIndex = <Round>::get();
+		let removed: Option<Proposal> = <Proposals<T>>::mutate(round, id, |user_proposals| {
+			user_proposals.iter().position(|el| el.proposal == *proposal).map(|pos| user_proposals.remove(pos))
+		});
+		let votes: u32 = removed.as_ref().map_or(0, |p| p.votes);
+
+		if let Some(category) = removed.and_then(|p| p.metadata.category) {
+			<CategoryProposalCount>::mutate(round, category, |count| *count = count.saturating_sub(1));
+		}
+
+		ProposalToIdentity::<T>::remove(proposal);
+		ProposalByCid::<T>::remove(proposal);
+		<ProposalAvailability<T>>::remove(proposal);
+		// Votes for removed proposals must not dilute the vote ratio of the remaining proposals
+		<ProposalVoteCount>::mutate(|vc| *vc = vc.saturating_sub(votes));
+		<ProposalCount>::mutate(|pc| *pc = pc.saturating_sub(1));
+	}
+
+	/// Replace the CID of a proposal and move all references to the new CID
+	fn amend_proposal_entry(id: &IdentityId<T>, old_proposal: &ProposalCID, new_proposal: ProposalCID) {
+		let round: RoundIndex = <Round>::get();
+		let mut index: u32 = 0;
+		<Proposals<T>>::mutate(round, id, |user_proposals| {
+			if let Some(document) = user_proposals.iter_mut().find(|el| el.proposal == *old_proposal) {
+				document.proposal = new_proposal;
+				index = document.index;
+			}
+		});
+
+		ProposalToIdentity::<T>::remove(old_proposal);
+		ProposalToIdentity::<T>::insert(&new_proposal, id);
+		ProposalByCid::<T>::remove(old_proposal);
+		ProposalByCid::<T>::insert(&new_proposal, (id, Cursor::new(round, index)));
+		// The new document has to be retrieved again
+		<ProposalAvailability<T>>::remove(old_proposal);
+
+		if let Some(bond) = ProposalBonds::<T>::take(old_proposal) {
+			ProposalBonds::<T>::insert(&new_proposal, bond);
+		}
+
+		if let Some(deposit) = UserProposeDeposits::<T>::take(old_proposal) {
+			UserProposeDeposits::<T>::insert(&new_proposal, deposit);
+		}
+	}
+
+	/// Slash the bond and the user deposit of a proposal. Returns the slashed amount.
+	fn slash_proposal_deposits(proposal: &ProposalCID) -> BalanceOf<T> {
+		let mut slashed = BalanceOf::<T>::from(0);
+
+		if let Some((account, bond)) = ProposalBonds::<T>::take(proposal) {
+			slashed += Self::slash_into_pot(&account, bond);
+		}
+
+		if let Some((account, deposit)) = UserProposeDeposits::<T>::take(proposal) {
+			slashed += Self::slash_into_pot(&account, deposit);
+		}
+
+		slashed
+	}
+
+	/// Slash reserved funds of an account into the governance pot. Returns the slashed amount.
+	fn slash_into_pot(account: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		let (imbalance, unslashed) = T::Currency::slash_reserved(account, amount);
+		T::Currency::resolve_creating(&Self::treasury_account(), imbalance);
+		amount - unslashed
+	}
+
+	/// Lock an identity out for IdentifiedUserPenality blocks and record the penalty in its
+	/// history. Returns the block at which the lockout expires.
+	fn impose_lockout(identity: &IdentityId<T>) -> T::BlockNumber {
+		let until: T::BlockNumber = frame_system::Module::<T>::block_number()
+			.saturating_add(T::IdentifiedUserPenality::get());
+		<PenalizedUntil<T>>::insert(identity, until);
+		<History<T>>::mutate(identity, |h| h.penalties_received += 1);
+		until
+	}
+
+	/// Submission cap of an identity per round. Reputation raises the base cap up to
+	/// ReputationCapMultiplier times, a penalty halves it for RecentPenaltyPeriod blocks
+	/// after the lockout expired.
+	fn identity_cap(id: &IdentityId<T>, base: u8) -> usize {
+		let base: usize = base.into();
+		let penalized_recently: bool = <PenalizedUntil<T>>::get(id).map_or(false, |until|
+			frame_system::Module::<T>::block_number() < until.saturating_add(T::RecentPenaltyPeriod::get())
+		);
+
+		if penalized_recently {
+			return (base / 2).max(1);
+		}
+
+		let step: Score = T::ReputationCapStep::get();
+
+		if step == 0 {
+			return base;
+		}
+
+		let max_multiplier: Score = T::ReputationCapMultiplier::get().max(1).into();
+		let multiplier: Score = (T::Reputation::reputation_of(id) / step).saturating_add(1).min(max_multiplier);
+		base.saturating_mul(multiplier as usize)
+	}
+
+	/// Ensure that the identity is not locked out by a penalty
+	fn ensure_not_penalized(id: &IdentityId<T>) -> DispatchResult {
+		let now: T::BlockNumber = frame_system::Module::<T>::block_number();
+		ensure!(<PenalizedUntil<T>>::get(id).map_or(true, |until| now >= until),
+			Error::<T>::IdentityPenalized
+		);
+		ensure!(!T::Identity::is_disputed(id), Error::<T>::IdentityDisputed);
+		Ok(())
+	}
+
+	/// Account of the governance pot
+	pub fn treasury_account() -> T::AccountId {
+		T::TreasuryModuleId::get().into_account()
+	}
+
+	/// Return the bond and the user deposit of a proposal. Returns the refunded amount.
+	fn refund_proposal_deposits(proposal: &ProposalCID) -> BalanceOf<T> {
+		let mut refunded = BalanceOf::<T>::from(0);
+
+		if let Some((account, bond)) = ProposalBonds::<T>::take(proposal) {
+			refunded += bond - T::Currency::unreserve(&account, bond);
+		}
+
+		if let Some((account, deposit)) = UserProposeDeposits::<T>::take(proposal) {
+			refunded += deposit - T::Currency::unreserve(&account, deposit);
+		}
+
+		refunded
+	}
 
 	/// Add vote to storage and update relevant storage values
 	fn add_vote_proposal(id: IdentityId<T>, proposal: ProposalCID, proposer: IdentityId<T>) {
 		// Add proposalCID to id votes
-		<ProposalVotes<T>>::mutate(&id, |vote_cids| {
-			vote_cids.push(proposal.clone())
+		let round: RoundIndex = <Round>::get();
+		<ProposalVotes<T>>::mutate(round, &id, |vote_cids| {
+			vote_cids.push(proposal)
 		});
 		// Increment vote count within Proposal structure
-		<Proposals<T>>::mutate(&proposer, |proposals| {
+		<Proposals<T>>::mutate(round, &proposer, |proposals| {
 			if let Some(p) = proposals.iter_mut().find(|el| el.proposal == proposal) {
 				p.votes += 1;
 			}
@@ -546,16 +2713,135 @@ impl<T: Trait> Module<T> {
 		// Increment total vote count
 		// TODO: Overflow handling
 		<ProposalVoteCount>::mutate(|vc| *vc += 1);
+		<History<T>>::mutate(&id, |h| h.votes_cast += 1);
+		T::Reputation::record(&id, ReputationAction::Voted);
+		Self::record_receipt(&id, proposal, 1);
+	}
+
+	/// Add votes for a proposal to the receipt of the identity
+	fn record_receipt(id: &IdentityId<T>, proposal: ProposalCID, votes: u32) {
+		<VoteReceipts<T>>::mutate(<Round>::get(), id, |receipt| {
+			receipt.get_or_insert_with(VoteReceipt::default).add(proposal, votes)
+		});
+	}
+
+	/// Remove a delegator from the delegators of its delegate
+	fn remove_delegator(delegate: &IdentityId<T>, delegator: &IdentityId<T>) {
+		<Delegators<T>>::mutate_exists(delegate, |delegators| {
+			if let Some(list) = delegators {
+				list.retain(|id| id != delegator);
+
+				if list.is_empty() {
+					*delegators = None;
+				}
+			}
+		});
+	}
+
+	/// Check the requirements of a direct proposal vote. Delegators are checked at tally time.
+	fn is_eligible_voter(id: &IdentityId<T>) -> bool {
+		Self::ensure_identity_level(id, T::ProposeVoteIdentityLevel::get()).is_ok()
+			&& Self::ensure_not_penalized(id).is_ok()
+			&& !T::Identity::is_banned(id)
+	}
+
+	/// Collect the eligible identities that did not vote in the round and are represented by the
+	/// delegate, directly or through delegates that did not vote either. At most MaxDelegationDepth
+	/// delegations are followed and at most MaxDelegators identities are visited.
+	fn represented_delegators(delegate: &IdentityId<T>, round: RoundIndex) -> Vec<IdentityId<T>> {
+		let mut represented: Vec<IdentityId<T>> = Vec::new();
+		let mut visited: Vec<IdentityId<T>> = Vec::new();
+		let mut level: Vec<IdentityId<T>> = Vec::new();
+		level.push(delegate.clone());
+
+		for _ in 0..T::MaxDelegationDepth::get() {
+			let mut next: Vec<IdentityId<T>> = Vec::new();
+
+			for delegator in level.iter().flat_map(|id| <Delegators<T>>::get(id)) {
+				// Delegators that voted themselves are represented by their own ballot
+				if delegator == *delegate || visited.contains(&delegator)
+					|| <ProposalVotes<T>>::contains_key(round, &delegator)
+				{
+					continue;
+				}
+
+				if visited.len() as u32 >= T::MaxDelegators::get() {
+					return represented;
+				}
+
+				visited.push(delegator.clone());
+
+				if Self::is_eligible_voter(&delegator) {
+					represented.push(delegator.clone());
+				}
+
+				next.push(delegator);
+			}
+
+			level = next;
+		}
+
+		represented
+	}
+
+	/// Add the weight of locked stake to the votes of a proposal
+	fn add_vote_weight(proposer: &IdentityId<T>, proposal: ProposalCID, weight: u32) {
+		<Proposals<T>>::mutate(<Round>::get(), proposer, |proposals| {
+			if let Some(p) = proposals.iter_mut().find(|el| el.proposal == proposal) {
+				p.votes = p.votes.saturating_add(weight);
+			}
+		});
+		<ProposalVoteCount>::mutate(|vc| *vc = vc.saturating_add(weight));
+	}
+
+	/// Lock stake of an account until the end of `last_round`. The locks of several votes
+	/// overlap, the largest stake that is still locked determines the lock.
+	fn lock_vote_stake(account: &T::AccountId, stake: BalanceOf<T>, last_round: RoundIndex) {
+		let round: RoundIndex = <Round>::get();
+		let locked: BalanceOf<T> = <ConvictionLocks<T>>::mutate(account, |locks| {
+			// Keep at most one entry per round, which bounds the entries by the longest lock period
+			locks.retain(|(last, _)| *last >= round);
+
+			match locks.iter_mut().find(|(last, _)| *last == last_round) {
+				Some(lock) => lock.1 = lock.1.max(stake),
+				None => locks.push((last_round, stake)),
+			}
+
+			locks.iter().map(|(_, stake)| *stake).max().unwrap_or(stake)
+		});
+		T::Currency::set_lock(CONVICTION_LOCK_ID, account, locked, WithdrawReasons::all());
+	}
+
+	/// Add a ranked ballot to storage. The first preference counts as vote of the proposal.
+	fn add_ranked_vote(id: IdentityId<T>, ranking: Vec<ProposalCID>) {
+		let round: RoundIndex = <Round>::get();
+
+		if let Some(first) = ranking.first() {
+			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(first);
+			<Proposals<T>>::mutate(round, &proposer, |proposals| {
+				if let Some(p) = proposals.iter_mut().find(|el| el.proposal == *first) {
+					p.votes += 1;
+				}
+			});
+		}
+
+		// The ballot is kept in the order of preference, the tally evaluates it
+		<VoteReceipts<T>>::insert(round, &id, VoteReceipt::new(&ranking, false));
+		<ProposalVotes<T>>::insert(round, &id, ranking);
+		<ProposalVoteCount>::mutate(|vc| *vc += 1);
+		<History<T>>::mutate(&id, |h| h.votes_cast += 1);
+		T::Reputation::record(&id, ReputationAction::Voted);
 	}
 
 	/// Add vote to storage and update relevant storage values
 	fn add_vote_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID, proposer: IdentityId<T>) {
 		// Add concernCID to id votes
-		<ConcernVotes<T>>::mutate(&id, |vote_cids| {
-			vote_cids.push(concern.clone())
+		let round: RoundIndex = <Round>::get();
+		<ConcernVotes<T>>::mutate(round, &id, |vote_cids| {
+			vote_cids.push(concern)
 		});
 		// Increment vote count within Concern structure
-		<Concerns<T>>::mutate(&proposer, |concerns| {
+		<Concerns<T>>::mutate(round, &proposer, |concerns| {
 			if let Some(p) = concerns.iter_mut().find(|el| {
 				el.concern == concern && el.associated_proposal == proposal
 			}) {
@@ -566,30 +2852,132 @@ impl<T: Trait> Module<T> {
 		// Increment total vote count
 		// TODO: Overflow handling
 		<ConcernVoteCount>::mutate(|vc| *vc += 1);
+		<History<T>>::mutate(&id, |h| h.votes_cast += 1);
+		T::Reputation::record(&id, ReputationAction::Voted);
 	}
 
-	/// Execute the state transit and schedule the next state transit
+	/// Check whether the outcome of a council poll is final: Either every council member has voted
+	/// or the quorum was reached and the votes agree with a supermajority. Abstentions count toward
+	/// the quorum, but not toward the supermajority.
+	fn council_poll_decided(ticket: &Ticket) -> bool {
+		let members: u32 = T::Council::member_count();
+		let result: Vec<(IdentityId<T>, bool)> = Self::council_votes(ticket).unwrap_or_default();
+		let votes: u32 = result.len() as u32;
+		let abstentions: u32 = T::Council::get_abstentions(ticket).iter()
+			.filter(|member| T::Council::is_member(member))
+			.count() as u32;
+		let participants: u32 = votes.saturating_add(abstentions);
+
+		if members == 0 || participants == 0 {
+			return false;
+		}
+
+		if participants >= members {
+			return true;
+		}
+
+		if votes == 0 || Permill::from_rational_approximation(participants, members) < T::CouncilEarlyTransitQuorum::get() {
+			return false;
+		}
+
+		let votes_no: u32 = result.iter().filter(|v| !v.1).count() as u32;
+		let votes_majority: u32 = votes_no.max(votes - votes_no);
+		Permill::from_rational_approximation(votes_majority, votes) >= T::CouncilEarlyTransitSupermajority::get()
+	}
+
+	/// Budget the winning proposals of the current round can request in total: RoundBudget,
+	/// capped by the funds of the treasury that funds the projects
+	fn available_round_budget() -> BudgetOf<T> {
+		T::Project::available_budget().min(T::RoundBudget::get())
+	}
+
+	/// Get the budget that a winning proposal requested in its metadata (zero if none)
+	fn requested_budget(round: RoundIndex, winner: &ProposalWinner<IdentityId<T>>) -> BudgetOf<T> {
+		<Proposals<T>>::get(round, &winner.proposer).iter()
+			.find(|proposal| proposal.proposal == winner.proposal)
+			.and_then(|proposal| proposal.metadata.budget)
+			.map_or_else(Default::default, |budget| budget.saturated_into())
+	}
+
+	/// Get the votes of a council poll that were cast by current council members.
+	/// Votes of removed members are not counted.
+	fn council_votes(ticket: &Ticket) -> Option<Vec<(IdentityId<T>, bool)>> {
+		T::Council::get_result(ticket)
+			.map(|result| result.into_iter().filter(|(member, _)| T::Council::is_member(member)).collect())
+	}
+
+	/// Check whether nobody submitted or voted anything during the current phase
+	fn phase_had_no_activity(state: States) -> bool {
+		match state {
+			States::Uninitialized => false,
+			States::Propose => <ProposalCount>::get() == 0,
+			States::VotePropose => <ProposalVoteCount>::get() == 0,
+			States::Concern => <ConcernCount>::get() == 0,
+			States::VoteConcern => <ConcernVoteCount>::get() == 0,
+			States::VoteCouncil => <CouncilVoteTickets>::get().iter().all(|ticket| {
+				T::Council::vote_count(ticket) == 0
+			}),
+		}
+	}
+
+	/// Execute the state transit exactly once per phase, regardless of whether it was triggered
+	/// by the scheduler, by root or by on_initialize. While a tally is running, the transit is
+	/// deferred until the tally finished, so tallies never overlap and the votes of a round are
+	/// not pruned before they were evaluated.
 	fn do_state_transit() -> DispatchResult {
+		ensure!(!<TransitInProgress>::get(), Error::<T>::TransitInProgress);
+
+		if <TallyCursor>::exists() {
+			DeferredTransit::put(true);
+			return Err(Error::<T>::TallyRunning.into());
+		}
+
+		let round: RoundIndex = <Round>::get();
+		let state: States = <State>::get();
+		let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+		ensure!(<LastTransit<T>>::get() != Some((round, state, current_block)),
+			Error::<T>::TransitAlreadyCompleted
+		);
+
+		TransitInProgress::put(true);
+		let result: DispatchResult = Self::rotate_state();
+		TransitInProgress::kill();
+
+		if result.is_ok() {
+			<LastTransit<T>>::put((round, state, current_block));
+		}
+
+		result
+	}
+
+	/// Execute the state transit and schedule the next state transit
+	fn rotate_state() -> DispatchResult {
 		let mut transit_time: T::BlockNumber = T::BlockNumber::from(0);
 
-		// TODO: Early state transit when the proposal limit was reached.
-		// TODO: Early state transition when every member of the council has voted.
-		// TODO: Make Scheduler named and cancel any scheduled state transits before adding new.
+		// Watchdog: Report phases that ended without any activity
+		let current_state: States = <State>::get();
+		if Self::phase_had_no_activity(current_state) {
+			let idle_count: u32 = <IdlePhaseCount>::mutate(current_state, |ic| {
+				*ic = ic.saturating_add(1);
+				*ic
+			});
+			Self::deposit_event(Event::<T>::PhaseHadNoActivity(current_state, idle_count));
+		}
+
 		// TODO: Change mutate to get, checks values, and change them at the end of this function
 		//			(verify first write last)
 		let newstate: States = <State>::mutate(|state| {
 			match state {
 				States::Uninitialized => {
 					*state = States::Propose;
-					transit_time = T::ProposeRoundDuration::get();
+					transit_time = Self::phase_duration(States::Propose);
 				},
 				States::Propose => {
 					// Only transit state if proposals exist
-					transit_time = T::ProposeRoundDuration::get();
-					for _ in <Proposals<T>>::iter() {
-						transit_time = T::ProposeVoteDuration::get();
+					transit_time = Self::phase_duration(States::Propose);
+					if <ProposalCount>::get() > 0 {
+						transit_time = Self::phase_duration(States::VotePropose);
 						*state = States::VotePropose;
-						break;
 					}
 				},
 				States::VotePropose => {
@@ -599,26 +2987,25 @@ impl<T: Trait> Module<T> {
 					// Start next proposal round if no proposal did receive enough votes
 					if <ProposalWinners<T>>::get(round).len() == 0 {
 						*state = States::Propose;
-						transit_time = T::ProposeRoundDuration::get();
-						if round == u8::MAX { Round::put(0); }
-						else { Round::put(round+1); }
+						transit_time = Self::phase_duration(States::Propose);
+						Self::incr_round();
 						return *state;
 					}
 
 					*state = States::Concern;
-					transit_time = T::ConcernRoundDuration::get();
+					transit_time = Self::phase_duration(States::Concern);
 				},
 				States::Concern => {
 					// Skip VoteConcern if no concerns exist
 					if <ConcernCount>::get() == 0 {
 						// Add every proposal and its concerns to a freshly created council poll
-						let round: u8 = <Round>::get();
+						let round: RoundIndex = <Round>::get();
 						let winners: VecDeque<ProposalWinner<IdentityId<T>>> = <ProposalWinners<T>>::get(&round);
 						Self::add_council_poll(winners);
 						*state = States::VoteCouncil;
-						transit_time = T::CouncilVoteRoundDuration::get();
+						transit_time = Self::phase_duration(States::VoteCouncil);
 					} else {
-						transit_time = T::ConcernVoteDuration::get();
+						transit_time = Self::phase_duration(States::VoteConcern);
 						*state = States::VoteConcern;
 					}
 				},
@@ -627,17 +3014,20 @@ impl<T: Trait> Module<T> {
 					let winners: VecDeque<ProposalWinner<IdentityId<T>>> = Self::evaluate_concern_votes();
 					// Add every proposal and its concerns to a freshly created council poll
 					Self::add_council_poll(winners);
-					transit_time = T::CouncilVoteRoundDuration::get();
+					transit_time = Self::phase_duration(States::VoteCouncil);
 					*state = States::VoteCouncil;
 				},
 				States::VoteCouncil => {
 					let round = <Round>::get();
 					let winners = <ProposalWinners<T>>::get(&round);
+					let mut decisions: Vec<(ProposalCID, bool)> = Vec::new();
+					let mut justifications: Vec<(ProposalCID, Vec<(IdentityId<T>, DocumentCID)>)> = Vec::new();
 
 					// Get voting result and evaluate vote percentage
 					for (idx, ticket) in <CouncilVoteTickets>::get().iter().enumerate() {
 						// TODO: Better error handling (error = ticket number not found in council)
-						if let Some(result) = T::Council::get_result(ticket) {
+						if let Some(result) = Self::council_votes(ticket) {
+							// Abstentions are not part of the result and don't dilute the Nay votes
 							let mut percentage_no = Permill::zero();
 							let mut votes_no: u32 = 0;
 
@@ -650,168 +3040,452 @@ impl<T: Trait> Module<T> {
 							}
 
 							// Spawn project from passed proposals
-							if percentage_no < T::CouncilAcceptConcernMinVotes::get() {
-								let _ = T::Project::spawn_project(winners[idx].clone());
+							let accepted: bool = percentage_no < T::CouncilAcceptConcernMinVotes::get();
+							decisions.push((winners[idx].proposal, accepted));
+							Self::deposit_event(Event::<T>::CouncilVoted(round, winners[idx].proposal, accepted));
+
+							if accepted {
+								let budget: BudgetOf<T> = Self::requested_budget(round, &winners[idx]);
+
+								if T::Project::spawn_project(winners[idx].clone(), budget).is_err() {
+									Self::deposit_event(Event::<T>::ProjectNotFunded(round, winners[idx].proposal, budget));
+								}
 							} else {
-								Event::<T>::CouncilDeniedProposal(winners[idx].clone(), result);
+								// Only the rejecting votes of current members count
+								let reasons: Vec<(IdentityId<T>, DocumentCID)> = T::Council::get_justifications(ticket)
+									.into_iter()
+									.filter(|(member, _)| result.iter().any(|(voter, accept)| voter == member && !accept))
+									.collect();
+								justifications.push((winners[idx].proposal, reasons.clone()));
+								Self::deposit_event(Event::<T>::CouncilDeniedProposal(winners[idx].clone(), result));
+								Self::deposit_event(Event::<T>::ProposalBlocked(round, winners[idx].proposal, reasons));
 							}
 						}
 					}
 
+					<RoundHistory<T>>::mutate(round, |result| {
+						result.winners = winners.iter().cloned().collect();
+						result.council_decisions = decisions;
+						result.council_justifications = justifications;
+					});
+
 					// increment round and rotate state
-					if round == u8::MAX { Round::put(0); }
-					else { Round::put(round+1); }
+					Self::incr_round();
 					*state = States::Propose;
-					transit_time = T::ProposeRoundDuration::get();
+					transit_time = Self::phase_duration(States::Propose);
 				}
 			}
 		*state
 		});
 
+		// Snapshot: Void the voice credits of the previous voting round
+		if newstate == States::VotePropose || newstate == States::VoteConcern {
+			<CreditEpoch>::mutate(|epoch| *epoch = epoch.wrapping_add(1));
+		}
+
 		let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
 		let next_state_transit: T::BlockNumber = current_block + transit_time;
+		Self::schedule_state_transit(next_state_transit, newstate)?;
+		Self::deposit_event(Event::<T>::StateRotated(newstate));
+		Ok(())
+	}
+
+	/// Cancel any previously scheduled state transit and schedule the next one
+	fn schedule_state_transit(when: T::BlockNumber, state: States) -> DispatchResult {
+		// The state transit might be enforced, in which case the scheduled transit is still pending
+		if let Some(id) = <ScheduledTransit>::take() {
+			let _ = T::Scheduler::cancel_named(id);
+		}
+
+		// The round, the state and the block identify the transit unambiguously. The block is
+		// required because a state can be repeated within a round (e.g. no proposals submitted)
+		let id: Vec<u8> = (STATE_TRANSIT_ID, <Round>::get(), state, when).encode();
 
-		if T::Scheduler::schedule(
-			DispatchTime::At(next_state_transit),
+		if T::Scheduler::schedule_named(
+			id.clone(),
+			DispatchTime::At(when),
 			None,
 			LOWEST_PRIORITY,
 			Root.into(),
 			Call::state_transit().into(),
 		).is_err() {
-			// Todo: Appropriate Error or handling.
-			return Err(DispatchError::Other("Setting anonymous scheduler for \"state_transit\" failed"));
+			return Err(Error::<T>::SchedulingFailed.into());
 		};
 
-		NextTransit::<T>::put(next_state_transit);
-		Self::deposit_event(Event::<T>::StateRotated(newstate));
+		ScheduledTransit::put(id);
+		NextTransit::<T>::put(when);
 		Ok(())
 	}
 
 	/// On state transit from VoteConcern, evaluate all concerns and votes and pay winners and correct voters.
 	fn evaluate_concern_votes() -> VecDeque<ProposalWinner<IdentityId<T>>> {
-		let total_votes: u32 = <ConcernVoteCount>::get();
-		let round: u8 = <Round>::get();
+		let round: RoundIndex = <Round>::get();
 		let mut winners: VecDeque<ProposalWinner<IdentityId<T>>> = <ProposalWinners<T>>::get(&round);
 		let mut total_reward_issued = BalanceOf::<T>::from(0);
 		let reward_propose: BalanceOf<T> = T::ConcernReward::get();
-		let reward_vote: BalanceOf<T> = T::ConcernVoteCorrectReward::get();
 
-		// Drain all Concerns and add winners into winner variable and into storage ProposalWinners
-		for (id, concerns) in <Concerns<T>>::drain() {
-			for concern in concerns.iter() {
-				// Here we inspect every single concern of a specific user. Add it if it won.
-				let mut vote_ratio = Permill::zero();
+		// Collect all Concerns of this round and let the tally algorithm determine the winners
+		let concerns: Vec<(IdentityId<T>, Concern)> = <Concerns<T>>::iter_prefix(round)
+			.flat_map(|(id, concerns)| concerns.into_iter().map(move |c| (id.clone(), c)))
+			.collect();
+		let votes: Vec<u32> = concerns.iter().map(|(_, c)| c.votes).collect();
 
-				if total_votes > 0 {
-					vote_ratio = Permill::from_rational_approximation(concern.votes, total_votes);
-				}
+		// Add winners into winner variable and into storage ProposalWinners
+		for (idx, _) in T::TallyAlgorithm::tally(&votes, Self::acceptance_threshold(States::VoteConcern)) {
+			let (id, concern) = &concerns[idx];
 
-				if vote_ratio >= T::ConcernVoteAcceptanceMin::get() {
-					if let Some(winner) = winners.iter_mut().find(|el| el.proposal == concern.associated_proposal) {
-						winner.concerns.push(concern.concern.clone());
+			if let Some(winner) = winners.iter_mut().find(|el| el.proposal == concern.associated_proposal) {
+				winner.concerns.push(concern.concern);
+				<History<T>>::mutate(id, |h| h.concerns_accepted += 1);
+				T::Reputation::record(id, ReputationAction::ConcernAccepted);
 
-						if T::Currency::deposit_into_existing(&T::Identity::get_address(&id), reward_propose).is_ok() {
-							total_reward_issued += reward_propose;
-						}
-					}
-				}
+				Self::accrue_reward(&T::Identity::get_address(id), reward_propose);
+				total_reward_issued += reward_propose;
 			}
 		}
 
-		// Drain all voters ProposalVotes and reward them if the proposal they voted for won
-		for (id, votes) in <ConcernVotes<T>>::drain() {
-			for _ in votes.iter().filter(|v| {
-				// Only count votes for winning proposals
-				for winner in winners.iter() {
-					for concern in winner.concerns.iter() {
-						if *concern == **v { return true; }
-					}
-				}
-				false
-			}) {
-				// TODO: When tx by identity is implemented, change to deposit_creating
-				// (since identity does not require to spend fees for tx,
-				// the account might not have been created on chain)
-				// TODO: Error handling
-				if T::Currency::deposit_into_existing(&T::Identity::get_address(&id), reward_vote).is_ok() {
-					total_reward_issued += reward_vote;
-				}
-			}
+		// Concerns are evaluated, pending notifications are obsolete
+		PendingConcernNotifications::<T>::drain().nth(usize::MAX);
+
+		// The concern round ended normally, return all deposits
+		for (_, (account, deposit)) in UserConcernDeposits::<T>::drain() {
+			T::Currency::unreserve(&account, deposit);
 		}
 
 		ProposalWinners::<T>::insert(round, winners.clone());
+		<RoundHistory<T>>::mutate(round, |result| {
+			result.concern_count = <ConcernCount>::get();
+			result.concern_votes = <ConcernVoteCount>::get();
+		});
 		// Clear ProposalToIdentity, ProposalVoteCount, ProposalCount
 		// Avoid collecting the iterator to avoid creating a new Vector
 		ConcernToIdentity::<T>::drain().nth(usize::MAX);
 		ConcernVoteCount::put(0);
 		ConcernCount::put(0);
-		Self::deposit_event(Event::<T>::TotalConcernReward(total_reward_issued));
+		NextConcernIndex::put(0);
+		// Reward all voters of this round if the concern they voted for won
+		Self::start_tally(round, States::VoteConcern, total_reward_issued);
 		return winners;
 	}
 
 
 	/// On state transit from VotePropose, evaluate all proposals and votes and pay correct voters.
 	fn evaluate_proposal_votes() {
-		let total_votes: u32 = <ProposalVoteCount>::get();
-		let round: u8 = <Round>::get();
+		let round: RoundIndex = <Round>::get();
 		let mut winners: Vec<ProposalWinner<IdentityId<T>>> = Vec::new();
-		let mut total_reward_issued = BalanceOf::<T>::from(0);
-		let reward: BalanceOf<T> = T::ProposeVoteCorrectReward::get();
 
-		// Drain all Proposals and put winners into winner variable and into storage ProposalWinners
-		for (id, proposals) in <Proposals<T>>::drain() {
-			for proposal in proposals.iter() {
-				// Here we inspect every single proposal of a specific user. Add it if it won.
-				let mut vote_ratio = Permill::zero();
+		// Collect all Proposals of this round and let the tally algorithm determine the winners
+		let proposals: Vec<(IdentityId<T>, Proposal)> = <Proposals<T>>::iter_prefix(round)
+			.flat_map(|(id, proposals)| proposals.into_iter().map(move |p| (id.clone(), p)))
+			.collect();
+		let mut votes: Vec<u32> = proposals.iter().map(|(_, p)| p.votes).collect();
+		let acceptance_min: Permill = Self::acceptance_threshold(States::VotePropose);
+		let index_of = |cid: &ProposalCID| proposals.iter().position(|(_, p)| p.proposal == *cid);
+
+		// Every eligible delegator that did not vote adds one vote to the ballot of its delegate:
+		// ranked ballots count once more, otherwise the first choice of the delegate gets a vote.
+		// Only voters are visited, the delegations of every voter are bounded by MaxDelegators.
+		let ranked: bool = T::TallyAlgorithm::scheme() == VotingScheme::RankedChoice;
+		let mut delegated: Vec<Vec<ProposalCID>> = Vec::new();
+
+		for (delegate, ballot) in <ProposalVotes<T>>::iter_prefix(round) {
+			let ballot: Vec<ProposalCID> = if ranked { ballot } else { ballot.into_iter().take(1).collect() };
+
+			for delegator in Self::represented_delegators(&delegate, round) {
+				<VoteReceipts<T>>::insert(round, &delegator, VoteReceipt::new(&ballot, true));
+				delegated.push(ballot.clone());
+			}
+		}
 
-				if total_votes > 0 {
-					vote_ratio = Permill::from_rational_approximation(proposal.votes, total_votes);
-				}
+		let results: Vec<(usize, Permill)> = if ranked {
+			// Translate the ranked proposals of every ballot into indices of `proposals`
+			let ballots: Vec<Vec<usize>> = <ProposalVotes<T>>::iter_prefix_values(round)
+				.chain(delegated.into_iter())
+				.map(|ranking| ranking.iter().filter_map(index_of).collect())
+				.collect();
+			T::TallyAlgorithm::tally_ranked(proposals.len(), &ballots, acceptance_min)
+		} else {
+			for idx in delegated.iter().flat_map(|ballot| ballot).filter_map(index_of) {
+				votes[idx] = votes[idx].saturating_add(1);
+			}
 
-				if vote_ratio >= T::ProposeVoteAcceptanceMin::get() {
-					let document = ProposalWinner::<IdentityId<T>>::new(
-						Vec::new(), id.clone(), proposal.proposal.clone(), vote_ratio
-					);
-					winners.push(document);
-				}
+			T::TallyAlgorithm::tally(&votes, acceptance_min)
+		};
+
+		// Higher ranked proposals are funded first, proposals that exceed the remaining budget
+		// of the round do not win
+		let mut results: Vec<(usize, Permill)> = results;
+		results.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| proposals[a.0].1.index.cmp(&proposals[b.0].1.index)));
+		let mut remaining_budget: BudgetOf<T> = Self::available_round_budget();
+
+		// Put winners into winner variable and into storage ProposalWinners
+		for (idx, vote_ratio) in results {
+			let (id, proposal) = &proposals[idx];
+			let budget: BudgetOf<T> = proposal.metadata.budget.map_or_else(Default::default, |b| b.saturated_into());
+
+			if budget > remaining_budget {
+				Self::deposit_event(Event::<T>::ProposalExceedsRoundBudget(round, proposal.proposal, budget));
+				continue;
 			}
+
+			remaining_budget -= budget;
+			let document = ProposalWinner::<IdentityId<T>>::new(
+				Vec::new(), id.clone(), proposal.proposal, vote_ratio
+			);
+			winners.push(document);
+			<History<T>>::mutate(id, |h| h.proposals_accepted += 1);
+			T::Reputation::record(id, ReputationAction::ProposalAccepted);
 		}
 
 		winners.sort_by(|a, b| a.vote_ratio.cmp(&b.vote_ratio));
 		ProposalWinners::<T>::insert(round, VecDeque::from(winners.clone()));
-		// Drain all voters ProposalVotes and reward them if the proposal they voted for won
-		for (id, votes) in <ProposalVotes<T>>::drain() {
-			for _ in votes.iter().filter(|v| {
-				// Only count votes for winning proposals
-				for winner in winners.iter() {
-					if winner.proposal == **v {
-						return true;
-					}
-				}
-				false
-			}) {
-				// TODO: When tx by identity is implemented, change to deposit_creating
-				// (since identity does not require to spend fees for tx,
-				// the account might not have been created on chain)
-				// TODO: Error handling
-				if T::Currency::deposit_into_existing(&T::Identity::get_address(&id), reward).is_ok() {
-					total_reward_issued += reward;
-				}
-			}
+		// The proposal round ended normally, return all bonds and deposits
+		for (_, (account, bond)) in ProposalBonds::<T>::drain() {
+			T::Currency::unreserve(&account, bond);
+		}
+
+		for (_, (account, deposit)) in UserProposeDeposits::<T>::drain() {
+			T::Currency::unreserve(&account, deposit);
 		}
 
+		<RoundHistory<T>>::mutate(round, |result| {
+			result.proposal_count = <ProposalCount>::get();
+			result.proposal_votes = <ProposalVoteCount>::get();
+		});
 		// Clear ProposalToIdentity, ProposalVoteCount, ProposalCount
 		// Avoid collecting the iterator to avoid creating a new Vector
 		ProposalToIdentity::<T>::drain().nth(usize::MAX);
+		<ProposalAvailability<T>>::drain().nth(usize::MAX);
+		// Unrevealed commitments are discarded
+		<VoteCommitments<T>>::remove_prefix(round);
+		<AnonymousNullifiers>::remove_prefix(round);
 		ProposalVoteCount::put(0);
 		ProposalCount::put(0);
-		Self::deposit_event(Event::<T>::TotalProposalReward(total_reward_issued));
+		NextProposalIndex::put(0);
+
+		// Reward all voters of this round if the proposal they voted for won. Without winners
+		// nobody is rewarded and the round is finished immediately, its votes are pruned.
+		if winners.is_empty() {
+			Self::deposit_event(Event::<T>::TotalProposalReward(BalanceOf::<T>::from(0)));
+		} else {
+			Self::start_tally(round, States::VotePropose, BalanceOf::<T>::from(0));
+		}
+	}
+
+	/// Start evaluating the voters of a voting phase. The first batch is evaluated immediately,
+	/// the remaining batches are evaluated in on_initialize of the following blocks. Only one
+	/// tally runs at a time, because state transits are deferred while a tally is running.
+	fn start_tally(round: RoundIndex, phase: States, reward_issued: BalanceOf<T>) {
+		<TallyCursor>::put((round, phase));
+		<TallyRewardTotal<T>>::put(reward_issued);
+		Self::tally_voter_rewards();
 	}
 
-	/*fn incr_round() {
-		<Round>::mutate(|r| {
-			if *r == u8::MAX { *r = 0; }
-			else { *r += 1; }
+	/// Reward up to TallyBatchSize voters of the running tally for every vote on a winner.
+	/// Returns the number of evaluated voters.
+	fn tally_voter_rewards() -> u32 {
+		let (round, phase): (RoundIndex, States) = match <TallyCursor>::get() {
+			Some(cursor) => cursor,
+			None => return 0,
+		};
+
+		let batch_size: usize = T::TallyBatchSize::get().max(1) as usize;
+		let winners: VecDeque<ProposalWinner<IdentityId<T>>> = <ProposalWinners<T>>::get(round);
+		// The votes are not required after the tally, evaluated voters are removed
+		let (voters, reward): (Vec<(IdentityId<T>, Vec<ProposalCID>)>, BalanceOf<T>) = if phase == States::VotePropose {
+			(<ProposalVotes<T>>::drain_prefix(round).take(batch_size).collect(), T::ProposeVoteCorrectReward::get())
+		} else {
+			(<ConcernVotes<T>>::drain_prefix(round).take(batch_size).collect(), T::ConcernVoteCorrectReward::get())
+		};
+		let mut total_reward_issued: BalanceOf<T> = <TallyRewardTotal<T>>::get();
+
+		for (id, votes) in voters.iter() {
+			// Only count votes for winning proposals or concerns
+			let correct_votes = votes.iter().filter(|v| winners.iter().any(|winner| {
+				if phase == States::VotePropose {
+					winner.proposal == **v
+				} else {
+					winner.concerns.contains(v)
+				}
+			}));
+
+			for _ in correct_votes {
+				Self::accrue_reward(&T::Identity::get_address(id), reward);
+				total_reward_issued += reward;
+			}
+		}
+
+		if voters.len() < batch_size {
+			<TallyCursor>::kill();
+			<TallyRewardTotal<T>>::kill();
+
+			if phase == States::VotePropose {
+				Self::deposit_event(Event::<T>::TotalProposalReward(total_reward_issued));
+			} else {
+				Self::deposit_event(Event::<T>::TotalConcernReward(total_reward_issued));
+			}
+
+			Self::start_reward_distribution();
+		} else {
+			<TallyRewardTotal<T>>::put(total_reward_issued);
+		}
+
+		voters.len() as u32
+	}
+
+	/// Write the complete dataset of a round into the offchain index, so archive nodes
+	/// can serve it without querying every proposal through the runtime api
+	fn index_round_digest(round: RoundIndex) {
+		let digest = RoundDigest {
+			round,
+			result: <RoundHistory<T>>::get(round),
+			proposals: <Proposals<T>>::iter_prefix(round)
+				.flat_map(|(id, proposals)| proposals.into_iter().map(move |p| (id.clone(), p)))
+				.collect(),
+			concerns: <Concerns<T>>::iter_prefix(round)
+				.flat_map(|(id, concerns)| concerns.into_iter().map(move |c| (id.clone(), c)))
+				.collect(),
+		};
+
+		sp_io::offchain_index::set(&(ROUND_DIGEST_KEY, round).encode(), &digest.encode());
+	}
+
+	/// Remove the proposals, concerns and votes of a finished round. The outcome is kept in
+	/// RoundHistory and the complete dataset in the offchain index.
+	fn prune_round(round: RoundIndex) {
+		for (_, proposals) in <Proposals<T>>::iter_prefix(round) {
+			for proposal in proposals.iter() {
+				if <ProposalByCid<T>>::get(&proposal.proposal).map_or(false, |(_, cursor)| cursor.round == round) {
+					<ProposalByCid<T>>::remove(&proposal.proposal);
+				}
+			}
+		}
+
+		<Proposals<T>>::remove_prefix(round);
+		<ProposalVotes<T>>::remove_prefix(round);
+		<Concerns<T>>::remove_prefix(round);
+		<ConcernVotes<T>>::remove_prefix(round);
+		<CategoryProposalCount>::remove_prefix(round);
+		<ProposalWinners<T>>::remove(round);
+	}
+
+	/// Start the next round and remove the result of the round that is out of the history depth.
+	/// Only called by state transits, which are refused while a tally of the round is running.
+	fn incr_round() {
+		let round: RoundIndex = <Round>::get();
+		Self::index_round_digest(round);
+		Self::prune_round(round);
+
+		let finished: RoundIndex = <Round>::mutate(|r| {
+			*r = r.saturating_add(1);
+			*r - 1
 		});
-	}*/
+		let election: Weight = T::Council::on_round_finished(finished);
+		// The election is not covered by the weight of the state transit
+		frame_system::Module::<T>::register_extra_weight_unchecked(election, DispatchClass::Mandatory);
+
+		if let Some(expired) = finished.checked_sub(T::HistoryDepth::get()) {
+			<RoundHistory<T>>::remove(expired);
+			<VoteReceipts<T>>::remove_prefix(expired);
+		}
+
+		Self::deposit_event(Event::<T>::RoundFinalized(finished));
+	}
+}
+
+/// Milestones are confirmed by the identified users in a round that lasts as long as the
+/// VotePropose phase and requires the same acceptance threshold
+impl<T: Trait> MilestoneConfirmation for Module<T> {
+	fn open_confirmation(project: ProjectID, milestone: MilestoneIndex, evidence: ProposalCID) -> DispatchResult {
+		let end: T::BlockNumber = frame_system::Module::<T>::block_number() + Self::phase_duration(States::VotePropose);
+		<MilestoneVoters<T>>::remove_prefix((project, milestone));
+		<MilestoneConfirmations<T>>::insert(project, milestone, (end, 0, 0));
+		Self::deposit_event(Event::<T>::MilestoneConfirmationOpened(project, milestone, evidence, end));
+		Ok(())
+	}
+
+	fn conclude_confirmation(project: ProjectID, milestone: MilestoneIndex) -> Option<bool> {
+		let (end, confirmations, rejections) = <MilestoneConfirmations<T>>::get(project, milestone)?;
+
+		if frame_system::Module::<T>::block_number() < end {
+			return None;
+		}
+
+		let confirmed: bool = confirmations > 0 && Permill::from_rational_approximation(
+			confirmations, confirmations.saturating_add(rejections)
+		) >= Self::acceptance_threshold(States::VotePropose);
+
+		<MilestoneConfirmations<T>>::remove(project, milestone);
+		<MilestoneVoters<T>>::remove_prefix((project, milestone));
+		Self::deposit_event(Event::<T>::MilestoneConfirmationConcluded(project, milestone, confirmed));
+		Some(confirmed)
+	}
+}
+
+/// The proposer receives ProposeReward of the budget once the project was completed.
+/// Without a price oracle, one unit of the budget asset is valued as one unit of the reward currency.
+impl<T: Trait> OnProjectCompleted<IdentityId<T>, BudgetOf<T>> for Module<T> {
+	fn on_project_completed(proposer: &IdentityId<T>, budget: BudgetOf<T>) {
+		let budget: BalanceOf<T> = budget.saturated_into::<u128>().saturated_into();
+		let reward: BalanceOf<T> = T::ProposeReward::get() * budget;
+
+		if reward > BalanceOf::<T>::from(0) {
+			Self::accrue_reward(&T::Identity::get_address(proposer), reward);
+			Self::start_reward_distribution();
+		}
+	}
+}
+
+impl<T: Trait> ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	/// Accept availability attestations and pin confirmations of documents that are not
+	/// attested or pinned yet, if they are signed by an off-chain authority
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		// Anonymous ballots are unsigned to avoid linking them to an account
+		if let Call::vote_anonymous(votes, nullifier, proof) = call {
+			if Self::ensure_anonymous_ballot(votes, nullifier, proof).is_err() {
+				return InvalidTransaction::BadProof.into();
+			}
+
+			return ValidTransaction::with_tag_prefix("AnonymousBallot")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((<Round>::get(), nullifier))
+				.longevity(5)
+				.propagate(true)
+				.build();
+		}
+
+		let (tag_prefix, payload, signature) = match call {
+			Call::attest_availability(payload, signature) => ("ProposalAvailability", payload, signature),
+			Call::confirm_pin(payload, signature) => ("DocumentPinned", payload, signature),
+			_ => return InvalidTransaction::Call.into(),
+		};
+
+		if !<OffchainAuthorities<T>>::get().contains(&payload.public.clone().into_account()) {
+			return InvalidTransaction::BadSigner.into();
+		}
+
+		if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+			return InvalidTransaction::BadProof.into();
+		}
+
+		let result: DispatchResult = match call {
+			Call::attest_availability(..) => Self::ensure_attestable(payload.round, &payload.document),
+			_ => Self::ensure_pinnable(payload.round, &payload.document),
+		};
+
+		if result.is_err() {
+			return InvalidTransaction::Stale.into();
+		}
+
+		// The payload is authenticated, hence the off-chain workers of other nodes can forward
+		// their attestations to the block authors
+		ValidTransaction::with_tag_prefix(tag_prefix)
+			.priority(T::UnsignedPriority::get())
+			.and_provides((payload.round, payload.document))
+			.longevity(5)
+			.propagate(true)
+			.build()
+	}
 }
@@ -0,0 +1,400 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the proposal pallet
+//!
+//! Every storage layout change adds a variant to Releases and a migration function that
+//! converts the storage from the previous version. The migrations are applied in order
+//! until the storage reaches CURRENT_RELEASE. With the feature "try-runtime", the state
+//! is checked before and after the upgrade.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::Vec,
+	sp_std::{self, cell::Cell, collections::vec_deque::VecDeque},
+	storage::{migration::{put_storage_value, StorageIterator}, unhashed},
+	traits::Get,
+	weights::Weight,
+	IterableStorageDoubleMap, IterableStorageMap, StorageMap, StorageValue,
+};
+use pallet_community_identity::IdentityId;
+use pallet_proposal_types::{deprecated::{self, migrate_cid, LegacyCID}, ProposalCID, RoundIndex};
+use crate::*;
+
+/// Name of the pallet storage prefix
+const PALLET: &[u8] = b"Proposal";
+/// Temporary storage key that carries the pre_upgrade state to post_upgrade
+#[cfg(feature = "try-runtime")]
+const PRE_UPGRADE_KEY: &[u8] = b":proposal:pre_upgrade";
+
+/// Migration function and the storage version it upgrades from and to
+type Migration = (Releases, Releases, fn() -> Weight);
+
+/// All migrations, ordered by the storage version they upgrade from
+fn migrations<T: Trait>() -> Vec<Migration> {
+	sp_std::vec![
+		(Releases::V0U8Round, Releases::V1VecCid, migrate_to_round_keys::<T> as fn() -> Weight),
+		(Releases::V1VecCid, Releases::V2FixedCid, migrate_to_fixed_cids::<T> as fn() -> Weight),
+		(Releases::V2FixedCid, Releases::V3ProposalMetadata, migrate_to_proposal_metadata::<T> as fn() -> Weight),
+		(Releases::V3ProposalMetadata, Releases::V4CouncilJustifications,
+			migrate_to_council_justifications::<T> as fn() -> Weight),
+	]
+}
+
+/// Apply all migrations that are required to reach the current storage layout
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	for (from, to, migration) in migrations::<T>() {
+		if StorageVersion::get() == from {
+			weight = weight.saturating_add(migration());
+			StorageVersion::put(to);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+	}
+
+	weight
+}
+
+/// State that must be preserved by the migrations: (proposal count, proposal owners, round results)
+#[cfg(feature = "try-runtime")]
+type UpgradeSnapshot = (u32, u32, u32);
+
+/// Record the state that the migrations must preserve
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Trait>() -> Result<(), &'static str> {
+	let version: Releases = StorageVersion::get();
+	ensure!(version <= CURRENT_RELEASE, "The storage version is newer than the runtime");
+
+	// The entries of the legacy layout are counted with the legacy types
+	let owners: u32 = StorageIterator::<IdentityId<T>>::new(PALLET, b"ProposalToIdentity").count() as u32;
+	let history: u32 = if version <= Releases::V1VecCid {
+		StorageIterator::<deprecated::RoundResult<IdentityId<T>>>::new(PALLET, b"RoundHistory").count() as u32
+	} else if version < Releases::V4CouncilJustifications {
+		StorageIterator::<deprecated::RoundResultWithoutJustifications<IdentityId<T>>>::new(PALLET, b"RoundHistory")
+			.count() as u32
+	} else {
+		<RoundHistory<T>>::iter().count() as u32
+	};
+
+	let snapshot: UpgradeSnapshot = (ProposalCount::get(), owners, history);
+	frame_support::storage::unhashed::put(PRE_UPGRADE_KEY, &snapshot);
+	Ok(())
+}
+
+/// Ensure that the storage reached the current layout and every entry survived the migrations
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Trait>() -> Result<(), &'static str> {
+	let (proposals, owners, history): UpgradeSnapshot =
+		frame_support::storage::unhashed::take(PRE_UPGRADE_KEY).ok_or("pre_upgrade was not executed")?;
+
+	ensure!(StorageVersion::get() == CURRENT_RELEASE, "The storage did not reach the current version");
+	ensure!(ProposalCount::get() == proposals, "The proposal count changed");
+	// Entries that do not decode in the current layout are skipped by the iterators
+	ensure!(<ProposalToIdentity<T>>::iter().count() as u32 == owners, "Proposal owners were lost");
+	ensure!(<RoundHistory<T>>::iter().count() as u32 == history, "Round results were lost");
+	Ok(())
+}
+
+/// Drain a map whose key contains legacy CIDs and return the decoded keys and values
+fn drain_legacy_keys<K: Decode, V: Decode>(item: &[u8]) -> Vec<(K, V)> {
+	// The maps use the identity hasher, therefore the storage key is the encoded map key
+	StorageIterator::<V>::new(PALLET, item).drain()
+		.filter_map(|(key, value)| K::decode(&mut &key[..]).ok().map(|k| (k, value)))
+		.collect()
+}
+
+/// Move the entries of an identity keyed map under the given round
+fn rekey_under_round<V: Decode + Encode>(item: &[u8], round: RoundIndex) -> u64 {
+	// The entries are collected first, the new keys share the prefix of the old ones
+	let entries: Vec<(Vec<u8>, V)> = StorageIterator::<V>::new(PALLET, item).drain().collect();
+	let count = entries.len() as u64;
+
+	for (identity, value) in entries {
+		// hasher(identity): the key of the double map is the encoded round followed by the identity
+		let mut key = round.encode();
+		key.extend_from_slice(&identity);
+		put_storage_value(PALLET, item, &key, value);
+	}
+
+	count
+}
+
+/// Move the entries of a map that is keyed by a u8 round under the RoundIndex key
+fn rekey_round_map<V: Decode + Encode>(item: &[u8]) -> u64 {
+	let entries: Vec<(u8, V)> = drain_legacy_keys::<u8, V>(item);
+	let count = entries.len() as u64;
+
+	for (round, value) in entries {
+		put_storage_value(PALLET, item, &RoundIndex::from(round).encode(), value);
+	}
+
+	count
+}
+
+/// Store the u8 round as RoundIndex and return it. Returns None if the round was widened already.
+fn widen_round() -> Option<RoundIndex> {
+	// A RoundIndex written by a later layout must not be interpreted as u8
+	let round: RoundIndex = match unhashed::get_raw(&<Round>::hashed_key()) {
+		Some(raw) if raw.len() == 1 => raw[0].into(),
+		Some(_) => return None,
+		None => 0,
+	};
+
+	<Round>::put(round);
+	Some(round)
+}
+
+/// Widen the round to RoundIndex and key the documents of the current round by (round, identity)
+fn migrate_to_round_keys<T: Trait>() -> Weight {
+	let round: RoundIndex = match widen_round() {
+		Some(round) => round,
+		None => return T::DbWeight::get().reads(1),
+	};
+
+	// The values still contain legacy CIDs, they are converted by the next migration
+	let mut entries: u64 = rekey_under_round::<Vec<deprecated::Proposal>>(b"Proposals", round);
+	entries = entries.saturating_add(rekey_under_round::<Vec<LegacyCID>>(b"ProposalVotes", round));
+	entries = entries.saturating_add(rekey_under_round::<Vec<deprecated::Concern>>(b"Concerns", round));
+	entries = entries.saturating_add(rekey_under_round::<Vec<LegacyCID>>(b"ConcernVotes", round));
+	entries = entries.saturating_add(
+		rekey_round_map::<VecDeque<deprecated::ProposalWinner<IdentityId<T>>>>(b"ProposalWinners")
+	);
+
+	T::DbWeight::get().reads_writes(entries.saturating_add(1), entries.saturating_mul(2).saturating_add(1))
+}
+
+/// Convert every CID that is stored as Vec<u8> into a fixed length digest
+fn migrate_to_fixed_cids<T: Trait>() -> Weight {
+	let mut entries: u64 = 0;
+
+	// Maps that contain CIDs in the key
+	for (cid, id) in drain_legacy_keys::<LegacyCID, IdentityId<T>>(b"ProposalToIdentity") {
+		<ProposalToIdentity<T>>::insert(migrate_cid(&cid), id);
+		entries += 1;
+	}
+
+	for (cid, bond) in drain_legacy_keys::<LegacyCID, (T::AccountId, BalanceOf<T>)>(b"ProposalBonds") {
+		<ProposalBonds<T>>::insert(migrate_cid(&cid), bond);
+		entries += 1;
+	}
+
+	for (cid, deposit) in drain_legacy_keys::<LegacyCID, (T::AccountId, BalanceOf<T>)>(b"UserProposeDeposits") {
+		<UserProposeDeposits<T>>::insert(migrate_cid(&cid), deposit);
+		entries += 1;
+	}
+
+	for ((concern, proposal), id) in
+		drain_legacy_keys::<(LegacyCID, LegacyCID), IdentityId<T>>(b"ConcernToIdentity")
+	{
+		<ConcernToIdentity<T>>::insert((migrate_cid(&concern), migrate_cid(&proposal)), id);
+		entries += 1;
+	}
+
+	for ((concern, proposal), deposit) in
+		drain_legacy_keys::<(LegacyCID, LegacyCID), (T::AccountId, BalanceOf<T>)>(b"UserConcernDeposits")
+	{
+		<UserConcernDeposits<T>>::insert((migrate_cid(&concern), migrate_cid(&proposal)), deposit);
+		entries += 1;
+	}
+
+	// Maps that contain CIDs in the value (translate requires Fn closures, hence the Cell)
+	let translated: Cell<u64> = Cell::new(0);
+	let count = || translated.set(translated.get() + 1);
+
+	// Proposals are written in the layout of V2FixedCid, the next migration adds the metadata
+	for (key, old) in StorageIterator::<Vec<deprecated::Proposal>>::new(PALLET, b"Proposals") {
+		let proposals: Vec<deprecated::ProposalWithoutMetadata> = old.into_iter().map(Into::into).collect();
+		put_storage_value(PALLET, b"Proposals", &key, proposals);
+		count();
+	}
+
+	<ProposalVotes<T>>::translate::<Vec<LegacyCID>, _>(|_, _, old| {
+		count();
+		Some(old.iter().map(migrate_cid).collect::<Vec<ProposalCID>>())
+	});
+
+	<Concerns<T>>::translate::<Vec<deprecated::Concern>, _>(|_, _, old| {
+		count();
+		Some(old.into_iter().map(Into::into).collect())
+	});
+
+	<ConcernVotes<T>>::translate::<Vec<LegacyCID>, _>(|_, _, old| {
+		count();
+		Some(old.iter().map(migrate_cid).collect::<Vec<ProposalCID>>())
+	});
+
+	// The notifications of a proposer were a list, they are keyed by (proposer, document) now
+	for (proposer, pending) in
+		drain_legacy_keys::<IdentityId<T>, Vec<(LegacyCID, LegacyCID)>>(b"PendingConcernNotifications")
+	{
+		for (concern, proposal) in pending.iter() {
+			<PendingConcernNotifications<T>>::insert(&proposer, (migrate_cid(concern), migrate_cid(proposal)), true);
+			entries += 1;
+		}
+	}
+
+	<ProposalWinners<T>>::translate::<VecDeque<deprecated::ProposalWinner<IdentityId<T>>>, _>(|_, old| {
+		count();
+		Some(old.into_iter().map(Into::into).collect())
+	});
+
+	// Round results are written in the layout of V2FixedCid, a later migration adds the justifications
+	for (key, old) in StorageIterator::<deprecated::RoundResult<IdentityId<T>>>::new(PALLET, b"RoundHistory") {
+		let result: deprecated::RoundResultWithoutJustifications<IdentityId<T>> = old.into();
+		put_storage_value(PALLET, b"RoundHistory", &key, result);
+		count();
+	}
+
+	entries = entries.saturating_add(translated.get());
+	T::DbWeight::get().reads_writes(entries, entries.saturating_mul(2))
+}
+
+/// Add empty metadata to every proposal
+fn migrate_to_proposal_metadata<T: Trait>() -> Weight {
+	let translated: Cell<u64> = Cell::new(0);
+
+	<Proposals<T>>::translate::<Vec<deprecated::ProposalWithoutMetadata>, _>(|_, _, old| {
+		translated.set(translated.get() + 1);
+		Some(old.into_iter().map(Into::into).collect())
+	});
+
+	T::DbWeight::get().reads_writes(translated.get(), translated.get())
+}
+
+/// Add empty council justifications to every round result
+fn migrate_to_council_justifications<T: Trait>() -> Weight {
+	let translated: Cell<u64> = Cell::new(0);
+
+	<RoundHistory<T>>::translate::<deprecated::RoundResultWithoutJustifications<IdentityId<T>>, _>(|_, old| {
+		translated.set(translated.get() + 1);
+		Some(old.into())
+	});
+
+	T::DbWeight::get().reads_writes(translated.get(), translated.get())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::storage::migration::get_storage_value;
+	use sp_io::TestExternalities;
+
+	/// Storage key of a double map entry with identity hashers
+	fn round_key(round: RoundIndex, identity: &[u8]) -> Vec<u8> {
+		let mut key = round.encode();
+		key.extend_from_slice(identity);
+		key
+	}
+
+	#[test]
+	fn releases_keep_their_encoding() {
+		let releases = [
+			(Releases::V0U8Round, 255u8),
+			(Releases::V1VecCid, 0),
+			(Releases::V2FixedCid, 1),
+			(Releases::V3ProposalMetadata, 2),
+			(Releases::V4CouncilJustifications, 3),
+		];
+
+		for (release, index) in releases.iter() {
+			assert_eq!(release.encode(), vec![*index]);
+			assert_eq!(Releases::decode(&mut &[*index][..]).ok(), Some(*release));
+		}
+
+		// The order of the versions does not depend on the encoding
+		assert!(Releases::V0U8Round < Releases::V1VecCid);
+		assert!(Releases::V3ProposalMetadata < CURRENT_RELEASE);
+	}
+
+	#[test]
+	fn u8_round_is_widened_once() {
+		TestExternalities::default().execute_with(|| {
+			unhashed::put_raw(&<Round>::hashed_key(), &[7u8]);
+			assert_eq!(widen_round(), Some(7));
+			assert_eq!(<Round>::get(), 7);
+			// The widened round is 4 bytes long and must not be read as u8 again
+			assert_eq!(widen_round(), None);
+			assert_eq!(<Round>::get(), 7);
+		});
+	}
+
+	#[test]
+	fn missing_round_starts_at_zero() {
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(widen_round(), Some(0));
+			assert!(unhashed::get_raw(&<Round>::hashed_key()).map_or(false, |raw| raw.len() == 4));
+		});
+	}
+
+	#[test]
+	fn identity_keyed_entries_move_under_the_round() {
+		let alice: [u8; 32] = [1; 32];
+		let bob: [u8; 32] = [2; 32];
+		let votes: Vec<LegacyCID> = vec![b"QmAlice".to_vec(), b"QmBob".to_vec()];
+
+		TestExternalities::default().execute_with(|| {
+			put_storage_value(PALLET, b"ProposalVotes", &alice, votes.clone());
+			put_storage_value(PALLET, b"ProposalVotes", &bob, Vec::<LegacyCID>::new());
+
+			assert_eq!(rekey_under_round::<Vec<LegacyCID>>(b"ProposalVotes", 3), 2);
+			assert_eq!(get_storage_value::<Vec<LegacyCID>>(PALLET, b"ProposalVotes", &round_key(3, &alice)),
+				Some(votes)
+			);
+			assert_eq!(get_storage_value::<Vec<LegacyCID>>(PALLET, b"ProposalVotes", &round_key(3, &bob)),
+				Some(Vec::new())
+			);
+			assert_eq!(get_storage_value::<Vec<LegacyCID>>(PALLET, b"ProposalVotes", &alice), None);
+			assert_eq!(StorageIterator::<Vec<LegacyCID>>::new(PALLET, b"ProposalVotes").count(), 2);
+		});
+	}
+
+	#[test]
+	fn u8_round_keys_become_round_indices() {
+		TestExternalities::default().execute_with(|| {
+			put_storage_value(PALLET, b"ProposalWinners", &2u8.encode(), 20u32);
+			put_storage_value(PALLET, b"ProposalWinners", &5u8.encode(), 50u32);
+
+			assert_eq!(rekey_round_map::<u32>(b"ProposalWinners"), 2);
+			assert_eq!(get_storage_value::<u32>(PALLET, b"ProposalWinners", &2u32.encode()), Some(20));
+			assert_eq!(get_storage_value::<u32>(PALLET, b"ProposalWinners", &5u32.encode()), Some(50));
+			assert_eq!(get_storage_value::<u32>(PALLET, b"ProposalWinners", &2u8.encode()), None);
+		});
+	}
+
+	#[test]
+	fn legacy_cid_keys_are_drained_and_converted() {
+		let first: LegacyCID = b"first document".to_vec();
+		let second: LegacyCID = b"second document".to_vec();
+
+		TestExternalities::default().execute_with(|| {
+			put_storage_value(PALLET, b"ProposalToIdentity", &first.encode(), 1u64);
+			put_storage_value(PALLET, b"ProposalToIdentity", &second.encode(), 2u64);
+
+			let mut drained = drain_legacy_keys::<LegacyCID, u64>(b"ProposalToIdentity");
+			drained.sort();
+			assert_eq!(drained, vec![(first.clone(), 1), (second.clone(), 2)]);
+			assert_eq!(StorageIterator::<u64>::new(PALLET, b"ProposalToIdentity").count(), 0);
+		});
+
+		// Binary CIDs keep their digest
+		let mut cid_v0: LegacyCID = vec![0x12, 0x20];
+		cid_v0.extend_from_slice(&[9u8; 32]);
+		assert_eq!(migrate_cid(&cid_v0), [9u8; 32]);
+
+		// Unparsable legacy CIDs are hashed, distinct CIDs stay distinct
+		assert_eq!(migrate_cid(&first), sp_io::hashing::blake2_256(&first));
+		assert_ne!(migrate_cid(&first), migrate_cid(&second));
+	}
+}
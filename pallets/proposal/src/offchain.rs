@@ -0,0 +1,137 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Off-chain workers of the proposal pallet. They talk to IPFS over HTTP, the endpoints
+//! are configured by the node operator in the persistent off-chain local storage.
+//! Attestations are signed with an "ipfs" key of the local keystore whose account is an
+//! off-chain authority, nodes without such a key do not submit attestations.
+
+use frame_support::sp_runtime::offchain::{http, storage::StorageValueRef, Duration, StorageKind};
+use frame_system::offchain::{SendUnsignedTransaction, Signer};
+use sp_io::offchain::local_storage_get;
+use frame_support::sp_std;
+use pallet_proposal_types::cid_v0;
+use crate::*;
+
+/// Local storage key of the IPFS gateway base url, e.g. "http://127.0.0.1:8080".
+/// The value is the raw url, it can be set with the offchain_localStorageSet RPC.
+pub const IPFS_GATEWAY_KEY: &[u8] = b"superorganism/ipfs-gateway";
+/// Local storage key of the HTTP API base url of the IPFS node that pins the winning
+/// documents, e.g. "http://127.0.0.1:5001". The value is the raw url.
+pub const IPFS_API_KEY: &[u8] = b"superorganism/ipfs-api";
+/// Prefix of the local storage keys that store when a proposal was checked the last time.
+/// Key: (prefix, proposal).encode()
+const AVAILABILITY_CHECK_KEY: &[u8] = b"superorganism/availability";
+/// Prefix of the local storage keys that store when the pinning of a document was requested
+/// the last time. Key: (prefix, document).encode()
+const PIN_REQUEST_KEY: &[u8] = b"superorganism/pin";
+/// How many requests are sent to IPFS at most per block and worker
+const MAX_REQUESTS: usize = 5;
+/// How many blocks must pass until a failed request for a document is repeated
+const RETRY_INTERVAL: u32 = 10;
+/// How long a single HTTP request may take
+const HTTP_TIMEOUT_MS: u64 = 3_000;
+
+/// Fetch the unattested proposals of the current round from the configured IPFS gateway and
+/// submit an availability attestation for every proposal that was retrievable
+pub(crate) fn attest_availability<T: Trait>(now: T::BlockNumber) {
+	let gateway: Vec<u8> = match local_storage_get(StorageKind::PERSISTENT, IPFS_GATEWAY_KEY) {
+		Some(gateway) => gateway,
+		None => return,
+	};
+
+	let round: RoundIndex = <Round>::get();
+	let unchecked = <ProposalToIdentity<T>>::iter()
+		.map(|(proposal, _)| proposal)
+		.filter(|proposal| !<ProposalAvailability<T>>::contains_key(proposal))
+		.filter(|proposal| request_due::<T>(AVAILABILITY_CHECK_KEY, proposal, now))
+		.take(MAX_REQUESTS);
+
+	for proposal in unchecked {
+		if ipfs_request(http::Method::Get, &gateway, b"/ipfs/", &cid_v0(&proposal)).is_ok() {
+			// The attestation is only a hint for the block author, failures are retried later
+			let _ = Signer::<T, T::AuthorityId>::any_account().send_unsigned_transaction(
+				|account| DocumentPayload { round, document: proposal, public: account.public.clone() },
+				|payload, signature| Call::attest_availability(payload, signature),
+			);
+		}
+	}
+}
+
+/// Instruct the configured IPFS node to pin the winning proposals of the current round and
+/// their accepted concerns, and submit a pin confirmation for every pinned document
+pub(crate) fn pin_winners<T: Trait>(now: T::BlockNumber) {
+	let api: Vec<u8> = match local_storage_get(StorageKind::PERSISTENT, IPFS_API_KEY) {
+		Some(api) => api,
+		None => return,
+	};
+
+	let round: RoundIndex = <Round>::get();
+	let unpinned: Vec<ProposalCID> = <ProposalWinners<T>>::get(round).iter()
+		.flat_map(|winner| sp_std::iter::once(winner.proposal).chain(winner.concerns.iter().copied()))
+		.filter(|document| !<PinnedDocuments<T>>::contains_key(document))
+		.filter(|document| request_due::<T>(PIN_REQUEST_KEY, document, now))
+		.take(MAX_REQUESTS)
+		.collect();
+
+	for document in unpinned {
+		// The IPFS HTTP API only accepts POST requests
+		if ipfs_request(http::Method::Post, &api, b"/api/v0/pin/add?arg=", &cid_v0(&document)).is_ok() {
+			let _ = Signer::<T, T::AuthorityId>::any_account().send_unsigned_transaction(
+				|account| DocumentPayload { round, document, public: account.public.clone() },
+				|payload, signature| Call::confirm_pin(payload, signature),
+			);
+		}
+	}
+}
+
+/// Is a request for the document due? Records the request in the local storage if it is.
+fn request_due<T: Trait>(prefix: &[u8], document: &ProposalCID, now: T::BlockNumber) -> bool {
+	let key: Vec<u8> = (prefix, document).encode();
+	let last_request = StorageValueRef::persistent(&key);
+
+	let due: bool = match last_request.get::<T::BlockNumber>() {
+		Some(Some(block)) => now >= block + T::BlockNumber::from(RETRY_INTERVAL),
+		_ => true,
+	};
+
+	if due {
+		last_request.set(&now);
+	}
+
+	due
+}
+
+/// Send a request for a CID to the IPFS endpoint at base ++ path ++ cid.
+/// Succeeds if the endpoint responds with status 200 before the timeout.
+pub(crate) fn ipfs_request(method: http::Method, base: &[u8], path: &[u8], cid: &[u8]) -> Result<(), http::Error> {
+	let mut url: Vec<u8> = base.to_vec();
+	url.extend_from_slice(path);
+	url.extend_from_slice(cid);
+	let url: &str = sp_std::str::from_utf8(&url).map_err(|_| http::Error::Unknown)?;
+
+	let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT_MS));
+	let pending = http::Request::get(url)
+		.method(method)
+		.deadline(deadline)
+		.send()
+		.map_err(|_| http::Error::IoError)?;
+	let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+
+	if response.code != 200 {
+		return Err(http::Error::Unknown);
+	}
+
+	Ok(())
+}
@@ -0,0 +1,246 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{dispatch::Vec, sp_std::{cmp::Reverse, vec}};
+use sp_arithmetic::Permill;
+use pallet_proposal_types::VotingScheme;
+use crate::traits::TallyAlgorithm;
+
+/// Every document that receives at least `acceptance_min` of the total votes wins.
+/// Every vote costs one voice credit, a voter can vote for a document once.
+pub struct SimpleThreshold;
+
+impl TallyAlgorithm for SimpleThreshold {
+	fn tally(votes: &[u32], acceptance_min: Permill) -> Vec<(usize, Permill)> {
+		threshold(votes, acceptance_min)
+	}
+
+	fn vote_cost(_prior_votes: u32) -> u32 {
+		1
+	}
+
+	fn repeated_votes() -> bool {
+		false
+	}
+
+	fn scheme() -> VotingScheme {
+		VotingScheme::SimpleThreshold
+	}
+}
+
+/// Every document that receives at least `acceptance_min` of the total votes wins.
+/// Casting n votes for the same document costs n² voice credits.
+pub struct Quadratic;
+
+impl TallyAlgorithm for Quadratic {
+	fn tally(votes: &[u32], acceptance_min: Permill) -> Vec<(usize, Permill)> {
+		threshold(votes, acceptance_min)
+	}
+
+	fn vote_cost(prior_votes: u32) -> u32 {
+		// (n + 1)² - n² = 2n + 1
+		prior_votes.saturating_mul(2).saturating_add(1)
+	}
+
+	fn repeated_votes() -> bool {
+		true
+	}
+
+	fn scheme() -> VotingScheme {
+		VotingScheme::Quadratic
+	}
+}
+
+/// Voters rank up to MaxRankedChoices proposals in a single ballot. The winner is determined by
+/// instant-runoff: The proposal with the fewest first preferences is eliminated and its ballots
+/// count for their next preference, until a proposal holds the majority of the remaining ballots.
+/// The winner must still reach `acceptance_min`. Concern votes are tallied by threshold.
+pub struct RankedChoice;
+
+impl TallyAlgorithm for RankedChoice {
+	fn tally(votes: &[u32], acceptance_min: Permill) -> Vec<(usize, Permill)> {
+		threshold(votes, acceptance_min)
+	}
+
+	fn vote_cost(_prior_votes: u32) -> u32 {
+		1
+	}
+
+	fn repeated_votes() -> bool {
+		false
+	}
+
+	fn scheme() -> VotingScheme {
+		VotingScheme::RankedChoice
+	}
+
+	fn tally_ranked(documents: usize, ballots: &[Vec<usize>], acceptance_min: Permill) -> Vec<(usize, Permill)> {
+		let mut eliminated: Vec<bool> = vec![false; documents];
+
+		loop {
+			// Count every ballot for its highest ranked proposal that was not eliminated yet
+			let mut counts: Vec<u32> = vec![0; documents];
+			let mut continuing: u32 = 0;
+
+			for ballot in ballots.iter() {
+				if let Some(choice) = ballot.iter().find(|idx| **idx < documents && !eliminated[**idx]) {
+					counts[*choice] += 1;
+					continuing += 1;
+				}
+			}
+
+			// Ties are resolved in favour of the earlier submission
+			let remaining = || (0..documents).filter(|idx| !eliminated[*idx]);
+			let leader: usize = match remaining().max_by_key(|idx| (counts[*idx], Reverse(*idx))) {
+				Some(leader) if continuing > 0 => leader,
+				_ => return Vec::new(),
+			};
+
+			if counts[leader].saturating_mul(2) > continuing {
+				let vote_ratio = Permill::from_rational_approximation(counts[leader], continuing);
+				return if vote_ratio >= acceptance_min { vec![(leader, vote_ratio)] } else { Vec::new() };
+			}
+
+			// The leader has no majority, therefore at least one other proposal remains
+			if let Some(loser) = remaining().min_by_key(|idx| (counts[*idx], Reverse(*idx))) {
+				eliminated[loser] = true;
+			}
+		}
+	}
+}
+
+/// Return index and vote ratio of every document that received at least `acceptance_min` of the total votes
+fn threshold(votes: &[u32], acceptance_min: Permill) -> Vec<(usize, Permill)> {
+	let total_votes: u32 = votes.iter().fold(0u32, |acc, v| acc.saturating_add(*v));
+
+	if total_votes == 0 {
+		return Vec::new();
+	}
+
+	votes.iter().enumerate().filter_map(|(idx, v)| {
+		let vote_ratio = Permill::from_rational_approximation(*v, total_votes);
+
+		if vote_ratio >= acceptance_min {
+			Some((idx, vote_ratio))
+		} else {
+			None
+		}
+	}).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn threshold_includes_documents_at_the_minimum() {
+		assert_eq!(SimpleThreshold::tally(&[1, 3], Permill::from_percent(25)),
+			vec![(0, Permill::from_percent(25)), (1, Permill::from_percent(75))]
+		);
+		assert_eq!(SimpleThreshold::tally(&[1, 3], Permill::from_percent(26)),
+			vec![(1, Permill::from_percent(75))]
+		);
+	}
+
+	#[test]
+	fn threshold_without_votes_has_no_winners() {
+		assert!(SimpleThreshold::tally(&[], Permill::zero()).is_empty());
+		assert!(SimpleThreshold::tally(&[0, 0], Permill::zero()).is_empty());
+		assert!(Quadratic::tally(&[0, 0, 0], Permill::zero()).is_empty());
+	}
+
+	#[test]
+	fn threshold_handles_large_totals() {
+		assert_eq!(Quadratic::tally(&[u32::MAX / 2, u32::MAX / 2], Permill::from_percent(50)),
+			vec![(0, Permill::from_percent(50)), (1, Permill::from_percent(50))]
+		);
+	}
+
+	#[test]
+	fn quadratic_cost_sums_up_to_the_square() {
+		assert_eq!(Quadratic::vote_cost(0), 1);
+		assert_eq!(Quadratic::vote_cost(1), 3);
+		assert_eq!(Quadratic::vote_cost(2), 5);
+
+		for votes in 0..20u32 {
+			let credits: u32 = (0..votes).map(Quadratic::vote_cost).sum();
+			assert_eq!(credits, votes * votes);
+		}
+	}
+
+	#[test]
+	fn quadratic_cost_saturates() {
+		assert_eq!(Quadratic::vote_cost(u32::MAX / 2), u32::MAX);
+		assert_eq!(Quadratic::vote_cost(u32::MAX), u32::MAX);
+	}
+
+	#[test]
+	fn single_votes_cost_one_credit() {
+		assert_eq!(SimpleThreshold::vote_cost(0), 1);
+		assert_eq!(SimpleThreshold::vote_cost(u32::MAX), 1);
+		assert_eq!(RankedChoice::vote_cost(7), 1);
+		assert!(!SimpleThreshold::repeated_votes());
+		assert!(Quadratic::repeated_votes());
+		assert!(!RankedChoice::repeated_votes());
+	}
+
+	#[test]
+	fn ranked_majority_wins_in_the_first_round() {
+		let ballots = vec![vec![1], vec![1], vec![1, 0], vec![0]];
+		assert_eq!(RankedChoice::tally_ranked(2, &ballots, Permill::zero()),
+			vec![(1, Permill::from_percent(75))]
+		);
+	}
+
+	#[test]
+	fn ranked_fewest_first_preferences_are_eliminated_and_transferred() {
+		// 0 leads the first round, but the ballot of the eliminated 2 transfers to 1
+		let ballots = vec![vec![0], vec![0], vec![1], vec![1], vec![2, 1]];
+		assert_eq!(RankedChoice::tally_ranked(3, &ballots, Permill::zero()),
+			vec![(1, Permill::from_percent(60))]
+		);
+	}
+
+	#[test]
+	fn ranked_ties_eliminate_the_later_document() {
+		// 0 and 1 are tied for the fewest first preferences, 1 is eliminated and transfers to 0.
+		// 0 and 2 are tied afterwards, 2 is eliminated and its ballots are exhausted.
+		let ballots = vec![vec![0, 2], vec![1, 0], vec![2], vec![2]];
+		assert_eq!(RankedChoice::tally_ranked(3, &ballots, Permill::zero()),
+			vec![(0, Permill::one())]
+		);
+	}
+
+	#[test]
+	fn ranked_ties_favour_the_earlier_document() {
+		let ballots = vec![vec![1], vec![0]];
+		assert_eq!(RankedChoice::tally_ranked(2, &ballots, Permill::zero()), vec![(0, Permill::one())]);
+	}
+
+	#[test]
+	fn ranked_winner_must_reach_acceptance_min() {
+		let ballots = vec![vec![0], vec![0], vec![0], vec![1]];
+		assert_eq!(RankedChoice::tally_ranked(2, &ballots, Permill::from_percent(75)),
+			vec![(0, Permill::from_percent(75))]
+		);
+		assert!(RankedChoice::tally_ranked(2, &ballots, Permill::from_percent(76)).is_empty());
+	}
+
+	#[test]
+	fn ranked_ignores_invalid_choices() {
+		assert!(RankedChoice::tally_ranked(0, &[], Permill::zero()).is_empty());
+		assert!(RankedChoice::tally_ranked(2, &[vec![], vec![5]], Permill::zero()).is_empty());
+		assert_eq!(RankedChoice::tally_ranked(2, &[vec![5, 1]], Permill::zero()), vec![(1, Permill::one())]);
+	}
+}
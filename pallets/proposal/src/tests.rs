@@ -23,3 +23,58 @@ fn correct_error_for_none_value() {
 	});
 }
 */
+
+use crate::pay_rewards;
+use std::collections::BTreeMap;
+
+/// Accounts below this id cannot receive transfers
+const FIRST_PAYABLE: u64 = 4;
+
+#[test]
+fn failing_rewards_do_not_block_the_queue() {
+	// Like PendingRewards, the queue iterates in a fixed order and every batch starts at its front
+	let batch_size: usize = 2;
+	let mut pending: BTreeMap<u64, u64> = (1..=FIRST_PAYABLE).map(|account| (account, 10)).collect();
+	let mut failed: BTreeMap<u64, u64> = BTreeMap::new();
+	let mut paid: Vec<(u64, u64)> = Vec::new();
+	let mut batches: u32 = 0;
+
+	while !pending.is_empty() {
+		let batch: Vec<(u64, u64)> = pending.iter().take(batch_size).map(|(a, r)| (*a, *r)).collect();
+		let payout = pay_rewards(batch, || 100, |account, _| *account >= FIRST_PAYABLE);
+		assert_eq!(payout.exhausted, None);
+
+		// Mirrors do_distribute_rewards: paid and failed rewards both leave the queue
+		for (account, reward) in payout.paid {
+			pending.remove(&account);
+			paid.push((account, reward));
+		}
+
+		for (account, reward) in payout.failed {
+			pending.remove(&account);
+			failed.insert(account, reward);
+		}
+
+		batches += 1;
+		assert!(batches <= 2, "the queue did not advance");
+	}
+
+	assert_eq!(paid, vec![(FIRST_PAYABLE, 10)]);
+	assert_eq!(failed.keys().cloned().collect::<Vec<u64>>(), vec![1, 2, 3]);
+}
+
+#[test]
+fn rewards_stop_when_the_pot_is_exhausted() {
+	let payout = pay_rewards(vec![(1u64, 5u64), (2, 20), (3, 5)], || 10, |_, _| true);
+	assert_eq!(payout.paid, vec![(1, 5)]);
+	assert!(payout.failed.is_empty());
+	assert_eq!(payout.exhausted, Some(10));
+}
+
+#[test]
+fn failed_rewards_are_collected_in_order() {
+	let payout = pay_rewards(vec![(1u64, 5u64), (2, 5), (3, 5)], || 10, |account, _| *account != 2);
+	assert_eq!(payout.paid, vec![(1, 5), (3, 5)]);
+	assert_eq!(payout.failed, vec![(2, 5)]);
+	assert_eq!(payout.exhausted, None);
+}
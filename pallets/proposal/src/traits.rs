@@ -0,0 +1,81 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{dispatch::Vec, sp_std, Parameter};
+use sp_arithmetic::Permill;
+use pallet_proposal_types::{Nullifier, ProposalCID, RoundIndex, VotingScheme};
+
+/// Computes the winners of a voting round.
+/// Every voting scheme (simple threshold, ranked-choice, quadratic, ...) is an implementation
+/// of this trait, which allows to add new voting schemes without touching the state machine.
+pub trait TallyAlgorithm {
+	/// Determine the winning documents. `votes` contains the votes for every document,
+	/// `acceptance_min` is the minimum vote ratio a document requires to win.
+	/// Returns the index and the vote ratio of every winning document.
+	fn tally(votes: &[u32], acceptance_min: Permill) -> Vec<(usize, Permill)>;
+
+	/// How many voice credits does a vote cost, given the number of votes the voter has already
+	/// cast for the same document?
+	fn vote_cost(prior_votes: u32) -> u32;
+
+	/// Can a voter cast more than one vote for the same document?
+	fn repeated_votes() -> bool;
+
+	/// Voting scheme this algorithm implements, exposed in the metadata
+	fn scheme() -> VotingScheme;
+
+	/// Determine the winning documents from ranked ballots. Every ballot contains the indices
+	/// of `documents` in the order of preference. Only called for the RankedChoice scheme,
+	/// by default every ranked document counts as one vote.
+	fn tally_ranked(documents: usize, ballots: &[Vec<usize>], acceptance_min: Permill) -> Vec<(usize, Permill)> {
+		let mut votes: Vec<u32> = sp_std::vec![0; documents];
+
+		for index in ballots.iter().flatten() {
+			if let Some(vote) = votes.get_mut(*index) {
+				*vote = vote.saturating_add(1);
+			}
+		}
+
+		Self::tally(&votes, acceptance_min)
+	}
+}
+
+/// Proves that the caster of an anonymous ballot is eligible to vote, without linking the
+/// ballot to an identity. The runtime can back this trait with blind-signed voting tokens
+/// or a zero-knowledge verifier.
+pub trait AnonymityProvider {
+	/// Proof of eligibility that accompanies an anonymous ballot
+	type Proof: Parameter;
+
+	/// Verify that `proof` proves that the voter behind `nullifier` holds an identity that is
+	/// eligible to vote in `round`, and that the proof was created for `ballot`.
+	/// Every eligible identity must map to exactly one nullifier per round.
+	fn verify(round: RoundIndex, nullifier: &Nullifier, ballot: &[ProposalCID], proof: &Self::Proof) -> bool;
+
+	/// Create a proof for the ballot, only used to benchmark anonymous ballots
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_proof(round: RoundIndex, nullifier: &Nullifier, ballot: &[ProposalCID]) -> Self::Proof;
+}
+
+/// Rejects every anonymous ballot
+impl AnonymityProvider for () {
+	type Proof = ();
+
+	fn verify(_: RoundIndex, _: &Nullifier, _: &[ProposalCID], _: &Self::Proof) -> bool {
+		false
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_proof(_: RoundIndex, _: &Nullifier, _: &[ProposalCID]) -> Self::Proof {}
+}
@@ -0,0 +1,157 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{dispatch::{Codec, Decode, Encode, EncodeLike, fmt::Debug, Vec}, Hashable};
+use sp_arithmetic::Permill;
+use crate::{cid_digest, ProposalCID};
+
+/// CID before it had a fixed length
+pub type LegacyCID = Vec<u8>;
+
+/// Convert a legacy CID into a digest. If the legacy CID cannot be parsed, the blake2-256 hash
+/// of it is used, which keeps it unique.
+pub fn migrate_cid(cid: &LegacyCID) -> ProposalCID {
+	cid_digest(cid).unwrap_or_else(|| cid.blake2_256())
+}
+
+/// Proposal before the CIDs had a fixed length
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct Proposal {
+	pub proposal: LegacyCID,
+	pub votes: u32,
+	pub index: u32,
+}
+
+impl From<Proposal> for ProposalWithoutMetadata {
+	fn from(old: Proposal) -> Self {
+		ProposalWithoutMetadata{proposal: migrate_cid(&old.proposal), votes: old.votes, index: old.index}
+	}
+}
+
+/// Proposal before it carried metadata
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProposalWithoutMetadata {
+	pub proposal: ProposalCID,
+	pub votes: u32,
+	pub index: u32,
+}
+
+impl From<ProposalWithoutMetadata> for crate::Proposal {
+	fn from(old: ProposalWithoutMetadata) -> Self {
+		crate::Proposal{proposal: old.proposal, votes: old.votes, index: old.index, metadata: Default::default()}
+	}
+}
+
+/// Concern before the CIDs had a fixed length
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct Concern {
+	pub associated_proposal: LegacyCID,
+	pub concern: LegacyCID,
+	pub votes: u32,
+	pub index: u32,
+}
+
+impl From<Concern> for crate::Concern {
+	fn from(old: Concern) -> Self {
+		crate::Concern{
+			associated_proposal: migrate_cid(&old.associated_proposal),
+			concern: migrate_cid(&old.concern),
+			votes: old.votes,
+			index: old.index,
+		}
+	}
+}
+
+/// ProposalWinner before the CIDs had a fixed length
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProposalWinner<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	pub concerns: Vec<LegacyCID>,
+	pub proposer: IdentityId,
+	pub proposal: LegacyCID,
+	pub vote_ratio: Permill
+}
+
+impl<IdentityId> From<ProposalWinner<IdentityId>> for crate::ProposalWinner<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	fn from(old: ProposalWinner<IdentityId>) -> Self {
+		crate::ProposalWinner::new(
+			old.concerns.iter().map(migrate_cid).collect(),
+			old.proposer,
+			migrate_cid(&old.proposal),
+			old.vote_ratio,
+		)
+	}
+}
+
+/// RoundResult before the CIDs had a fixed length
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct RoundResult<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	pub winners: Vec<ProposalWinner<IdentityId>>,
+	pub proposal_count: u32,
+	pub proposal_votes: u32,
+	pub concern_count: u32,
+	pub concern_votes: u32,
+	pub council_decisions: Vec<(LegacyCID, bool)>,
+}
+
+impl<IdentityId> From<RoundResult<IdentityId>> for RoundResultWithoutJustifications<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	fn from(old: RoundResult<IdentityId>) -> Self {
+		RoundResultWithoutJustifications {
+			winners: old.winners.into_iter().map(Into::into).collect(),
+			proposal_count: old.proposal_count,
+			proposal_votes: old.proposal_votes,
+			concern_count: old.concern_count,
+			concern_votes: old.concern_votes,
+			council_decisions: old.council_decisions.iter()
+				.map(|(cid, accepted)| (migrate_cid(cid), *accepted))
+				.collect(),
+		}
+	}
+}
+
+/// RoundResult before the council justified rejections
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct RoundResultWithoutJustifications<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	pub winners: Vec<crate::ProposalWinner<IdentityId>>,
+	pub proposal_count: u32,
+	pub proposal_votes: u32,
+	pub concern_count: u32,
+	pub concern_votes: u32,
+	pub council_decisions: Vec<(ProposalCID, bool)>,
+}
+
+impl<IdentityId> From<RoundResultWithoutJustifications<IdentityId>> for crate::RoundResult<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	fn from(old: RoundResultWithoutJustifications<IdentityId>) -> Self {
+		crate::RoundResult {
+			winners: old.winners,
+			proposal_count: old.proposal_count,
+			proposal_votes: old.proposal_votes,
+			concern_count: old.concern_count,
+			concern_votes: old.concern_votes,
+			council_decisions: old.council_decisions,
+			council_justifications: Vec::new(),
+		}
+	}
+}
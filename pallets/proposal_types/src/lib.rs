@@ -20,10 +20,166 @@ use sp_arithmetic::Permill;
 #[cfg(feature = "std")]
 use frame_support::serde::{Deserialize, Serialize};
 
+/// Types and conversions of the storage layout before the CIDs had a fixed length
+pub mod deprecated;
 
-// Important: Change Vec<u8> to a fixed length type (otherwise attackable)
-pub type ProposalCID = Vec<u8>;
+/// Length of the sha2-256 digest that identifies a document
+pub const CID_DIGEST_LENGTH: usize = 32;
+/// Multihash code of sha2-256
+const MULTIHASH_SHA2_256: u8 = 0x12;
+/// CID version 1 prefix
+const CID_VERSION_1: u8 = 0x01;
+/// Alphabet of base58btc, the encoding of CIDv0 strings
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// sha2-256 digest of the document, extracted from the CID
+pub type ProposalCID = [u8; CID_DIGEST_LENGTH];
 pub type ConcernCID = ProposalCID;
+/// Index of a full governance cycle (Propose -> ... -> VoteCouncil)
+pub type RoundIndex = u32;
+/// Unlinkable tag of an anonymous voter, unique per voter and round
+pub type Nullifier = [u8; 32];
+/// Category of a proposal, e.g. infrastructure or community
+pub type CategoryId = u8;
+
+/// ISO 639-1 language code, e.g. b"en"
+pub type LanguageCode = [u8; 2];
+
+/// Maximum number of items a single page of a list contains
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Stable ordering key of a document. Documents are ordered by round and submission index.
+#[derive(Copy, Clone, Debug, Decode, Default, Encode, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Cursor {
+	pub round: RoundIndex,
+	pub index: u32,
+}
+
+impl Cursor {
+	pub fn new(round: RoundIndex, index: u32) -> Self {
+		Cursor{round, index}
+	}
+}
+
+/// Contains one page of a list and the cursor to the last item if more items exist
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Page<Item> {
+	pub items: Vec<Item>,
+	pub next: Option<Cursor>,
+}
+
+impl<Item> Page<Item> {
+	/// Order the items by their cursor and return up to `limit` items located after `start`
+	pub fn paginate(mut items: Vec<(Cursor, Item)>, start: Option<Cursor>, limit: u32) -> Self {
+		let limit: usize = limit.min(MAX_PAGE_SIZE) as usize;
+		items.sort_by(|a, b| a.0.cmp(&b.0));
+
+		if let Some(start) = start {
+			items.retain(|(cursor, _)| *cursor > start);
+		}
+
+		let next: Option<Cursor> = if items.len() > limit && limit > 0 {
+			Some(items[limit - 1].0)
+		} else {
+			None
+		};
+		items.truncate(limit);
+
+		Page{items: items.into_iter().map(|(_, item)| item).collect(), next}
+	}
+}
+
+/// Human readable explanation of a dispatch error
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ErrorExplanation {
+	/// Incremented every time the explanation changes
+	pub version: u32,
+	/// Short UTF-8 message or CID of a document containing the explanation
+	pub message: Vec<u8>,
+}
+
+impl ErrorExplanation {
+	pub fn new(version: u32, message: Vec<u8>) -> Self {
+		ErrorExplanation{version, message}
+	}
+}
+
+/// Extract the sha2-256 digest from a binary CIDv0 (multihash) or CIDv1.
+/// Returns None if the bytes are not a CID with a sha2-256 multihash.
+pub fn cid_digest(cid: &[u8]) -> Option<ProposalCID> {
+	let multihash: &[u8] = match cid.first() {
+		// CIDv0: <multihash>
+		Some(&MULTIHASH_SHA2_256) => cid,
+		// CIDv1: <version><codec varint><multihash>
+		Some(&CID_VERSION_1) => {
+			let codec_length: usize = cid[1..].iter().position(|byte| byte & 0x80 == 0)? + 1;
+			// The codec must fit into an u64
+			if codec_length > 9 {
+				return None;
+			}
+			&cid[1 + codec_length..]
+		},
+		_ => return None,
+	};
+
+	// <hash function code><digest length><digest>
+	if multihash.len() != CID_DIGEST_LENGTH + 2 || multihash[0] != MULTIHASH_SHA2_256
+		|| multihash[1] as usize != CID_DIGEST_LENGTH
+	{
+		return None;
+	}
+
+	let mut digest: ProposalCID = [0u8; CID_DIGEST_LENGTH];
+	digest.copy_from_slice(&multihash[2..]);
+	Some(digest)
+}
+
+/// Encode a sha2-256 digest as CIDv0 string ("Qm..."), which IPFS nodes and gateways accept.
+pub fn cid_v0(digest: &ProposalCID) -> Vec<u8> {
+	let mut multihash: Vec<u8> = Vec::with_capacity(CID_DIGEST_LENGTH + 2);
+	multihash.push(MULTIHASH_SHA2_256);
+	multihash.push(CID_DIGEST_LENGTH as u8);
+	multihash.extend_from_slice(digest);
+
+	// Convert the big endian number to base 58, the digits are collected in little endian.
+	// The multihash never starts with a zero byte, therefore no leading '1' is required.
+	let mut digits: Vec<u8> = Vec::new();
+
+	for byte in multihash.iter() {
+		let mut carry: u32 = *byte as u32;
+
+		for digit in digits.iter_mut() {
+			carry += (*digit as u32) << 8;
+			*digit = (carry % 58) as u8;
+			carry /= 58;
+		}
+
+		while carry > 0 {
+			digits.push((carry % 58) as u8);
+			carry /= 58;
+		}
+	}
+
+	digits.iter().rev().map(|digit| BASE58_ALPHABET[*digit as usize]).collect()
+}
+
+/// Optional information about a proposal, which allows frontends to list proposals
+/// without fetching every document from IPFS
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalMetadata {
+	/// blake2-256 hash of the title
+	pub title_hash: Option<[u8; 32]>,
+	/// Category of the proposal
+	pub category: Option<CategoryId>,
+	/// Requested budget in the smallest unit of the currency
+	pub budget: Option<u128>,
+	/// sha2-256 digest of the CID of the discussion thread
+	pub discussion: Option<ProposalCID>,
+}
 
 /// Contains proposal and vote count
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
@@ -31,17 +187,20 @@ pub type ConcernCID = ProposalCID;
 pub struct Proposal {
 	pub proposal: ProposalCID,
 	pub votes: u32,
+	/// Submission index within the round
+	pub index: u32,
+	pub metadata: ProposalMetadata,
 }
 
 impl Proposal {
-	pub fn new(proposal: ProposalCID) -> Self {
-		Proposal{proposal, votes: 0}
+	pub fn new(proposal: ProposalCID, index: u32) -> Self {
+		Proposal{proposal, votes: 0, index, metadata: ProposalMetadata::default()}
 	}
 }
 
 impl Default for Proposal {
 	fn default() -> Self {
-		Proposal::new(ProposalCID::default())
+		Proposal::new(ProposalCID::default(), 0)
 	}
 }
 
@@ -52,17 +211,19 @@ pub struct Concern {
 	pub associated_proposal: ProposalCID,
 	pub concern: ConcernCID,
 	pub votes: u32,
+	/// Submission index within the round
+	pub index: u32,
 }
 
 impl Concern {
-	pub fn new(concern: ConcernCID, associated_proposal: ProposalCID) -> Self {
-		Concern{concern, associated_proposal, votes: 0}
+	pub fn new(concern: ConcernCID, associated_proposal: ProposalCID, index: u32) -> Self {
+		Concern{concern, associated_proposal, votes: 0, index}
 	}
 }
 
 impl Default for Concern {
 	fn default() -> Self {
-		Concern::new(ConcernCID::default(), ProposalCID::default())
+		Concern::new(ConcernCID::default(), ProposalCID::default(), 0)
 	}
 }
 
@@ -96,6 +257,153 @@ impl<IdentityId> Default for ProposalWinner<IdentityId> where
 	}
 }
 
+/// Outcome of a finished round
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoundResult<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	/// Winning proposals including the accepted concerns
+	pub winners: Vec<ProposalWinner<IdentityId>>,
+	/// Number of submitted proposals
+	pub proposal_count: u32,
+	/// Number of votes for proposals
+	pub proposal_votes: u32,
+	/// Number of submitted concerns
+	pub concern_count: u32,
+	/// Number of votes for concerns
+	pub concern_votes: u32,
+	/// Council decision for every winning proposal (true = project spawned)
+	pub council_decisions: Vec<(ProposalCID, bool)>,
+	/// Blocked proposal -> (Council member, sha2-256 digest of the justification CID)
+	/// of every rejecting vote
+	pub council_justifications: Vec<(ProposalCID, Vec<(IdentityId, ProposalCID)>)>,
+}
+
+impl<IdentityId> Default for RoundResult<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	fn default() -> Self {
+		RoundResult {
+			winners: Vec::new(),
+			proposal_count: 0,
+			proposal_votes: 0,
+			concern_count: 0,
+			concern_votes: 0,
+			council_decisions: Vec::new(),
+			council_justifications: Vec::new(),
+		}
+	}
+}
+
+/// Complete dataset of a finished round, written into the offchain index when the round ends
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoundDigest<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	pub round: RoundIndex,
+	pub result: RoundResult<IdentityId>,
+	/// Every proposal of the round and its proposer
+	pub proposals: Vec<(IdentityId, Proposal)>,
+	/// Every concern of the round and its submitter
+	pub concerns: Vec<(IdentityId, Concern)>,
+}
+
+/// Actions an identity can perform in the current phase
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Eligibility {
+	/// Can the identity submit a proposal or a concern?
+	pub can_submit: bool,
+	/// Does a submission require a deposit, because the identity level is too low?
+	pub requires_deposit: bool,
+	/// Can the identity vote?
+	pub can_vote: bool,
+	/// Remaining voice credits in the current voting phase
+	pub voice_credits: u32,
+}
+
+/// Overview of the current round. The documents of the round are fetched page-wise.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoundSnapshot<IdentityId, BlockNumber> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+	BlockNumber: Codec + Clone + Eq + Debug,
+{
+	pub round: RoundIndex,
+	pub state: States,
+	pub paused: bool,
+	/// Block of the next state transit
+	pub next_transit: BlockNumber,
+	/// Number of proposals of the round, see active_proposals
+	pub proposal_count: u32,
+	/// Number of concerns of the round, see active_concerns
+	pub concern_count: u32,
+	/// Winning proposals of the proposal voting phase
+	pub winners: Vec<ProposalWinner<IdentityId>>,
+	/// Actions the requesting identity can perform (None if no identity was supplied)
+	pub eligibility: Option<Eligibility>,
+}
+
+/// Aggregated governance participation of an identity
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GovernanceHistory {
+	pub proposals_submitted: u32,
+	pub proposals_accepted: u32,
+	pub votes_cast: u32,
+	pub concerns_raised: u32,
+	pub concerns_accepted: u32,
+	pub reviews_performed: u32,
+	pub penalties_received: u32,
+}
+
+/// Public information about a proposer, which allows frontends to show who submitted a
+/// proposal without external indexers
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposerInfo {
+	/// Effective identity level of the proposer
+	pub level: u8,
+	/// blake2-256 hash of the display handle
+	pub display_hash: Option<[u8; 32]>,
+	/// sha2-256 digest of the CID of the contact or profile document
+	pub contact: Option<[u8; 32]>,
+}
+
+/// Record of the proposal votes an identity cast during a round
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VoteReceipt {
+	/// Voted proposals and the number of votes they received from the identity,
+	/// in the order of preference for ranked ballots
+	pub votes: Vec<(ProposalCID, u32)>,
+	/// Were the votes adopted from a delegate?
+	pub delegated: bool,
+}
+
+impl VoteReceipt {
+	/// Aggregate the votes of a ballot into a receipt
+	pub fn new(ballot: &[ProposalCID], delegated: bool) -> Self {
+		let mut receipt = VoteReceipt { votes: Vec::new(), delegated };
+
+		for proposal in ballot.iter() {
+			receipt.add(*proposal, 1);
+		}
+
+		receipt
+	}
+
+	/// Add votes for a proposal to the receipt
+	pub fn add(&mut self, proposal: ProposalCID, votes: u32) {
+		match self.votes.iter_mut().find(|(cid, _)| *cid == proposal) {
+			Some(entry) => entry.1 = entry.1.saturating_add(votes),
+			None => self.votes.push((proposal, votes)),
+		}
+	}
+}
+
 /// Contains the five different states the pallet can be in
 #[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -113,3 +421,59 @@ impl Default for States {
         States::Uninitialized
     }
 }
+
+/// Conviction of a vote that is backed by locked stake. Higher convictions multiply the weight
+/// of the stake, but lock it for more rounds (including the round of the vote).
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Conviction {
+	/// 1x weight, locked for 1 round
+	Locked1x,
+	/// 2x weight, locked for 2 rounds
+	Locked2x,
+	/// 3x weight, locked for 4 rounds
+	Locked3x,
+	/// 4x weight, locked for 8 rounds
+	Locked4x,
+	/// 5x weight, locked for 16 rounds
+	Locked5x,
+	/// 6x weight, locked for 32 rounds
+	Locked6x,
+}
+
+impl Conviction {
+	/// Multiplier of the weight of the stake
+	pub fn multiplier(self) -> u32 {
+		match self {
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 3,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 5,
+			Conviction::Locked6x => 6,
+		}
+	}
+
+	/// How many rounds the stake stays locked, including the round of the vote
+	pub fn lock_rounds(self) -> RoundIndex {
+		1 << (self.multiplier() - 1)
+	}
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::Locked1x
+	}
+}
+
+/// Voting schemes a TallyAlgorithm can implement
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VotingScheme {
+	/// One vote per document, documents above the acceptance threshold win
+	SimpleThreshold,
+	/// Repeated votes for the same document cost quadratically more voice credits
+	Quadratic,
+	/// Voters rank proposals, the winner is determined by instant-runoff
+	RankedChoice,
+}
@@ -0,0 +1,146 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # pallet-reputation
+//! Accumulates a reputation score per identity for the participation in the governance.
+//! The score is credited or debited by other pallets through the Reputation trait, the amount
+//! every action earns or costs is configured by the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+use codec::{Decode, Encode};
+use frame_support::{decl_event, decl_module, decl_storage, Parameter,
+	sp_runtime::traits::{Member, Saturating}, traits::Get,
+};
+#[cfg(feature = "std")]
+use frame_support::serde::{Deserialize, Serialize};
+use crate::traits::Reputation;
+/// Public interface to the reputation registry
+pub mod traits;
+
+/// Accumulated reputation of an identity
+pub type Score = u64;
+
+/// Governance activity that earns reputation
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReputationAction {
+	/// Voted in a proposal or concern voting round
+	Voted,
+	/// Submitted a proposal that won a round
+	ProposalAccepted,
+	/// Submitted a concern that was accepted against a winning proposal
+	ConcernAccepted,
+	/// Performed a peer review that was confirmed by an audit
+	Reviewed,
+	/// Did not vote on an assigned peer review until it expired
+	MissedReview,
+}
+
+impl ReputationAction {
+	/// Does the action cost reputation instead of earning it?
+	pub fn is_penalty(&self) -> bool {
+		*self == ReputationAction::MissedReview
+	}
+}
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Identifier of the identities that earn reputation
+	type IdentityId: Parameter + Member + Default;
+
+	/// How much reputation does a vote earn?
+	type VoteScore: Get<Score>;
+
+	/// How much reputation does a winning proposal earn its proposer?
+	type AcceptedProposalScore: Get<Score>;
+
+	/// How much reputation does an accepted concern earn its submitter?
+	type AcceptedConcernScore: Get<Score>;
+
+	/// How much reputation does a peer review earn every reviewer once an audit confirmed it?
+	type ReviewScore: Get<Score>;
+
+	/// How much reputation does a reviewer lose for not voting on an assigned peer review?
+	type MissedReviewPenalty: Get<Score>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Reputation {
+		/// Identity -> Accumulated reputation
+		pub Scores get(fn scores): map hasher(identity) T::IdentityId => Score = 0;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where IdentityId = <T as Trait>::IdentityId {
+		/// An identity earned reputation \[Identity, Action, Score\]
+		ReputationEarned(IdentityId, ReputationAction, Score),
+		/// An identity lost reputation \[Identity, Action, Score\]
+		ReputationLost(IdentityId, ReputationAction, Score),
+	}
+);
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		/// How much reputation does a vote earn?
+		const VoteScore: Score = T::VoteScore::get();
+		/// How much reputation does a winning proposal earn its proposer?
+		const AcceptedProposalScore: Score = T::AcceptedProposalScore::get();
+		/// How much reputation does an accepted concern earn its submitter?
+		const AcceptedConcernScore: Score = T::AcceptedConcernScore::get();
+		/// How much reputation does a peer review earn every reviewer once an audit confirmed it?
+		const ReviewScore: Score = T::ReviewScore::get();
+		/// How much reputation does a reviewer lose for not voting on an assigned peer review?
+		const MissedReviewPenalty: Score = T::MissedReviewPenalty::get();
+
+		fn deposit_event() = default;
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Reputation an action earns or costs
+	fn action_score(action: ReputationAction) -> Score {
+		match action {
+			ReputationAction::Voted => T::VoteScore::get(),
+			ReputationAction::ProposalAccepted => T::AcceptedProposalScore::get(),
+			ReputationAction::ConcernAccepted => T::AcceptedConcernScore::get(),
+			ReputationAction::Reviewed => T::ReviewScore::get(),
+			ReputationAction::MissedReview => T::MissedReviewPenalty::get(),
+		}
+	}
+}
+
+impl<T: Trait> Reputation<T::IdentityId> for Module<T> {
+	fn record(identity: &T::IdentityId, action: ReputationAction) {
+		let amount: Score = Self::action_score(action);
+
+		if amount == 0 {
+			return;
+		}
+
+		if action.is_penalty() {
+			<Scores<T>>::mutate(identity, |score| *score = score.saturating_sub(amount));
+			Self::deposit_event(RawEvent::ReputationLost(identity.clone(), action, amount));
+		} else {
+			<Scores<T>>::mutate(identity, |score| *score = score.saturating_add(amount));
+			Self::deposit_event(RawEvent::ReputationEarned(identity.clone(), action, amount));
+		}
+	}
+
+	fn reputation_of(identity: &T::IdentityId) -> Score {
+		<Scores<T>>::get(identity)
+	}
+}
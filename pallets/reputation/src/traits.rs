@@ -0,0 +1,38 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{ReputationAction, Score};
+
+/// Interface of the reputation registry. Pallets credit the identities for their governance
+/// activity and gate privileges on the accumulated score.
+pub trait Reputation<IdentityId> {
+	/// Credit an identity with the reputation the action earns or debit the reputation it costs
+	fn record(identity: &IdentityId, action: ReputationAction);
+	/// Get the accumulated reputation of an identity
+	fn reputation_of(identity: &IdentityId) -> Score;
+
+	/// Did the identity accumulate at least `required` reputation?
+	fn has_reputation(identity: &IdentityId, required: Score) -> bool {
+		Self::reputation_of(identity) >= required
+	}
+}
+
+/// No reputation is tracked, every identity has a score of 0
+impl<IdentityId> Reputation<IdentityId> for () {
+	fn record(_identity: &IdentityId, _action: ReputationAction) {}
+
+	fn reputation_of(_identity: &IdentityId) -> Score {
+		0
+	}
+}
@@ -9,11 +9,11 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, ModuleId, MultiSignature,
+	transaction_validity::{TransactionPriority, TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -33,7 +33,7 @@ pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	traits::{Currency, KeyOwnerProofSystem, OnUnbalanced, Randomness},
 	weights::{
 		Weight, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -42,9 +42,11 @@ pub use frame_support::{
 
 /// Import custom pallets
 pub use pallet_community_identity;
+use pallet_community_identity::LevelRequirement;
 pub use pallet_council;
 pub use pallet_project;
 pub use pallet_proposal;
+pub use pallet_reputation;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -100,10 +102,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-superorganism"),
 	impl_name: create_runtime_str!("node-superorganism"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 2,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -287,22 +289,38 @@ impl pallet_scheduler::Trait for Runtime {
 
 
 parameter_types! {
-	const TwoYears: u32 = 63_115_200;
+	const TwoYears: BlockNumber = DAYS * 730;
 	// TODO: Change from test value to OneWeek
 	const OneWeek: BlockNumber = 10; // DAYS * 7 as BlockNumber;
 
 	/// How long is an identified user locked out from submitting proposals / concerns
-	/// for bad behaviour. Value in seconds.
-	pub const IdentifiedUserPenality: u32 = TwoYears::get();
+	/// for bad behaviour. Value in blocks.
+	pub const IdentifiedUserPenality: BlockNumber = TwoYears::get();
+	/// How long are the submission caps of an identity halved after its lockout expired?
+	/// Value in blocks.
+	pub const RecentPenaltyPeriod: BlockNumber = TwoYears::get();
+	/// How much reputation raises the submission caps of an identity by another multiple of
+	/// ProposeIdentifiedUserCap / ConcernIdentifiedUserCap? 0 disables the reputation bonus
+	pub const ReputationCapStep: pallet_reputation::Score = 500;
+	/// Up to which multiple of the submission caps can reputation raise them?
+	pub const ReputationCapMultiplier: u8 = 3;
 	/// Part 1.1: Proposal state configuration
-	// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
-	// pub const UserProposeFee : Balance = 100_000_000_000_000
+	/// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
+	pub const UserProposeFee: Balance = 100_000_000_000_000;
+	/// How many funds must be reserved as a bond when submitting a proposal?
+	pub const ProposeBond: Balance = 10_000_000_000_000;
+	/// How much budget can the metadata of a proposal request at most?
+	pub const MaxRequestedBudget: Balance = 1_000_000_000_000_000_000;
+	/// How much budget can the winning proposals of a round request in total?
+	pub const RoundBudget: Balance = 5_000_000_000_000_000_000;
 	/// How many proposals can be submitted per proposal round? (required for weight calculation)
 	pub const ProposeCap: u32 = 1_000;
 	/// How many proposals can an identified user submit per proposal round?
 	pub const ProposeIdentifiedUserCap: u8 =  1;
 	/// Which identity level is required to create a proposal?
 	pub const ProposeIdentityLevel: u8 = 2;
+	/// How many proposal categories exist? Categories are numbered from 0.
+	pub const ProposalCategories: u8 = 8;
 	/// How high is the reward (%) for the proposer if the proposal is converted into a project?
 	pub const ProposeReward: Permill = Permill::from_percent(5);
 	/// How long can proposals be submitted? Value in blocks.
@@ -316,6 +334,21 @@ parameter_types! {
 	pub const ProposeVoteIdentityLevel: u8 = 3;
 	/// How many votes can each identified user (with an appropriate identity level) submit?
 	pub const ProposeVoteMaxPerIdentifiedUser: u16 = 3;
+	/// How many proposals can a ranked ballot contain? Only used by the RankedChoice scheme.
+	pub const MaxRankedChoices: u32 = 5;
+	/// How many delegations are followed at most to find the delegate that votes for an identity?
+	pub const MaxDelegationDepth: u32 = 5;
+	/// How many identities can a delegate represent at most, directly or through other delegates?
+	pub const MaxDelegators: u32 = 100;
+	/// During how many blocks before the end of the VotePropose phase are committed votes revealed?
+	/// 0 disables commit-reveal voting.
+	pub const RevealPeriod: BlockNumber = 0;
+	/// During how many blocks before the end of a voting phase must proposers not vote for their
+	/// own proposals? 0 disables the quiet period.
+	pub const ProposerQuietPeriod: BlockNumber = 2;
+	/// How much stake must be locked for one additional vote (before the conviction multiplier)?
+	/// 0 disables conviction voting.
+	pub const ConvictionVoteUnit: Balance = 10_000_000_000_000;
 	/// How high is the reward if a proposal that the user voted for passes into next round?
 	pub const ProposeVoteCorrectReward: Balance = 100_000_000_000;
 	/// Part 2.1: Concern state configuration
@@ -329,8 +362,8 @@ parameter_types! {
 	pub const ConcernReward: Balance = 10_000_000_000_000;
 	/// How long can concerns be submitted? Value in blocks.
 	pub const ConcernRoundDuration: BlockNumber = OneWeek::get();
-	// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
-	// pub const UserConcernFee: Balance = 10_000_000_000_000;
+	/// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
+	pub const UserConcernFee: Balance = 10_000_000_000_000;
 	/// How many votes (ratio) does a concern require to be accepted for the next round?
 	pub const ConcernVoteAcceptanceMin: Permill = Permill::from_percent(3);
 	/// Part 2.2: Concern voting state configuration
@@ -343,12 +376,33 @@ parameter_types! {
 	pub const ConcernVoteMaxPerIdentifiedUser: u16 = 3;
 	/// How high is the reward if a concern that the user voted for passes into next round?
 	pub const ConcernVoteCorrectReward: Balance = 10_000_000_000;
+	/// How many accrued rewards are paid out per block after a voting round?
+	pub const RewardBatchSize: u32 = 100;
+	/// How many voters are evaluated per block after a voting round?
+	pub const TallyBatchSize: u32 = 500;
+	/// How many results of finished rounds are kept in the round history?
+	pub const HistoryDepth: u32 = 52;
+	/// How many bytes may an error explanation contain?
+	pub const MaxErrorExplanationLength: u32 = 256;
+	/// Priority of the availability attestations submitted by the off-chain worker
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	/// Must the off-chain worker attest that a proposal is retrievable on IPFS before
+	/// it can receive votes?
+	pub const ExcludeUnavailableProposals: bool = false;
+	/// How many non-transferable voice credits does every identity receive per voting round?
+	pub const VoiceCreditsPerRound: u32 = 9;
 	/// Part 3: Final evaluation of the winning proposals and associated concern by the council
 	/// How much time is reserved for the council to vote? Value in blocks.
 	pub const CouncilVoteRoundDuration: BlockNumber = OneWeek::get();
 	/// How many percent of the council must agree that a concern is too serious to launch a
 	/// project from the associated proposal?
 	pub const CouncilAcceptConcernMinVotes: Permill = Permill::from_percent(85);
+	/// How many percent of the council must have voted on every poll to end the council vote
+	/// early, given that a supermajority was achieved?
+	pub const CouncilEarlyTransitQuorum: Permill = Permill::from_percent(75);
+	/// How many percent of the votes must agree on the outcome of every poll to end the
+	/// council vote early, given that the quorum was achieved?
+	pub const CouncilEarlyTransitSupermajority: Permill = Permill::from_percent(90);
 }
 
 /// Configure the proposal pallet
@@ -361,51 +415,266 @@ impl pallet_proposal::Trait for Runtime {
 	type Proposal = Call;
 	type Identity = pallet_community_identity::Module<Runtime>;
 	type Council = pallet_council::Module<Runtime>;
+	type Reputation = pallet_reputation::Module<Runtime>;
 	type Project = pallet_project::Module<Runtime>;
+	type TreasuryModuleId = TreasuryId;
+	type CouncilOrigin = EnsureRoot<AccountId>;
+	// Use pallet_proposal::tally::Quadratic for quadratic voting with VoiceCreditsPerRound credits
+	// or pallet_proposal::tally::RankedChoice for instant-runoff voting with ranked ballots
+	type TallyAlgorithm = pallet_proposal::tally::SimpleThreshold;
+	// Anonymous ballots require a verifier of blind-signed voting tokens or zero-knowledge proofs
+	type AnonymityProvider = ();
+	type WeightInfo = ();
 
 	// Parameters
 	type IdentifiedUserPenality = IdentifiedUserPenality;
-	// type UserProposeFee = Get<Balance<Self>>;
+	type RecentPenaltyPeriod = RecentPenaltyPeriod;
+	type ReputationCapStep = ReputationCapStep;
+	type ReputationCapMultiplier = ReputationCapMultiplier;
+	type UserProposeFee = UserProposeFee;
+	type ProposeBond = ProposeBond;
+	type MaxRequestedBudget = MaxRequestedBudget;
+	type RoundBudget = RoundBudget;
 	type ProposeCap = ProposeCap;
 	type ProposeIdentifiedUserCap = ProposeIdentifiedUserCap;
 	type ProposeIdentityLevel = ProposeIdentityLevel;
+	type ProposalCategories = ProposalCategories;
 	type ProposeReward = ProposeReward;
 	type ProposeRoundDuration = ProposeRoundDuration;
 	type ProposeVoteAcceptanceMin = ProposeVoteAcceptanceMin;
 	type ProposeVoteDuration = ProposeVoteDuration;
 	type ProposeVoteIdentityLevel = ProposeVoteIdentityLevel;
 	type ProposeVoteMaxPerIdentifiedUser = ProposeVoteMaxPerIdentifiedUser;
+	type MaxRankedChoices = MaxRankedChoices;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type MaxDelegators = MaxDelegators;
+	type RevealPeriod = RevealPeriod;
+	type ProposerQuietPeriod = ProposerQuietPeriod;
+	type ConvictionVoteUnit = ConvictionVoteUnit;
 	type ProposeVoteCorrectReward = ProposeVoteCorrectReward;
 	type ConcernCap = ConcernCap;
 	type ConcernIdentifiedUserCap = ConcernIdentifiedUserCap;
 	type ConcernIdentityLevel = ConcernIdentityLevel;
 	type ConcernReward = ConcernReward;
 	type ConcernRoundDuration = ConcernRoundDuration;
-	// type UserConcernFee = UserConcernFee;
+	type UserConcernFee = UserConcernFee;
 	type ConcernVoteAcceptanceMin = ConcernVoteAcceptanceMin;
 	type ConcernVoteDuration = ConcernVoteDuration;
 	type ConcernVoteIdentityLevel = ConcernVoteIdentityLevel;
 	type ConcernVoteMaxPerIdentifiedUser = ConcernVoteMaxPerIdentifiedUser;
 	type ConcernVoteCorrectReward = ConcernVoteCorrectReward;
+	type RewardBatchSize = RewardBatchSize;
+	type TallyBatchSize = TallyBatchSize;
+	type HistoryDepth = HistoryDepth;
+	type MaxErrorExplanationLength = MaxErrorExplanationLength;
+	type UnsignedPriority = UnsignedPriority;
+	type AuthorityId = pallet_proposal::crypto::AuthorityId;
+	type ExcludeUnavailableProposals = ExcludeUnavailableProposals;
+	type VoiceCreditsPerRound = VoiceCreditsPerRound;
 	type CouncilVoteRoundDuration = CouncilVoteRoundDuration;
 	type CouncilAcceptConcernMinVotes = CouncilAcceptConcernMinVotes;
+	type CouncilEarlyTransitQuorum = CouncilEarlyTransitQuorum;
+	type CouncilEarlyTransitSupermajority = CouncilEarlyTransitSupermajority;
+}
+
+/// Submit the unsigned transactions of the off-chain workers
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where Call: From<C> {
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+/// Sign the payloads of the unsigned transactions of the off-chain workers
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+parameter_types! {
+	/// Identifier of the governance pot that pays the rewards and funds the projects
+	pub const TreasuryId: ModuleId = ModuleId(*b"so/trsry");
+	/// How many blocks after the spawn of a project is its deadline?
+	pub const ProjectDuration: BlockNumber = 12 * OneWeek::get();
+	/// Into how many milestones can the budget of a project be split at most?
+	pub const MaxMilestones: u32 = 10;
+}
+
+/// Deposits slashed funds into the governance pot
+pub struct SlashToTreasury;
+
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for SlashToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Runtime>) {
+		Balances::resolve_creating(&TreasuryId::get().into_account(), amount);
+	}
 }
 
 /// Configure the project pallet
 impl pallet_project::Trait for Runtime {
+	// Project budgets can be denominated in another asset by using another ReservableCurrency
 	type Currency = pallet_balances::Module<Runtime>;
 	type Event = Event;
 	type Identity = pallet_community_identity::Module<Runtime>;
+	type CouncilOrigin = EnsureRoot<AccountId>;
+	type TreasuryModuleId = TreasuryId;
+	type ProjectDuration = ProjectDuration;
+	type MaxMilestones = MaxMilestones;
+	// The identified users confirm community milestones with the voting rules of the proposal pallet
+	type MilestoneConfirmation = pallet_proposal::Module<Runtime>;
+	// Completed projects reward their proposer with ProposeReward of the budget
+	type OnCompleted = pallet_proposal::Module<Runtime>;
+}
+
+parameter_types! {
+	/// Which identity level can be granted at most without peer review?
+	pub const MaxBootstrapLevel: u8 = 3;
+	/// How many blocks does an audit era last?
+	pub const AuditEraLength: BlockNumber = OneWeek::get();
+	/// How many reviews can be pulled for an audit per era?
+	pub const MaxAuditsPerEra: u32 = 10;
+	/// How much must reviewers bond? The bond is slashed if they approved a fraudulent review.
+	pub const ReviewerStake: Balance = 100_000_000_000;
+	/// How many blocks must pass after unbonding until reviewers can withdraw their bond?
+	pub const ReviewerUnbondingPeriod: BlockNumber = 4 * OneWeek::get();
+	/// How many blocks must pass after a rejected review until a new review can be requested?
+	pub const ReviewCooldown: BlockNumber = OneWeek::get();
+	/// How many blocks can the assigned reviewers vote on a review?
+	pub const ReviewTimeout: BlockNumber = OneWeek::get();
+	/// How much does the subject reserve while its review is open?
+	pub const ReviewDeposit: Balance = 10_000_000_000;
+	/// How many blocks must pass between two review requests of the same identity?
+	pub const ReviewRequestInterval: BlockNumber = OneWeek::get();
+	/// How many open reviews can await the vote of a reviewer at once?
+	pub const MaxPendingReviews: u32 = 20;
+	/// How much does the subject reserve to appeal a rejected review?
+	pub const ReviewAppealDeposit: Balance = 50_000_000_000;
+	/// How much does every reviewer that voted on a concluded review receive from the governance pot?
+	pub const ReviewerReward: Balance = 10_000_000_000;
+	/// How many reviewers participate in a peer review at most? (required for weight calculation)
+	pub const MaxReviewers: u32 = 10;
+	/// How many reviews can a reviewer approve in a single batch?
+	pub const MaxApprovalBatch: u32 = 50;
+	/// Priority of the regional reviewer assignments submitted by the off-chain worker
+	pub const RegionalAssignmentPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	/// Reviewers, minimum reviewer level and cooldown after the previous upgrade for the levels
+	/// 1 to 4. Higher levels need more and more trusted reviewers and are reached more slowly.
+	pub LevelRequirements: Vec<LevelRequirement<BlockNumber>> = vec![
+		LevelRequirement { reviewers: 3, min_reviewer_level: 2, upgrade_cooldown: 0 },
+		LevelRequirement { reviewers: 5, min_reviewer_level: 3, upgrade_cooldown: OneWeek::get() },
+		LevelRequirement { reviewers: 7, min_reviewer_level: 4, upgrade_cooldown: 4 * OneWeek::get() },
+		LevelRequirement { reviewers: 10, min_reviewer_level: 5, upgrade_cooldown: 12 * OneWeek::get() },
+	];
+	/// How many secondary addresses (e.g. hot wallets) can be linked to an identity?
+	pub const MaxLinkedAddresses: u32 = 5;
+	/// How many guardians can an identity appoint for its recovery?
+	pub const MaxGuardians: u32 = 10;
+	/// How many blocks must pass after the guardians approved a recovery until it can be finalized?
+	pub const RecoveryDelay: BlockNumber = OneWeek::get();
+	/// How many percent of the assigned reviewers must approve a review to grant the level?
+	pub const ReviewApprovalThreshold: Permill = Permill::from_percent(66);
+	/// How many blocks is an identity level valid until it decays and has to be re-verified?
+	pub const IdentityValidityPeriod: BlockNumber = 52 * OneWeek::get();
+	/// How many blocks before the expiry are users notified to request a re-verification?
+	pub const ReverificationNotice: BlockNumber = 4 * OneWeek::get();
+	/// How many reviewers with a higher level must support the revocation of an identity?
+	pub const RevocationQuorum: u32 = 3;
+	/// How many blocks can the subject of a revocation appeal it?
+	pub const AppealPeriod: BlockNumber = 2 * OneWeek::get();
 }
 
 /// Configure the community_identity pallet
 impl pallet_community_identity::Trait for Runtime {
+	type Event = Event;
 	type Timestamp = Moment;
+	type Time = Timestamp;
+	type Randomness = RandomnessCollectiveFlip;
+	type MaxBootstrapLevel = MaxBootstrapLevel;
+	type Currency = pallet_balances::Module<Runtime>;
+	type Auditors = pallet_council::Module<Runtime>;
+	type CouncilOrigin = EnsureRoot<AccountId>;
+	type RevocationQuorum = RevocationQuorum;
+	type AppealPeriod = AppealPeriod;
+	type AuditEraLength = AuditEraLength;
+	type MaxAuditsPerEra = MaxAuditsPerEra;
+	type ReviewerStake = ReviewerStake;
+	type ReviewerUnbondingPeriod = ReviewerUnbondingPeriod;
+	type ReviewCooldown = ReviewCooldown;
+	type ReviewTimeout = ReviewTimeout;
+	type ReviewDeposit = ReviewDeposit;
+	type ReviewRequestInterval = ReviewRequestInterval;
+	type MaxPendingReviews = MaxPendingReviews;
+	type ReviewAppealDeposit = ReviewAppealDeposit;
+	type TreasuryModuleId = TreasuryId;
+	type ReviewerReward = ReviewerReward;
+	type MaxReviewers = MaxReviewers;
+	type MaxApprovalBatch = MaxApprovalBatch;
+	type UnsignedPriority = RegionalAssignmentPriority;
+	type LevelRequirements = LevelRequirements;
+	type MaxLinkedAddresses = MaxLinkedAddresses;
+	type MaxGuardians = MaxGuardians;
+	type RecoveryDelay = RecoveryDelay;
+	type ReviewApprovalThreshold = ReviewApprovalThreshold;
+	type IdentityValidityPeriod = IdentityValidityPeriod;
+	type ReverificationNotice = ReverificationNotice;
+	type Reputation = pallet_reputation::Module<Runtime>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	/// How much has to be reserved to become a council candidate?
+	pub const CandidacyBond: Balance = 10_000_000_000_000;
+	/// How many percent of the votes must a candidate receive to get the full bond back?
+	pub const CandidacyApprovalFloor: Permill = Permill::from_percent(5);
+	/// How many percent of the bond of a candidate below the approval floor are slashed?
+	pub const CandidacyBondSlash: Permill = Permill::from_percent(50);
+	/// How many members can the council have at most?
+	pub const MaxCouncilMembers: u32 = 21;
+	/// Which identity level is required to nominate yourself and to vote in council elections?
+	pub const ElectionIdentityLevel: u8 = 3;
+	/// How many identities can vote in a council election?
+	pub const MaxElectionVoters: u32 = 10_000;
+	/// After how many proposal rounds is the council elected?
+	pub const ElectionRounds: u32 = 4;
+	/// How many proposal rounds does the term of a council member last?
+	pub const TermRounds: u32 = 8;
 }
 
 /// Configure the community_identity pallet
 impl pallet_council::Trait for Runtime {
+	type Event = Event;
 	type Identity = pallet_community_identity::Module<Runtime>;
+	type Currency = pallet_balances::Module<Runtime>;
+	type CandidacyBond = CandidacyBond;
+	type CandidacyApprovalFloor = CandidacyApprovalFloor;
+	type CandidacyBondSlash = CandidacyBondSlash;
+	type SlashedCandidacyBonds = SlashToTreasury;
+	type MaxMembers = MaxCouncilMembers;
+	type ElectionIdentityLevel = ElectionIdentityLevel;
+	type MaxVoters = MaxElectionVoters;
+	type ElectionRounds = ElectionRounds;
+	type TermRounds = TermRounds;
+}
+
+parameter_types! {
+	/// How much reputation does a vote earn?
+	pub const VoteScore: pallet_reputation::Score = 1;
+	/// How much reputation does a winning proposal earn its proposer?
+	pub const AcceptedProposalScore: pallet_reputation::Score = 50;
+	/// How much reputation does an accepted concern earn its submitter?
+	pub const AcceptedConcernScore: pallet_reputation::Score = 20;
+	/// How much reputation does a peer review earn every reviewer once an audit confirmed it?
+	pub const ReviewScore: pallet_reputation::Score = 10;
+	/// How much reputation does a reviewer lose for not voting on an assigned peer review?
+	pub const MissedReviewPenalty: pallet_reputation::Score = 5;
+}
+
+/// Configure the reputation pallet
+impl pallet_reputation::Trait for Runtime {
+	type Event = Event;
+	type IdentityId = pallet_community_identity::IdentityId<Runtime>;
+	type VoteScore = VoteScore;
+	type AcceptedProposalScore = AcceptedProposalScore;
+	type AcceptedConcernScore = AcceptedConcernScore;
+	type ReviewScore = ReviewScore;
+	type MissedReviewPenalty = MissedReviewPenalty;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -425,10 +694,11 @@ construct_runtime!(
 		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Custom pallets
-		CommunityIdentity: pallet_community_identity::{Module, Call},
-		Council: pallet_council::{Module, Call, Storage},
+		CommunityIdentity: pallet_community_identity::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned},
+		Council: pallet_council::{Module, Call, Storage, Event<T>, Config<T>},
 		Project: pallet_project::{Module, Call, Storage, Event<T>},
-		Proposal: pallet_proposal::{Module, Call, Storage, Event<T>, Config},
+		Proposal: pallet_proposal::{Module, Call, Storage, Event<T>, Config, ValidateUnsigned},
+		Reputation: pallet_reputation::{Module, Call, Storage, Event<T>},
 
 	}
 );
@@ -451,7 +721,8 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>
+	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_community_identity::check_review_request::CheckReviewRequest<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -590,6 +861,111 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_proposal_rpc_runtime_api::ProposalApi<Block, AccountId, BlockNumber, AccountId> for Runtime {
+		fn current_state() -> pallet_proposal_rpc_runtime_api::States {
+			Proposal::state()
+		}
+
+		fn current_round() -> pallet_proposal_rpc_runtime_api::RoundIndex {
+			Proposal::round()
+		}
+
+		fn next_transit() -> BlockNumber {
+			Proposal::next_transit()
+		}
+
+		fn active_proposals(
+			start: Option<pallet_proposal_rpc_runtime_api::Cursor>,
+			limit: u32,
+		) -> pallet_proposal_rpc_runtime_api::Page<(AccountId, pallet_proposal_rpc_runtime_api::Proposal)> {
+			Proposal::active_proposals(start, limit)
+		}
+
+		fn active_concerns(
+			start: Option<pallet_proposal_rpc_runtime_api::Cursor>,
+			limit: u32,
+		) -> pallet_proposal_rpc_runtime_api::Page<(AccountId, pallet_proposal_rpc_runtime_api::Concern)> {
+			Proposal::active_concerns(start, limit)
+		}
+
+		fn winners(
+			round: pallet_proposal_rpc_runtime_api::RoundIndex,
+		) -> Vec<pallet_proposal_rpc_runtime_api::ProposalWinner<AccountId>> {
+			Proposal::winners_of(round)
+		}
+
+		fn voter_eligibility(account: AccountId) -> pallet_proposal_rpc_runtime_api::Eligibility {
+			Proposal::voter_eligibility(&account)
+		}
+
+		fn history_of(identity: AccountId) -> pallet_proposal_rpc_runtime_api::GovernanceHistory {
+			Proposal::history_of(identity)
+		}
+
+		fn proposer_info(identity: AccountId) -> pallet_proposal_rpc_runtime_api::ProposerInfo {
+			Proposal::proposer_info(identity)
+		}
+
+		fn open_concerns_of(
+			identity: AccountId,
+			start: Option<pallet_proposal_rpc_runtime_api::Cursor>,
+			limit: u32,
+		) -> pallet_proposal_rpc_runtime_api::Page<pallet_proposal_rpc_runtime_api::Concern> {
+			Proposal::open_concerns_of(identity, start, limit)
+		}
+
+		fn round_result(
+			round: pallet_proposal_rpc_runtime_api::RoundIndex,
+		) -> pallet_proposal_rpc_runtime_api::RoundResult<AccountId> {
+			Proposal::round_history(round)
+		}
+
+		fn proposal_by_cid(
+			proposal: pallet_proposal_rpc_runtime_api::ProposalCID,
+		) -> Option<(AccountId, pallet_proposal_rpc_runtime_api::Cursor)> {
+			Proposal::proposal_by_cid(proposal)
+		}
+
+		fn error_explanation(
+			module: u8,
+			error: u8,
+			language: pallet_proposal_rpc_runtime_api::LanguageCode,
+		) -> Option<pallet_proposal_rpc_runtime_api::ErrorExplanation> {
+			Proposal::error_explanation((module, error, language))
+		}
+
+		fn vote_receipt(
+			identity: AccountId,
+			round: pallet_proposal_rpc_runtime_api::RoundIndex,
+		) -> Option<pallet_proposal_rpc_runtime_api::VoteReceipt> {
+			Proposal::vote_receipt(round, identity)
+		}
+
+		fn verify_vote(
+			identity: AccountId,
+			round: pallet_proposal_rpc_runtime_api::RoundIndex,
+			proposal: pallet_proposal_rpc_runtime_api::ProposalCID,
+		) -> bool {
+			Proposal::verify_vote(identity, round, proposal)
+		}
+
+		fn round_snapshot(
+			identity: Option<AccountId>,
+		) -> pallet_proposal_rpc_runtime_api::RoundSnapshot<AccountId, BlockNumber> {
+			Proposal::round_snapshot(identity)
+		}
+	}
+
+	impl pallet_community_identity_rpc_runtime_api::CommunityIdentityApi<
+		Block,
+		AccountId,
+		pallet_community_identity::TicketId,
+	> for Runtime {
+		fn pending_reviews_of(reviewer: AccountId) -> Vec<pallet_community_identity::TicketId> {
+			CommunityIdentity::pending_by_reviewer(reviewer)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -619,6 +995,8 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_community_identity, CommunityIdentity);
+			add_benchmark!(params, batches, pallet_proposal, Proposal);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)